@@ -0,0 +1,166 @@
+//! This module define [`AsyncParse`] trait for parsers which do network I/O (e.g. HTTP, Vault, etcd), so they could
+//! be awaited instead of blocking the runtime (see [`ConfigBuilder::load_async`] and [`Config::reload_async`]).
+//! The existing synchronous parsers could be appended as is (they will be called in place) or could be adapted via
+//! [`Blocking`] wrapper to be called on [tokio](https://tokio.rs/) blocking thread pool.
+//!
+//! To enable that functionality one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["async"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::asynchronous::{AsyncParse, Blocking, ParseFuture};
+//! use irx_config::parsers::json;
+//! use irx_config::{AnyResult, Case, ConfigBuilder, Value};
+//!
+//! struct VaultParser;
+//!
+//! impl Case for VaultParser {}
+//!
+//! impl AsyncParse for VaultParser {
+//!     fn parse<'a>(&'a mut self, _value: &'a Value) -> ParseFuture<'a> {
+//!         Box::pin(async move { fetch_secrets().await })
+//!     }
+//!
+//!     fn is_remote(&self, _value: &Value) -> bool {
+//!         true
+//!     }
+//! }
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_async_parser(VaultParser)
+//!     .append_async_parser(Blocking::new(
+//!         json::ParserBuilder::default()
+//!             .default_path("config.json")
+//!             .build()?,
+//!     ))
+//!     .load_async()
+//!     .await?;
+//! ```
+//!
+//! [`ConfigBuilder::load_async`]: crate::ConfigBuilder::load_async
+//! [`Config::reload_async`]: crate::Config::reload_async
+
+use crate::{AnyResult, Case, Parse, Value};
+use std::{future::Future, path::PathBuf, pin::Pin};
+
+/// The future returned by [`AsyncParse::parse`] method.
+pub type ParseFuture<'a> = Pin<Box<dyn Future<Output = AnyResult<Value>> + 'a>>;
+
+/// A data structure that can be parsed asynchronously.
+pub trait AsyncParse: Case {
+    /// Parse data to [`Value`] structure asynchronously. The `value` parameter has the same meaning as for
+    /// [`Parse::parse`] method.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur during parsing then error will be returned.
+    fn parse<'a>(&'a mut self, value: &'a Value) -> ParseFuture<'a>;
+
+    /// Same as [`Parse::is_remote`].
+    #[inline]
+    fn is_remote(&self, _value: &Value) -> bool {
+        false
+    }
+
+    /// Same as [`Parse::watch_paths`].
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Same as [`Parse::depends_on`].
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Same as [`Parse::select_profile`].
+    #[inline]
+    fn select_profile(&mut self, _profile: &str) {}
+}
+
+/// The wrapper which adapts any synchronous parser to [`AsyncParse`] trait, the wrapped parser is called on tokio
+/// blocking thread pool.
+pub struct Blocking<P> {
+    parser: Option<P>,
+    case_on: bool,
+}
+
+impl<P: Parse> Blocking<P> {
+    /// Create [`Blocking`] wrapper for given parser.
+    pub fn new(parser: P) -> Self {
+        let case_on = parser.is_case_sensitive();
+        Self {
+            parser: Some(parser),
+            case_on,
+        }
+    }
+
+    /// Get wrapped parser back. If the parser was lost because of panic during parsing then `None` will be returned.
+    #[inline]
+    pub fn into_inner(self) -> Option<P> {
+        self.parser
+    }
+}
+
+impl<P> Case for Blocking<P> {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.case_on
+    }
+}
+
+impl<P> AsyncParse for Blocking<P>
+where
+    P: Parse + Send + 'static,
+{
+    fn parse<'a>(&'a mut self, value: &'a Value) -> ParseFuture<'a> {
+        Box::pin(async move {
+            let mut parser = self
+                .parser
+                .take()
+                .ok_or("Parser was lost because of panic during previous parsing")?;
+            let value = value.clone();
+            let (parser, result) = tokio::task::spawn_blocking(move || {
+                let result = parser.parse(&value);
+                (parser, result)
+            })
+            .await?;
+            self.parser = Some(parser);
+            result
+        })
+    }
+
+    #[inline]
+    fn is_remote(&self, value: &Value) -> bool {
+        self.parser.as_ref().is_some_and(|p| p.is_remote(value))
+    }
+
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.parser
+            .as_ref()
+            .map(Parse::watch_paths)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        self.parser
+            .as_ref()
+            .map(Parse::depends_on)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn select_profile(&mut self, profile: &str) {
+        if let Some(ref mut parser) = self.parser {
+            parser.select_profile(profile);
+        }
+    }
+}
@@ -1,9 +1,41 @@
 //! This module define main configuration structures: [`Config`] and [`ConfigBuilder`].
 
-use crate::{AnyParser, Error, MergeCase, Parse, Result, Value, DEFAULT_KEYS_SEPARATOR};
-use serde::de::DeserializeOwned;
+#[cfg(feature = "golden")]
+use crate::diff::ValueDiff;
+#[cfg(feature = "export")]
+use crate::export::Record;
+#[cfg(feature = "golden")]
+use crate::golden::GoldenOptions;
+#[cfg(feature = "lint")]
+use crate::lint::{Finding, Linter};
+#[cfg(feature = "locale")]
+use crate::locale::Locale;
+#[cfg(feature = "render")]
+use crate::render::Format;
+#[cfg(feature = "schema")]
+use crate::schema::Schema;
+#[cfg(feature = "async")]
+use crate::{asynchronous::AsyncParse, AnyAsyncParser};
+use crate::{
+    lease::Lease,
+    value::{MergeRules, SerdeError},
+    AnyParser, AnyResult, ArrayMerge, Case, CowString, Error, MergeCase, MergeStrategy, Parse,
+    Result, Value, DEFAULT_KEYS_SEPARATOR, DEFAULT_PROFILES_KEY, DEFAULT_TEMPLATE_KEY,
+};
+use arc_swap::ArcSwap;
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::Value as InnerValue;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant, SystemTime},
+};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "blake2b")] {
@@ -17,7 +49,7 @@ cfg_if::cfg_if! {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 struct Hash(HashModule::Hash);
 
 impl Hash {
@@ -25,6 +57,11 @@ impl Hash {
     fn as_bytes(&self) -> &[u8] {
         self.0.as_bytes()
     }
+
+    #[inline]
+    fn named(&self) -> String {
+        [HASH_NAME, ": ", &self.to_string()].concat()
+    }
 }
 
 impl From<&[u8]> for Hash {
@@ -48,212 +85,2478 @@ impl Display for Hash {
     }
 }
 
+enum Source {
+    Sync(AnyParser),
+    #[cfg(feature = "async")]
+    Async(AnyAsyncParser),
+}
+
+impl Source {
+    fn is_case_sensitive(&self) -> bool {
+        match self {
+            Self::Sync(p) => p.is_case_sensitive(),
+            #[cfg(feature = "async")]
+            Self::Async(p) => p.is_case_sensitive(),
+        }
+    }
+
+    fn is_remote(&self, value: &Value) -> bool {
+        match self {
+            Self::Sync(p) => p.is_remote(value),
+            #[cfg(feature = "async")]
+            Self::Async(p) => p.is_remote(value),
+        }
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        match self {
+            Self::Sync(p) => p.watch_paths(),
+            #[cfg(feature = "async")]
+            Self::Async(p) => p.watch_paths(),
+        }
+    }
+
+    fn depends_on(&self) -> Vec<String> {
+        match self {
+            Self::Sync(p) => p.depends_on(),
+            #[cfg(feature = "async")]
+            Self::Async(p) => p.depends_on(),
+        }
+    }
+
+    fn leases(&self) -> Vec<Lease> {
+        match self {
+            Self::Sync(p) => p.leases(),
+            #[cfg(feature = "async")]
+            Self::Async(_) => Vec::new(),
+        }
+    }
+
+    fn sealed_keys(&self) -> Vec<Vec<String>> {
+        match self {
+            Self::Sync(p) => p.sealed_keys(),
+            #[cfg(feature = "async")]
+            Self::Async(_) => Vec::new(),
+        }
+    }
+
+    fn renew(&mut self, lease: &Lease) -> AnyResult<Option<Lease>> {
+        match self {
+            Self::Sync(p) => p.renew(lease),
+            #[cfg(feature = "async")]
+            Self::Async(_) => Ok(None),
+        }
+    }
+
+    fn select_profile(&mut self, profile: &str) {
+        match self {
+            Self::Sync(p) => p.select_profile(profile),
+            #[cfg(feature = "async")]
+            Self::Async(p) => p.select_profile(profile),
+        }
+    }
+}
+
+/// The parser source of [`Config`] with the results of its last (re)load.
+pub struct Layer {
+    name: String,
+    number: usize,
+    parser: Source,
+    depends_on: Vec<String>,
+    raw_value: Value,
+    last_value: Value,
+    sealed_suffix: String,
+    fingerprint: Hash,
+    case_on: bool,
+    loaded_at: Option<Instant>,
+    leases: Vec<(Lease, Instant)>,
+}
+
+impl Layer {
+    fn new(name: String, number: usize, parser: Source, sealed_suffix: &str) -> Self {
+        let raw_value = Value::default();
+        let fingerprint = Hash::from(raw_value.as_bytes().as_ref());
+        let case_on = parser.is_case_sensitive();
+        Self {
+            name,
+            number,
+            parser,
+            depends_on: Vec::new(),
+            last_value: raw_value.clone(),
+            raw_value,
+            sealed_suffix: sealed_suffix.to_string(),
+            fingerprint,
+            case_on,
+            loaded_at: None,
+            leases: Vec::new(),
+        }
+    }
+
+    fn parse(&mut self, value: &Value) -> Result<Value> {
+        let result = match self.parser {
+            Source::Sync(ref mut p) => p.parse(value),
+            #[cfg(feature = "async")]
+            Source::Async(_) => return Err(Error::AsyncParser(self.number)),
+        };
+        self.update(result)
+    }
+
+    #[cfg(feature = "async")]
+    async fn parse_async(&mut self, value: &Value) -> Result<Value> {
+        let result = match self.parser {
+            Source::Sync(ref mut p) => p.parse(value),
+            Source::Async(ref mut p) => p.parse(value).await,
+        };
+        self.update(result)
+    }
+
+    fn update(&mut self, result: AnyResult<Value>) -> Result<Value> {
+        let result = result.map_err(|e| Error::ParseValue(e, self.number))?;
+        self.fingerprint = Hash::from(result.as_bytes().as_ref());
+        self.raw_value = result.clone();
+        self.last_value = result.clone();
+        self.last_value.seal(&self.sealed_suffix);
+        self.last_value.seal_keys(&self.parser.sealed_keys());
+        let now = Instant::now();
+        self.loaded_at = Some(now);
+        self.leases = self
+            .parser
+            .leases()
+            .into_iter()
+            .map(|l| {
+                let expiry = now + l.get_duration();
+                (l, expiry)
+            })
+            .collect();
+        Ok(result)
+    }
+
+    fn renew(&mut self, deadline: Instant) -> Result<bool> {
+        for (lease, expiry) in &mut self.leases {
+            if *expiry > deadline {
+                continue;
+            }
+            if !lease.is_renewable() {
+                return Ok(false);
+            }
+
+            let renewed = self
+                .parser
+                .renew(lease)
+                .map_err(|e| Error::RenewLease(e, lease.id().to_owned(), self.number))?;
+            let Some(renewed) = renewed else {
+                return Ok(false);
+            };
+            *expiry = Instant::now() + renewed.get_duration();
+            *lease = renewed;
+        }
+        Ok(true)
+    }
+
+    /// Name of the layer. If name was not set during appending of the parser then it will be `parser #N`, where `N`
+    /// is the position of the parser in [`ConfigBuilder`] starting from `1`.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get key paths of options which the parser takes from other parsers: declared by the parser itself (see
+    /// [`Parse::depends_on`]) and by [`ConfigBuilder::depends_on`].
+    pub fn depends_on(&self) -> Vec<String> {
+        let mut result = self.parser.depends_on();
+        for path in &self.depends_on {
+            if !result.contains(path) {
+                result.push(path.clone());
+            }
+        }
+        result
+    }
+
+    /// Get reference to [`Value`] structure returned by the parser during last (re)load. The value is sealed with
+    /// sealed suffix (see [`ConfigBuilder::sealed_suffix`]), so secrets are obfuscated during display/debugging output.
+    #[inline]
+    pub fn last_value(&self) -> &Value {
+        &self.last_value
+    }
+
+    /// Calculate hash for [`Value`] structure returned by the parser during last (re)load.
+    #[inline]
+    pub fn fingerprint(&self) -> String {
+        self.fingerprint.named()
+    }
+
+    /// Return `true` if the parser keys are case sensitive, otherwise return `false`.
+    #[inline]
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_on
+    }
+
+    /// Get time of the last successful parse if any.
+    #[inline]
+    pub fn loaded_at(&self) -> Option<Instant> {
+        self.loaded_at
+    }
+
+    /// Get leases returned by the parser (see [`Parse::leases`]) with their expiration times.
+    #[inline]
+    pub fn leases(&self) -> &[(Lease, Instant)] {
+        &self.leases
+    }
+}
+
+impl Debug for Layer {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_fmt(format_args!(
+            "Layer {{ name: {:?}, last_value: {:?}, fingerprint: {:?}, case_on: {:?} }}",
+            self.name, self.last_value, self.fingerprint, self.case_on,
+        ))
+    }
+}
+
 /// Container for all parser sources which will (re)load data from a parsers in order in which they was added
 /// to [`ConfigBuilder`]. It will provide access to merged set of (re)loaded configuration parameters.
 pub struct Config {
-    parsers: Vec<AnyParser>,
-    value: Value,
+    layers: Vec<Layer>,
+    value: Arc<Value>,
+    shared: Arc<ArcSwap<Value>>,
     case_on: bool,
     hash: Hash,
     sealed_suffix: String,
     keys_delimiter: String,
+    merge_rules: MergeRules,
+    offline: bool,
+    offline_option: Option<String>,
+    warnings: Vec<String>,
+    history: VecDeque<HistoryEntry>,
+    history_size: usize,
+    provenance: Option<BTreeMap<String, usize>>,
+    #[cfg(feature = "schema")]
+    schema: Option<Schema>,
+    default_templates: bool,
+    interpolation: bool,
+    env_interpolation: bool,
+    seal_detector: Option<SealDetector>,
+    strict_sealing: bool,
+    significance: Vec<(String, Significance)>,
+    suppressed: bool,
+    checkpoints: BTreeMap<String, HistoryEntry>,
+    pinned: Option<Hash>,
+    pinned_keys: BTreeMap<String, Option<InnerValue>>,
+    staged: Option<Staged>,
+    ttls: Vec<(String, Duration)>,
+    #[cfg(feature = "access")]
+    accessed: Option<std::sync::Mutex<BTreeSet<String>>>,
+    profile: Option<String>,
 }
 
 impl Config {
-    /// Reload and re-merge all configuration data from parsers.
+    /// Reload and re-merge all configuration data from parsers. If all changes are insignificant (see
+    /// [`ConfigBuilder::significance`]) then the current configuration data is kept (see [`Config::is_suppressed`]).
     ///
     /// # Errors
     ///
     /// If any errors will occur during parsing/merging then error will be returned.
     pub fn reload(&mut self) -> Result<&mut Self> {
-        let mut value = Value::default();
-        for (idx, parser) in self.parsers.iter_mut().enumerate() {
-            value = parser
-                .parse(&value)
-                .map_err(|e| Error::ParseValue(e, idx + 1))?
-                .merge_with_case(&value, self.case_on);
-        }
+        let value = self.load_value()?;
+        let staged = self.prepare(value)?;
+        self.apply_significant(&staged)?;
+        Ok(self)
+    }
 
-        value.seal(&self.sealed_suffix);
-        self.hash = Hash::from(value.as_bytes().as_ref());
-        self.value = value;
+    /// Same as [`Config::reload`], but asynchronous parsers (see [`AsyncParse`]) are awaited. The synchronous
+    /// parsers are called in place.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur during parsing/merging then error will be returned.
+    #[cfg(feature = "async")]
+    pub async fn reload_async(&mut self) -> Result<&mut Self> {
+        let value = self.load_value_async().await?;
+        let staged = self.prepare(value)?;
+        self.apply_significant(&staged)?;
         Ok(self)
     }
 
-    /// Name of the hash used for loaded configuration data.
+    /// Return `true` if the last [`Config::reload`] found only insignificant changes (see
+    /// [`ConfigBuilder::significance`]) and the current configuration data was kept, otherwise return `false`.
     #[inline]
-    pub fn hash_name() -> &'static str {
-        HASH_NAME
+    pub fn is_suppressed(&self) -> bool {
+        self.suppressed
     }
 
-    /// Calculate hash for currently loaded configuration data.
-    #[inline]
-    pub fn hash(&self) -> String {
-        [HASH_NAME, ": ", &self.hash.to_string()].concat()
+    fn apply_significant(&mut self, staged: &Staged) -> Result<()> {
+        self.suppressed = !self.is_significant(&staged.entry.value);
+        if self.suppressed {
+            self.warnings
+                .push("Only insignificant changes were loaded, the current data is kept".into());
+            return Ok(());
+        }
+        self.apply(staged)
     }
 
-    /// Returns configuration data value to corresponding key/nested keys.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// let name: Option<u32> = conf.get_by_keys(["logger", "name"])?;
-    /// ```
+    fn is_significant(&self, value: &Value) -> bool {
+        if self.significance.is_empty() {
+            return true;
+        }
+
+        let (mut old, mut new) = (self.value.as_ref().clone(), value.clone());
+        for (path, predicate) in &self.significance {
+            let old = old.remove_by_key_path_with_delim(path, &self.keys_delimiter);
+            let new = new.remove_by_key_path_with_delim(path, &self.keys_delimiter);
+            match (old.ok().flatten(), new.ok().flatten()) {
+                (Some(o), Some(n)) if o == n || !predicate(&o, &n) => (),
+                (None, None) => (),
+                _ => return true,
+            }
+        }
+        old != new
+    }
+
+    /// Reload and re-merge all configuration data from parsers, but keep the result as candidate (see
+    /// [`Config::staged`]) alongside the current configuration data. The candidate could be activated by
+    /// [`Config::commit`] or dropped by [`Config::discard`], so applications could run canary logic (e.g. shadow
+    /// traffic with new settings) before. The previous candidate (if any) is replaced.
     ///
     /// # Errors
     ///
-    /// If keys is empty, the error will be returned.
-    #[inline]
-    pub fn get_by_keys<I, K, T>(&self, keys: I) -> Result<Option<T>>
-    where
-        I: IntoIterator<Item = K>,
-        K: AsRef<str>,
-        T: DeserializeOwned,
-    {
-        self.value.get_by_keys(keys)
-    }
-
-    /// Returns configuration data value to corresponding key path with keys delimiter. Default delimiter is
-    /// [`DEFAULT_KEYS_SEPARATOR`].
+    /// If any errors will occur during parsing/merging then error will be returned.
     ///
     /// # Example
     ///
     /// ```
-    /// use serde::Deserialize;
-    ///
-    /// #[derive(Deserialize)]
-    /// struct Person {
-    ///     first_name: String,
-    ///     last_name: String,
-    ///     age: u8,
+    /// let candidate = conf.preview_reload()?;
+    /// if canary_passed(candidate.value()) {
+    ///     conf.commit()?;
+    /// } else {
+    ///     conf.discard();
     /// }
-    ///
-    /// let person: Option<Person> = conf.get_by_key_path("contact:info")?;
     /// ```
+    pub fn preview_reload(&mut self) -> Result<&HistoryEntry> {
+        let value = self.load_value()?;
+        let staged = self.prepare(value)?;
+        Ok(&self.staged.insert(staged).entry)
+    }
+
+    /// Get candidate configuration data loaded by [`Config::preview_reload`] if any.
+    #[inline]
+    pub fn staged(&self) -> Option<&HistoryEntry> {
+        self.staged.as_ref().map(|s| &s.entry)
+    }
+
+    /// Activate candidate configuration data loaded by [`Config::preview_reload`].
     ///
     /// # Errors
     ///
-    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    /// If there is no candidate or it would change pinned data (see [`Config::pin`]) then error will be returned, the
+    /// candidate will be kept in the latter case.
+    pub fn commit(&mut self) -> Result<&mut Self> {
+        let staged = self.staged.take().ok_or(Error::NothingStaged)?;
+        if let Err(e) = self.apply(&staged) {
+            self.staged = Some(staged);
+            return Err(e);
+        }
+        Ok(self)
+    }
+
+    /// Drop candidate configuration data loaded by [`Config::preview_reload`] and return it if any.
     #[inline]
-    pub fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
-    where
-        T: DeserializeOwned,
-        P: AsRef<str>,
-    {
-        self.value
-            .get_by_key_path_with_delim(path, &self.keys_delimiter)
+    pub fn discard(&mut self) -> Option<HistoryEntry> {
+        self.staged.take().map(|s| s.entry)
     }
 
-    /// Returns configuration data value to corresponding key path with delimiter.
+    fn load_value(&mut self) -> Result<Value> {
+        self.load_layers(|_| true)
+    }
+
+    /// The layers without dependencies are parsed first in appended order. Then the dependent layers are parsed in
+    /// order of dependencies between them (see [`Config::dependent_order`]), which is refined until it is stable.
+    fn load_layers<F: Fn(usize) -> bool>(&mut self, reparse: F) -> Result<Value> {
+        let mut parsed = vec![None; self.layers.len()];
+        self.warnings.clear();
+        let (independent, mut order) = self.partition_layers();
+        for idx in independent {
+            parsed[idx] = Some(self.parse_layer(idx, &parsed, &reparse)?);
+        }
+        for _ in 0..=order.len() {
+            order.iter().for_each(|i| parsed[*i] = None);
+            for &idx in &order {
+                parsed[idx] = Some(self.parse_layer(idx, &parsed, &reparse)?);
+            }
+            let next = self.dependent_order(&order, &parsed)?;
+            if next == order {
+                return Ok(self.merge_parsed(&parsed));
+            }
+            order = next;
+        }
+        Err(self.unresolved(order[0]))
+    }
+
+    fn parse_layer<F: Fn(usize) -> bool>(
+        &mut self,
+        idx: usize,
+        parsed: &[Option<Value>],
+        reparse: &F,
+    ) -> Result<Value> {
+        if !reparse(idx) {
+            return Ok(self.layers[idx].raw_value.clone());
+        }
+        let value = self.merge_parsed(parsed);
+        match self.offline_value(idx, &value) {
+            Some(v) => Ok(v),
+            None => self.layers[idx].parse(&value),
+        }
+    }
+
+    #[cfg(feature = "async")]
+    async fn load_value_async(&mut self) -> Result<Value> {
+        let mut parsed = vec![None; self.layers.len()];
+        self.warnings.clear();
+        let (independent, mut order) = self.partition_layers();
+        for idx in independent {
+            parsed[idx] = Some(self.parse_layer_async(idx, &parsed).await?);
+        }
+        for _ in 0..=order.len() {
+            order.iter().for_each(|i| parsed[*i] = None);
+            for &idx in &order {
+                parsed[idx] = Some(self.parse_layer_async(idx, &parsed).await?);
+            }
+            let next = self.dependent_order(&order, &parsed)?;
+            if next == order {
+                return Ok(self.merge_parsed(&parsed));
+            }
+            order = next;
+        }
+        Err(self.unresolved(order[0]))
+    }
+
+    #[cfg(feature = "async")]
+    async fn parse_layer_async(&mut self, idx: usize, parsed: &[Option<Value>]) -> Result<Value> {
+        let value = self.merge_parsed(parsed);
+        match self.offline_value(idx, &value) {
+            Some(v) => Ok(v),
+            None => self.layers[idx].parse_async(&value).await,
+        }
+    }
+
+    /// Split layers to the ones without dependencies and the dependent ones, both in appended order.
+    fn partition_layers(&self) -> (Vec<usize>, Vec<usize>) {
+        (0..self.layers.len()).partition(|i| self.layers[*i].depends_on().is_empty())
+    }
+
+    /// Sort dependent layers topologically: the layer is parsed after other dependent layers which supplied any of its
+    /// dependencies during last parse. The appended order is kept for independent ones. If dependencies are cyclic
+    /// then [`Error::UnresolvedDependency`] is returned.
+    fn dependent_order(&self, layers: &[usize], parsed: &[Option<Value>]) -> Result<Vec<usize>> {
+        let supplies = |supplier: usize, path: &str| {
+            parsed[supplier].as_ref().is_some_and(|v| {
+                matches!(
+                    v.get_by_key_path_with_delim::<InnerValue, _, _>(path, &self.keys_delimiter),
+                    Ok(Some(_))
+                )
+            })
+        };
+        let mut pending: Vec<(usize, Vec<usize>)> = layers
+            .iter()
+            .map(|&idx| {
+                let paths = self.layers[idx].depends_on();
+                let suppliers = layers
+                    .iter()
+                    .copied()
+                    .filter(|&s| s != idx && paths.iter().any(|p| supplies(s, p)))
+                    .collect();
+                (idx, suppliers)
+            })
+            .collect();
+        pending.sort_by_key(|(idx, _)| *idx);
+
+        let mut order = Vec::with_capacity(pending.len());
+        while !pending.is_empty() {
+            let Some(pos) = pending
+                .iter()
+                .position(|(_, s)| s.iter().all(|i| order.contains(i)))
+            else {
+                return Err(self.unresolved(pending[0].0));
+            };
+            order.push(pending.remove(pos).0);
+        }
+        Ok(order)
+    }
+
+    fn unresolved(&self, idx: usize) -> Error {
+        let layer = &self.layers[idx];
+        let path = layer.depends_on().into_iter().next().unwrap_or_default();
+        Error::UnresolvedDependency(layer.number, path)
+    }
+
+    fn merge_parsed(&self, parsed: &[Option<Value>]) -> Value {
+        parsed.iter().flatten().fold(Value::default(), |v, p| {
+            let mut p = p.clone();
+            if let Some(ref profile) = self.profile {
+                p.apply_profile(DEFAULT_PROFILES_KEY, profile, &self.merge_rules);
+            }
+            p.merge_with_rules(&v, self.case_on, &self.merge_rules)
+        })
+    }
+
+    /// Return `true` if value for given key path (with keys delimiter) is not stale, otherwise return `false`. The
+    /// value is stale if it is inside subtree marked with TTL (see [`ConfigBuilder::ttl`]) and TTL is elapsed since
+    /// the last parse of the layer which supplied that subtree. The values without TTL are always fresh.
     ///
     /// # Example
     ///
     /// ```
-    /// let name: Option<u32> = conf.get_by_key_path_with_delim("logger:name", ":")?;
+    /// if !conf.is_fresh("auth:token") {
+    ///     conf.refresh_expiring(Duration::ZERO)?;
+    /// }
     /// ```
-    ///
-    /// # Errors
-    ///
-    /// If keys path or delimiter is empty, the corresponding error will be returned.
     #[inline]
-    pub fn get_by_key_path_with_delim<T, P, D>(&self, path: P, delim: D) -> Result<Option<T>>
-    where
-        T: DeserializeOwned,
-        P: AsRef<str>,
-        D: AsRef<str>,
-    {
-        self.value.get_by_key_path_with_delim(path, delim)
+    pub fn is_fresh<P: AsRef<str>>(&self, path: P) -> bool {
+        self.expires_in(path).map_or(true, |d| !d.is_zero())
     }
 
-    /// Deserialize configuration to destination struct/value.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use serde::Deserialize;
+    /// Get time left until value for given key path (with keys delimiter) becomes stale (see [`Config::is_fresh`]).
+    /// If value has no TTL then `None` will be returned.
+    pub fn expires_in<P: AsRef<str>>(&self, path: P) -> Option<Duration> {
+        let keys: Vec<_> = path.as_ref().split(&self.keys_delimiter).collect();
+        let (ttl_path, ttl) = self
+            .ttls
+            .iter()
+            .filter(|(p, _)| {
+                let prefix: Vec<_> = p.split(&self.keys_delimiter).collect();
+                keys.starts_with(&prefix)
+            })
+            .max_by_key(|(p, _)| p.len())?;
+        let expiry = self.expiry(ttl_path, *ttl)?;
+        Some(expiry.saturating_duration_since(Instant::now()))
+    }
+
+    /// Get the earliest time when any value with TTL (see [`ConfigBuilder::ttl`]) becomes stale. It could be used to
+    /// schedule [`Config::refresh_expiring`] calls.
+    pub fn next_expiry(&self) -> Option<Instant> {
+        self.ttls
+            .iter()
+            .filter_map(|(p, ttl)| self.expiry(p, *ttl))
+            .min()
+    }
+
+    /// Reload only layers which supplied values with TTL (see [`ConfigBuilder::ttl`]) which are stale or become stale
+    /// within given margin, the other layers are merged from their last (re)loaded values. Return `true` if any layer
+    /// was reloaded, otherwise return `false`.
     ///
-    /// #[derive(Deserialize)]
-    /// struct Person {
-    ///     first_name: String,
-    ///     last_name: String,
-    ///     age: u8,
-    /// }
+    /// # Errors
     ///
-    /// let person: Person = conf.get()?;
-    /// ```
+    /// If any errors will occur during parsing/merging then error will be returned.
+    pub fn refresh_expiring(&mut self, margin: Duration) -> Result<bool> {
+        let deadline = Instant::now() + margin;
+        let owners: BTreeSet<usize> = self
+            .ttls
+            .iter()
+            .filter_map(|(p, ttl)| {
+                let idx = self.owner(p)?;
+                let expiry = self.layers[idx].loaded_at? + *ttl;
+                (expiry <= deadline).then_some(idx)
+            })
+            .collect();
+        if owners.is_empty() {
+            return Ok(false);
+        }
+
+        let value = self.load_layers(|idx| owners.contains(&idx))?;
+        let staged = self.prepare(value)?;
+        self.apply(&staged)?;
+        Ok(true)
+    }
+
+    /// Get the earliest expiration time of leases returned by parsers (see [`Parse::leases`]). It could be used to
+    /// schedule [`Config::renew_leases`] calls.
+    pub fn next_renewal(&self) -> Option<Instant> {
+        self.layers
+            .iter()
+            .flat_map(|l| l.leases.iter().map(|(_, e)| *e))
+            .min()
+    }
+
+    /// Renew leases returned by parsers (see [`Parse::leases`]) which are expired or expire within given margin.
+    /// If any lease is not renewable or the parser could not renew it (see [`Parse::renew`]) then the layer of that
+    /// parser is reloaded, the other layers are merged from their last (re)loaded values. Return `true` if any layer
+    /// was reloaded, otherwise return `false`.
     ///
     /// # Errors
     ///
-    /// In case of any de-serialization problems the corresponding error will be returned.
-    #[inline]
-    pub fn get<T: DeserializeOwned>(&self) -> Result<T> {
-        self.value.get()
+    /// If any errors will occur during renewal or parsing/merging then error will be returned.
+    pub fn renew_leases(&mut self, margin: Duration) -> Result<bool> {
+        let deadline = Instant::now() + margin;
+        let mut rotated = BTreeSet::new();
+        for (idx, layer) in self.layers.iter_mut().enumerate() {
+            if !layer.renew(deadline)? {
+                rotated.insert(idx);
+            }
+        }
+        if rotated.is_empty() {
+            return Ok(false);
+        }
+
+        let value = self.load_layers(|idx| rotated.contains(&idx))?;
+        let staged = self.prepare(value)?;
+        self.apply(&staged)?;
+        Ok(true)
     }
 
-    /// Get reference to internal [`Value`] structure.
-    #[inline]
-    pub fn get_value(&self) -> &Value {
-        &self.value
+    fn expiry(&self, path: &str, ttl: Duration) -> Option<Instant> {
+        let idx = self.owner(path)?;
+        Some(self.layers[idx].loaded_at? + ttl)
     }
-}
 
-impl Debug for Config {
-    #[inline]
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        f.write_fmt(format_args!(
-            "Config {{ parsers: size({}), value: {:?}, case_on: {:?}, hash: {:?}, sealed_suffix: {:?}, keys_delimiter: {:?} }}",
-            self.parsers.len(),
-            self.value,
-            self.case_on,
-            self.hash,
-            self.sealed_suffix,
-            self.keys_delimiter,
-        ))
+    fn owner(&self, path: &str) -> Option<usize> {
+        let sealed_path = [path, &self.sealed_suffix].concat();
+        self.layers.iter().position(|l| {
+            [path, sealed_path.as_str()].iter().any(|p| {
+                matches!(
+                    l.raw_value
+                        .get_by_key_path_with_delim::<InnerValue, _, _>(p, &self.keys_delimiter),
+                    Ok(Some(_))
+                )
+            })
+        })
     }
-}
 
-impl Display for Config {
-    #[inline]
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        f.write_fmt(format_args!("Config: {}\n{}", self.hash(), self.value))
+    fn offline_value(&mut self, idx: usize, value: &Value) -> Option<Value> {
+        let layer = &self.layers[idx];
+        let offline = self.offline
+            || is_option_on(self.offline_option.as_deref(), value, &self.keys_delimiter);
+        if !(offline && layer.parser.is_remote(value)) {
+            return None;
+        }
+
+        self.warnings.push(format!(
+            "Layer '{}' was not fetched in offline mode, the last loaded value is used",
+            layer.name
+        ));
+        Some(layer.raw_value.clone())
     }
-}
 
-impl PartialEq for Config {
+    fn prepare(&mut self, mut value: Value) -> Result<Staged> {
+        if self.default_templates {
+            value.apply_templates(DEFAULT_TEMPLATE_KEY);
+        }
+        if self.interpolation || self.env_interpolation {
+            let sealed: Vec<_> = self
+                .layers
+                .iter()
+                .flat_map(|l| l.parser.sealed_keys())
+                .collect();
+            value.interpolate(
+                &self.keys_delimiter,
+                self.interpolation,
+                self.env_interpolation,
+                &self.sealed_suffix,
+                &sealed,
+            )?;
+        }
+        #[cfg(feature = "schema")]
+        if let Some(ref schema) = self.schema {
+            schema.apply(&mut value);
+        }
+
+        let provenance = self.provenance.as_ref().map(|_| {
+            let mut provenance = Provenance {
+                layers: &self.layers,
+                case_on: self.case_on,
+                suffix: crate::normalize_case(&self.sealed_suffix, self.case_on),
+                delim: &self.keys_delimiter,
+                result: BTreeMap::new(),
+            };
+            provenance.walk(value.as_inner(), &mut Vec::new(), &mut Vec::new());
+            provenance.result
+        });
+
+        for keys in value.seal_checked(&self.sealed_suffix) {
+            let path = keys.join(&self.keys_delimiter);
+            if self.strict_sealing {
+                return Err(Error::SealedKeyCollision(path));
+            }
+            self.warnings.push(format!(
+                "Key path '{path}' is defined both with and without sealed suffix, the sealed value is used"
+            ));
+        }
+        if let Some(ref detector) = self.seal_detector {
+            value.seal_if(&self.keys_delimiter, detector);
+        }
+        for layer in &self.layers {
+            value.seal_keys(&layer.parser.sealed_keys());
+        }
+        let hash = Hash::from(value.as_bytes().as_ref());
+        Ok(Staged {
+            entry: HistoryEntry {
+                value: Arc::new(value),
+                hash,
+                timestamp: SystemTime::now(),
+            },
+            provenance,
+        })
+    }
+
+    fn apply(&mut self, staged: &Staged) -> Result<()> {
+        self.activate(staged.entry.value.clone(), staged.entry.hash.clone())?;
+        if staged.provenance.is_some() {
+            self.provenance = staged.provenance.clone();
+        }
+
+        if self.history_size > 0 {
+            self.history.truncate(self.history_size - 1);
+            self.history.push_front(staged.entry.clone());
+        }
+        Ok(())
+    }
+
+    fn activate(&mut self, value: Arc<Value>, hash: Hash) -> Result<()> {
+        if let Some(ref pinned) = self.pinned {
+            if *pinned != hash {
+                return Err(Error::Pinned(pinned.named()));
+            }
+        }
+        for (path, pinned) in &self.pinned_keys {
+            let current: Option<InnerValue> =
+                value.get_by_key_path_with_delim(path, &self.keys_delimiter)?;
+            if current != *pinned {
+                return Err(Error::PinnedKey(path.clone()));
+            }
+        }
+
+        self.value = value;
+        self.hash = hash;
+        self.shared.store(self.value.clone());
+        Ok(())
+    }
+
+    /// Get at most `n` last (re)loaded snapshots of configuration data (see [`HistoryEntry`]), the newest one first.
+    /// History should be enabled by [`ConfigBuilder::history`], otherwise empty result will be returned.
+    #[inline]
+    pub fn history(&self, n: usize) -> Vec<&HistoryEntry> {
+        self.history.iter().take(n).collect()
+    }
+
+    /// Restore `k`-th snapshot from history (see [`Config::history`]) as the current configuration data without
+    /// calling parsers, `0` is the last (re)loaded one. The history itself is not changed.
+    ///
+    /// # Errors
+    ///
+    /// If there is no such snapshot in history then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // Restore configuration data which was active before the last reload.
+    /// conf.rollback(1)?;
+    /// ```
+    pub fn rollback(&mut self, k: usize) -> Result<&mut Self> {
+        let entry = self.history.get(k).ok_or(Error::MissingSnapshot(k))?;
+        self.activate(entry.value.clone(), entry.hash.clone())?;
+        Ok(self)
+    }
+
+    /// Save current configuration data as named checkpoint, the checkpoint with the same name will be replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// conf.checkpoint("pre-migration");
+    /// conf.reload()?;
+    /// // Something went wrong.
+    /// conf.restore("pre-migration")?;
+    /// ```
+    pub fn checkpoint<S: Into<String>>(&mut self, name: S) -> &HistoryEntry {
+        let entry = HistoryEntry {
+            value: self.value.clone(),
+            hash: self.hash.clone(),
+            timestamp: SystemTime::now(),
+        };
+        let name = name.into();
+        self.checkpoints.insert(name.clone(), entry);
+        &self.checkpoints[&name]
+    }
+
+    /// Get named checkpoint (see [`Config::checkpoint`]) if any.
+    #[inline]
+    pub fn get_checkpoint<S: AsRef<str>>(&self, name: S) -> Option<&HistoryEntry> {
+        self.checkpoints.get(name.as_ref())
+    }
+
+    /// Restore named checkpoint (see [`Config::checkpoint`]) as the current configuration data without calling
+    /// parsers.
+    ///
+    /// # Errors
+    ///
+    /// If there is no such checkpoint or restored data would change pinned data (see [`Config::pin`]) then error will
+    /// be returned.
+    pub fn restore<S: AsRef<str>>(&mut self, name: S) -> Result<&mut Self> {
+        let entry = self
+            .checkpoints
+            .get(name.as_ref())
+            .ok_or_else(|| Error::MissingCheckpoint(name.as_ref().into()))?;
+        self.activate(entry.value.clone(), entry.hash.clone())?;
+        Ok(self)
+    }
+
+    /// Pin whole configuration data with given hash (see [`Config::hash`]), which should be the hash of the current
+    /// configuration data. While pinned, any (re)load, rollback or restore which would change configuration data will
+    /// fail and current data will be kept. It is a guard rail for change-freeze windows.
+    ///
+    /// # Errors
+    ///
+    /// If given hash does not match hash of the current configuration data then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hash = conf.hash();
+    /// conf.pin(&hash)?;
+    /// // Fails if configuration data was changed.
+    /// conf.reload()?;
+    /// conf.unpin();
+    /// ```
+    pub fn pin<S: AsRef<str>>(&mut self, hash: S) -> Result<&mut Self> {
+        if hash.as_ref() != self.hash() {
+            return Err(Error::HashMismatch(hash.as_ref().into()));
+        }
+        self.pinned = Some(self.hash.clone());
+        Ok(self)
+    }
+
+    /// Pin current value for given key path (with keys delimiter). While pinned, any (re)load, rollback or restore
+    /// which would change that value will fail and current data will be kept.
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    pub fn pin_key<P: AsRef<str>>(&mut self, path: P) -> Result<&mut Self> {
+        let current = self
+            .value
+            .get_by_key_path_with_delim(path.as_ref(), &self.keys_delimiter)?;
+        self.pinned_keys.insert(path.as_ref().into(), current);
+        Ok(self)
+    }
+
+    /// Unpin whole configuration data (see [`Config::pin`]).
+    #[inline]
+    pub fn unpin(&mut self) -> &mut Self {
+        self.pinned = None;
+        self
+    }
+
+    /// Unpin value for given key path (see [`Config::pin_key`]).
+    #[inline]
+    pub fn unpin_key<P: AsRef<str>>(&mut self, path: P) -> &mut Self {
+        self.pinned_keys.remove(path.as_ref());
+        self
+    }
+
+    /// Return `true` if whole configuration data or any key path is pinned, otherwise return `false`.
+    #[inline]
+    pub fn is_pinned(&self) -> bool {
+        self.pinned.is_some() || !self.pinned_keys.is_empty()
+    }
+
+    /// Get layer of the parser which supplied value for given key path (with keys delimiter) during last (re)load.
+    /// Provenance tracking should be enabled by [`ConfigBuilder::provenance`], otherwise `None` will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// if let Some(layer) = conf.provenance("logger:level") {
+    ///     println!("logger:level is supplied by '{}'", layer.name());
+    /// }
+    /// ```
+    pub fn provenance<P: AsRef<str>>(&self, path: P) -> Option<&Layer> {
+        let path = crate::normalize_case(path.as_ref(), self.case_on);
+        let idx = self.provenance.as_ref()?.get(path.as_ref())?;
+        self.layers.get(*idx)
+    }
+
+    /// Get all key paths (with keys delimiter) in sorted order with layers of the parsers which supplied values for
+    /// them during last (re)load. Provenance tracking should be enabled by [`ConfigBuilder::provenance`], otherwise
+    /// empty report will be returned.
+    pub fn provenance_report(&self) -> Vec<(&str, &Layer)> {
+        self.provenance
+            .iter()
+            .flatten()
+            .map(|(p, idx)| (p.as_str(), &self.layers[*idx]))
+            .collect()
+    }
+
+    /// Get paths of the files used by all parsers during last (re)load (see [`Parse::watch_paths`]).
+    pub fn watch_paths(&self) -> Vec<PathBuf> {
+        self.layers
+            .iter()
+            .flat_map(|l| l.parser.watch_paths())
+            .collect()
+    }
+
+    /// Get warnings collected during last (re)load.
+    #[inline]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Name of the hash used for loaded configuration data.
+    #[inline]
+    pub fn hash_name() -> &'static str {
+        HASH_NAME
+    }
+
+    /// Calculate hash for currently loaded configuration data.
+    #[inline]
+    pub fn hash(&self) -> String {
+        self.hash.named()
+    }
+
+    /// Returns configuration data value to corresponding key/nested keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let name: Option<u32> = conf.get_by_keys(["logger", "name"])?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If keys is empty, the error will be returned.
+    #[inline]
+    pub fn get_by_keys<I, K, T>(&self, keys: I) -> Result<Option<T>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        #[cfg(feature = "access")]
+        if self.accessed.is_some() {
+            let keys: Vec<_> = keys.into_iter().map(|k| k.as_ref().to_string()).collect();
+            self.record_access([keys.join(&self.keys_delimiter)]);
+            return self.value.get_by_keys(keys);
+        }
+        self.value.get_by_keys(keys)
+    }
+
+    /// Returns configuration data value to corresponding key path with keys delimiter. Default delimiter is
+    /// [`DEFAULT_KEYS_SEPARATOR`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Person {
+    ///     first_name: String,
+    ///     last_name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// let person: Option<Person> = conf.get_by_key_path("contact:info")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    #[inline]
+    pub fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        self.get_by_key_path_with_delim(path, &self.keys_delimiter)
+    }
+
+    /// Returns configuration data value to corresponding key path with delimiter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let name: Option<u32> = conf.get_by_key_path_with_delim("logger:name", ":")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If keys path or delimiter is empty, the corresponding error will be returned.
+    #[inline]
+    pub fn get_by_key_path_with_delim<T, P, D>(&self, path: P, delim: D) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+        D: AsRef<str>,
+    {
+        #[cfg(feature = "access")]
+        if self.accessed.is_some() && !delim.as_ref().is_empty() {
+            let keys: Vec<_> = path.as_ref().split(delim.as_ref()).collect();
+            self.record_access([keys.join(&self.keys_delimiter)]);
+        }
+        self.value.get_by_key_path_with_delim(path, delim)
+    }
+
+    /// Deserialize configuration to destination struct/value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Person {
+    ///     first_name: String,
+    ///     last_name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// let person: Person = conf.get()?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// In case of any de-serialization problems the corresponding error will be returned.
+    #[inline]
+    pub fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        #[cfg(feature = "access")]
+        if self.accessed.is_some() {
+            return self.get_recorded();
+        }
+        self.value.get()
+    }
+
+    /// Return [`Deserializer`] over whole configuration data (see [`Value::deserializer`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut unused = Vec::new();
+    /// let settings: Settings =
+    ///     serde_ignored::deserialize(conf.deserializer(), |p| unused.push(p.to_string()))?;
+    /// ```
+    #[inline]
+    pub fn deserializer(&self) -> impl Deserializer<'_, Error = SerdeError> {
+        self.value.deserializer()
+    }
+
+    /// Returns whole configuration data deserialized to any type which implements [`serde::Deserialize`] trait, together
+    /// with key paths (with keys delimiter) which were present in configuration data but were not consumed during
+    /// deserialization (e.g. misspelled options).
+    ///
+    /// To enable that functionality one has to add the following to Cargo.toml:
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// irx-config = { version = "3.5", features = ["unused"] }
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let (settings, unused): (Settings, _) = conf.get_with_unused()?;
+    /// for path in unused {
+    ///     eprintln!("Unknown option: '{path}'");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// In case of any de-serialization problems the corresponding error will be returned.
+    #[cfg(feature = "unused")]
+    pub fn get_with_unused<T: DeserializeOwned>(&self) -> Result<(T, Vec<String>)> {
+        let mut unused = Vec::new();
+        let value = serde_ignored::deserialize(self.deserializer(), |path| {
+            let mut keys = Vec::new();
+            path_keys(&path, &mut keys);
+            unused.push(keys.join(&self.keys_delimiter));
+        })
+        .map_err(|e| Error::SerdeError(e, "Failed to deserialize value".into()))?;
+        Ok((value, unused))
+    }
+
+    /// Returns whole configuration data deserialized via given [`DeserializeSeed`] (see [`Value::get_seeded`]).
+    ///
+    /// # Errors
+    ///
+    /// In case of any de-serialization problems the corresponding error will be returned.
+    #[inline]
+    pub fn get_seeded<'de, S: DeserializeSeed<'de>>(&'de self, seed: S) -> Result<S::Value> {
+        self.value.get_seeded(seed)
+    }
+
+    /// Return sorted key paths (with keys delimiter) which were read via [`Config::get_by_keys`],
+    /// [`Config::get_by_key_path`] (and other getters by key path) or [`Config::get`] since load, if recording is
+    /// enabled by [`ConfigBuilder::record_access`]. For [`Config::get`] the key paths of values consumed by
+    /// deserialization are recorded, so the dictionaries or fields which are not defined by destination type are not
+    /// included. The result could be used to prune dead configuration keys or as patterns for [`Config::restricted`].
+    ///
+    /// To enable that functionality one has to add the following to Cargo.toml:
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// irx-config = { version = "3.5", features = ["access"] }
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let settings: Settings = conf.get()?;
+    /// run(&settings);
+    /// for path in conf.accessed_key_paths() {
+    ///     println!("{path}");
+    /// }
+    /// ```
+    #[cfg(feature = "access")]
+    pub fn accessed_key_paths(&self) -> Vec<String> {
+        self.accessed
+            .as_ref()
+            .map(|a| {
+                a.lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .iter()
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "access")]
+    fn record_access<I: IntoIterator<Item = String>>(&self, paths: I) {
+        if let Some(ref accessed) = self.accessed {
+            let paths = paths
+                .into_iter()
+                .map(|p| crate::normalize_case(&p, self.case_on).into_owned());
+            accessed
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .extend(paths);
+        }
+    }
+
+    #[cfg(feature = "access")]
+    fn get_recorded<T: DeserializeOwned>(&self) -> Result<T> {
+        let delim = self.keys_delimiter.as_str();
+        let mut unused = Vec::new();
+        let value = serde_ignored::deserialize(self.deserializer(), |path| {
+            let mut keys = Vec::new();
+            path_keys(&path, &mut keys);
+            unused.push(keys.join(delim));
+        })
+        .map_err(|e| Error::SerdeError(e, "Failed to deserialize value".into()))?;
+
+        let is_under = |path: &str, parent: &str| {
+            path.strip_prefix(parent)
+                .is_some_and(|r| r.is_empty() || r.starts_with(delim))
+        };
+        let paths = self.value.key_paths(delim);
+        let used = paths.iter().filter(|&p| {
+            !paths.iter().any(|o| o != p && is_under(o, p))
+                && !unused.iter().any(|u| is_under(p, u))
+        });
+        self.record_access(used.cloned());
+        Ok(value)
+    }
+
+    /// Returns configuration data value to corresponding key path with keys delimiter. Unlike
+    /// [`Config::get_by_key_path`], if key path does not exists then [`Error::MissingKey`] will be returned with
+    /// suggestion of nearest existing key path if any (see [`Config::suggest_key_path`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let level: String = conf.require_by_key_path("logger:level")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If key path does not exists or any other errors will occur then error will be returned.
+    pub fn require_by_key_path<T, P>(&self, path: P) -> Result<T>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        let path = path.as_ref();
+        self.get_by_key_path(path)?
+            .ok_or_else(|| Error::MissingKey(path.to_string(), self.suggest_key_path(path)))
+    }
+
+    /// Return existing key path nearest (by edit distance) to given key path if any.
+    #[inline]
+    pub fn suggest_key_path<P: AsRef<str>>(&self, path: P) -> Option<String> {
+        self.value.suggest_key_path(path, &self.keys_delimiter)
+    }
+
+    /// Return all existing key paths which start with given prefix in sorted order. It could be used to complete key
+    /// paths in command-line tools.
+    pub fn complete_key_path<P: AsRef<str>>(&self, prefix: P) -> Vec<String> {
+        let prefix = crate::normalize_case(prefix.as_ref(), self.value.is_case_sensitive());
+        let mut paths = self.value.key_paths(&self.keys_delimiter);
+        paths.retain(|p| p.starts_with(prefix.as_ref()));
+        paths
+    }
+
+    /// Resolve concurrency level (e.g. number of workers) for given key path (with keys delimiter). The following
+    /// values are supported:
+    ///
+    /// * `"auto"` - number of available CPU cores;
+    /// * `"xN"` - number of available CPU cores multiplied by `N` (could be fractional, e.g. `"x0.5"`);
+    /// * absolute number (e.g. `8` or `"8"`).
+    ///
+    /// If value is missing then result of `default` function will be returned. The resolved level is at least `1`.
+    ///
+    /// # Errors
+    ///
+    /// If value is not supported then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let workers = conf.resolve_concurrency("server:workers", || 4)?;
+    /// ```
+    pub fn resolve_concurrency<P, F>(&self, path: P, default: F) -> Result<usize>
+    where
+        P: AsRef<str>,
+        F: FnOnce() -> usize,
+    {
+        let path = path.as_ref();
+        let value: Option<InnerValue> = self.get_by_key_path(path)?;
+        let Some(value) = value.filter(|v| !v.is_null()) else {
+            return Ok(default().max(1));
+        };
+        let cores = || {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        };
+
+        let result = match value {
+            InnerValue::Number(ref n) => n.as_u64().and_then(|n| usize::try_from(n).ok()),
+            InnerValue::String(ref s) => {
+                let data = s.trim().to_lowercase();
+                match data.strip_prefix('x') {
+                    _ if "auto" == data => Some(cores()),
+                    Some(factor) => factor
+                        .trim()
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|f| f.is_finite() && *f > 0.0)
+                        .map(|f| (cores() as f64 * f).round() as usize),
+                    None => data.parse().ok(),
+                }
+            }
+            _ => None,
+        };
+        result
+            .map(|r| r.max(1))
+            .ok_or_else(|| Error::InvalidConcurrency(path.into(), value.to_string()))
+    }
+
+    /// Remove configuration data value for corresponding key/nested keys and return removed value if any. The value
+    /// will be removed until next (re)load.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let name = conf.remove_by_keys(["logger", "name"])?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If configuration data is pinned (see [`Config::pin`], [`Config::pin_key`]) and removal would change it or any
+    /// other errors will occur then error will be returned.
+    #[inline]
+    pub fn remove_by_keys<I, K>(&mut self, keys: I) -> Result<Option<Value>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+    {
+        self.remove_with(|v| v.remove_by_keys(keys))
+    }
+
+    /// Remove configuration data value for corresponding key path with keys delimiter and return removed value if
+    /// any. Default delimiter is [`DEFAULT_KEYS_SEPARATOR`]. The value will be removed until next (re)load.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let name = conf.remove_by_key_path("logger:name")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned. If configuration data is
+    /// pinned (see [`Config::pin`], [`Config::pin_key`]) and removal would change it then error will be returned.
+    #[inline]
+    pub fn remove_by_key_path<P>(&mut self, path: P) -> Result<Option<Value>>
+    where
+        P: AsRef<str>,
+    {
+        let delim = self.keys_delimiter.clone();
+        self.remove_with(|v| v.remove_by_key_path_with_delim(path, &delim))
+    }
+
+    fn remove_with<F>(&mut self, remove: F) -> Result<Option<Value>>
+    where
+        F: FnOnce(&mut Value) -> Result<Option<Value>>,
+    {
+        let mut value = self.value.as_ref().clone();
+        let removed = remove(&mut value)?;
+        if removed.is_some() {
+            let hash = Hash::from(value.as_bytes().as_ref());
+            self.activate(Arc::new(value), hash)?;
+        }
+        Ok(removed)
+    }
+
+    /// Render configuration data in given human oriented format (see [`Format`]). Sealed values are obfuscated and
+    /// marked with [`SEALED_MARKER`]. If provenance tracking is enabled then parsers names are rendered as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::render::Format;
+    ///
+    /// println!("{}", conf.render(Format::Table));
+    /// ```
+    ///
+    /// [`SEALED_MARKER`]: crate::render::SEALED_MARKER
+    #[cfg(feature = "render")]
+    pub fn render(&self, format: Format) -> String {
+        let sources = self
+            .provenance_report()
+            .into_iter()
+            .map(|(p, l)| (p, l.name()))
+            .collect();
+        crate::render::render(&self.value, &self.keys_delimiter, format, &sources)
+    }
+
+    /// Return [Graphviz](https://graphviz.org/) `DOT` representation of the keys tree (see [`Value::to_dot`]). If
+    /// provenance tracking is enabled then leaf nodes are annotated with parsers names as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// std::fs::write("config.dot", conf.to_dot())?;
+    /// // dot -Tsvg config.dot -o config.svg
+    /// ```
+    #[cfg(feature = "render")]
+    pub fn to_dot(&self) -> String {
+        let sources = self
+            .provenance_report()
+            .into_iter()
+            .map(|(p, l)| (p, l.name()))
+            .collect();
+        crate::render::dot(&self.value, &self.keys_delimiter, &sources)
+    }
+
+    /// Get flattened configuration data as typed records (see [`Record`]) in sorted order of key paths. Sealed values
+    /// are obfuscated. If provenance tracking is enabled then records contain names of the parsers which supplied
+    /// values.
+    #[cfg(feature = "export")]
+    pub fn records(&self) -> Vec<Record> {
+        crate::export::records(&self.value, &self.keys_delimiter, |p| {
+            self.provenance(p).map(|l| l.name().to_string())
+        })
+    }
+
+    /// Write flattened configuration data as typed records (see [`Config::records`]) in `JSON Lines` format to given
+    /// writer.
+    ///
+    /// # Errors
+    ///
+    /// If records could not be written then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut output = Vec::new();
+    /// conf.write_records(&mut output)?;
+    /// ```
+    #[cfg(feature = "export")]
+    pub fn write_records<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        for record in self.records() {
+            serde_json::to_writer(&mut writer, &record.to_json())
+                .map_err(|e| Error::SerdeError(e, "Failed to write record".into()))?;
+            writer
+                .write_all(b"\n")
+                .map_err(|e| Error::IO(e, "Failed to write record".into()))?;
+        }
+        writer
+            .flush()
+            .map_err(|e| Error::IO(e, "Failed to flush records".into()))
+    }
+
+    /// Get reference to internal [`Value`] structure.
+    #[inline]
+    pub fn get_value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Get snapshot of the current [`Value`] structure. The snapshot is not changed by subsequent (re)loads.
+    #[inline]
+    pub fn snapshot(&self) -> Arc<Value> {
+        self.value.clone()
+    }
+
+    /// Get reader (see [`ConfigReader`]) which could be shared between threads to get complete snapshots of
+    /// configuration data without any locking, even while [`Config::reload`] is in progress.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let reader = conf.reader();
+    /// std::thread::spawn(move || {
+    ///     let level: Option<String> = reader.get_by_key_path("logger:level").unwrap();
+    /// });
+    ///
+    /// conf.reload()?;
+    /// ```
+    #[inline]
+    pub fn reader(&self) -> ConfigReader {
+        ConfigReader {
+            value: self.shared.clone(),
+            keys_delimiter: self.keys_delimiter.clone(),
+        }
+    }
+
+    /// Get lazily deserialized section (see [`LazySection`]) for given key path (with keys delimiter). The section is
+    /// deserialized on first access and invalidated on (re)load, including (re)loads done after the section is created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Deserialize)]
+    /// struct Db {
+    ///     url: String,
+    ///     pool: usize,
+    /// }
+    ///
+    /// let db = conf.lazy_section::<Db>("db");
+    /// let pool = db.get()?.pool;
+    ///
+    /// conf.reload()?;
+    /// let url = &db.get()?.url;
+    /// ```
+    pub fn lazy_section<T: DeserializeOwned>(&self, path: impl Into<String>) -> LazySection<T> {
+        LazySection {
+            value: self.shared.clone(),
+            path: path.into(),
+            keys_delimiter: self.keys_delimiter.clone(),
+            cached: ArcSwap::from_pointee(CachedSection::new(self.value.clone())),
+        }
+    }
+
+    /// Get metadata (see [`ConfigMeta`]) of the current configuration data.
+    pub fn meta(&self) -> ConfigMeta {
+        ConfigMeta {
+            hash: self.hash(),
+            layers: self.layers.iter().map(|l| l.name.clone()).collect(),
+            warnings: self.warnings.clone(),
+            sealed: self.value.sealed_key_paths(&self.keys_delimiter),
+            sealed_suffix: self.sealed_suffix.clone(),
+            keys_delimiter: self.keys_delimiter.clone(),
+            case_sensitive: self.case_on,
+        }
+    }
+
+    /// Get view of the configuration which is serialized with sealed values as is (see [`Unsealed`]), unlike
+    /// [`Config`] itself. It should be used only to pass configuration to trusted receiver (e.g. spawned worker
+    /// process, see `handoff` module).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // parent
+    /// let data = serde_json::to_string(&conf.unsealed())?;
+    ///
+    /// // worker
+    /// let conf: Config = serde_json::from_str(&data)?;
+    /// ```
+    #[inline]
+    pub fn unsealed(&self) -> Unsealed<'_> {
+        Unsealed(self)
+    }
+
+    /// Create [`Config`] from fully resolved configuration data and its metadata (e.g. deserialized in worker process
+    /// after resolving by parent process), so parsers do not have to be run again. The created [`Config`] has single
+    /// layer named [`RESOLVED_LAYER`] which returns given data, the sealed values are sealed again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // parent
+    /// let data = serde_json::to_string(&conf.unsealed())?;
+    ///
+    /// // worker
+    /// let conf: Config = serde_json::from_str(&data)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If hash of the data does not match the hash in metadata then [`Error::HashMismatch`] will be returned.
+    pub fn from_parts(value: Value, meta: ConfigMeta) -> Result<Self> {
+        let delim = meta.keys_delimiter.clone();
+        let sealed = meta.sealed;
+        let mut config = ConfigBuilder::default()
+            .append_named_parser(RESOLVED_LAYER, StaticParser(value))
+            .sealed_suffix(meta.sealed_suffix)
+            .keys_delimiter(meta.keys_delimiter)
+            .merge_case(if meta.case_sensitive {
+                MergeCase::Sensitive
+            } else {
+                MergeCase::Insensitive
+            })
+            .seal_if(move |path, _| {
+                sealed.iter().any(|s| {
+                    path.strip_prefix(s.as_str())
+                        .is_some_and(|r| r.is_empty() || r.starts_with(&delim))
+                })
+            })
+            .load()?;
+        if config.hash() != meta.hash {
+            return Err(Error::HashMismatch(meta.hash));
+        }
+        config.warnings = meta.warnings;
+        Ok(config)
+    }
+
+    /// Get selected profile (see [`ConfigBuilder::profile`]) if any.
+    #[inline]
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Get key level delimiter used by [`Config::get_by_key_path`] method.
+    #[inline]
+    pub fn keys_delimiter(&self) -> &str {
+        &self.keys_delimiter
+    }
+
+    /// Get suffix used to seal secret values.
+    #[inline]
+    pub fn sealed_suffix(&self) -> &str {
+        &self.sealed_suffix
+    }
+
+    /// Create view (see [`RestrictedConfig`]) of the current configuration data which exposes only values matched by
+    /// given key path (with keys delimiter) patterns, where `*` matches any single key. The pattern also exposes all
+    /// nested values of the matched one. The sealed values are hidden entirely. It is intended for passing
+    /// configuration into untrusted (e.g. third-party plugin) code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let view = conf.restricted(["plugins:metrics", "server:*:port"]);
+    /// plugin.init(&view);
+    /// ```
+    pub fn restricted<I, S>(&self, patterns: I) -> RestrictedConfig
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns: Vec<Vec<String>> = patterns
+            .into_iter()
+            .map(|p| {
+                p.as_ref()
+                    .split(&self.keys_delimiter)
+                    .map(String::from)
+                    .collect()
+            })
+            .collect();
+        RestrictedConfig {
+            value: self.value.restricted(&patterns),
+            keys_delimiter: self.keys_delimiter.clone(),
+        }
+    }
+
+    /// Run all built-in linting rules (see [`Linter`]) and return findings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// for finding in conf.lint() {
+    ///     println!("{finding}");
+    /// }
+    /// ```
+    #[cfg(feature = "lint")]
+    #[inline]
+    pub fn lint(&self) -> Vec<Finding> {
+        Linter::default().lint(self)
+    }
+
+    /// Compare configuration data against golden `JSON` file (see [`crate::golden`]) and return differences, where
+    /// the golden file is treated as old value. The differences of ignored key paths (see [`GoldenOptions::ignore`])
+    /// are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let diff = conf.diff_golden("deploy/golden.json", &GoldenOptions::default().ignore("build:id"))?;
+    /// if !diff.is_empty() {
+    ///     eprintln!("{diff}");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If golden file could not be read or parsed then error will be returned.
+    #[cfg(feature = "golden")]
+    #[inline]
+    pub fn diff_golden<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        options: &GoldenOptions,
+    ) -> Result<ValueDiff> {
+        options.diff(&self.value, path.as_ref(), &self.keys_delimiter)
+    }
+
+    /// Returns localized configuration data value for given key path under the locale subtree of given root key path
+    /// (e.g. `messages:<locale>:greeting`). The tags of locale chain (see [`Locale::chain`]) are tried in order and
+    /// the first found value is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let greeting: Option<String> =
+    ///     conf.get_localized("messages", "greeting", &Locale::new("fr-CA").fallback("en"))?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    #[cfg(feature = "locale")]
+    pub fn get_localized<T, R, P>(&self, root: R, path: P, locale: &Locale) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        R: AsRef<str>,
+        P: AsRef<str>,
+    {
+        for tag in locale.chain() {
+            let path = self.locale_path(root.as_ref(), &tag, path.as_ref());
+            if let Some(value) = self.get_by_key_path(path)? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns localized bundle: locale subtrees of given root key path (e.g. `messages:<locale>`) merged in reverse
+    /// order of locale chain (see [`Locale::chain`]), so more specific tags override generic ones and fallbacks
+    /// supply missing values.
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    #[cfg(feature = "locale")]
+    pub fn localized<R: AsRef<str>>(&self, root: R, locale: &Locale) -> Result<Value> {
+        let mut bundle = Value::with_case(self.value.is_case_sensitive());
+        for tag in locale.chain().iter().rev() {
+            let path = self.locale_path(root.as_ref(), tag, "");
+            if let Some(value) = self.get_by_key_path::<Value, _>(path)? {
+                bundle.merge_from(&value);
+            }
+        }
+        Ok(bundle)
+    }
+
+    #[cfg(feature = "locale")]
+    fn locale_path(&self, root: &str, tag: &str, path: &str) -> String {
+        [root, tag, path]
+            .into_iter()
+            .filter(|k| !k.is_empty())
+            .collect::<Vec<_>>()
+            .join(&self.keys_delimiter)
+    }
+
+    /// Same as [`Config::diff_golden`], but panics with rendered differences if configuration data does not match
+    /// golden file. It is intended for integration tests.
+    ///
+    /// # Panics
+    ///
+    /// If golden file could not be read or configuration data does not match it.
+    #[cfg(feature = "golden")]
+    #[allow(clippy::panic)]
+    pub fn assert_matches_golden<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        options: &GoldenOptions,
+    ) {
+        let path = path.as_ref();
+        match self.diff_golden(path, options) {
+            Ok(diff) if diff.is_empty() => (),
+            Ok(diff) => panic!(
+                "Configuration does not match golden file: '{}'\n{}",
+                path.display(),
+                diff.render(&self.keys_delimiter)
+            ),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Get all layers in order in which parsers was added to [`ConfigBuilder`].
+    #[inline]
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Get layer by name. If there are several layers with same name, the first one will be returned.
+    #[inline]
+    pub fn layer<S: AsRef<str>>(&self, name: S) -> Option<&Layer> {
+        self.layers.iter().find(|l| l.name == name.as_ref())
+    }
+}
+
+impl Debug for Config {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_fmt(format_args!(
+            "Config {{ parsers: size({}), value: {:?}, case_on: {:?}, hash: {:?}, sealed_suffix: {:?}, keys_delimiter: {:?} }}",
+            self.layers.len(),
+            self.value,
+            self.case_on,
+            self.hash,
+            self.sealed_suffix,
+            self.keys_delimiter,
+        ))
+    }
+}
+
+impl Display for Config {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_fmt(format_args!("Config: {}\n{}", self.hash(), self.value))
+    }
+}
+
+impl PartialEq for Config {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl Eq for Config {}
+
+impl PartialOrd for Config {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Config {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.hash.as_bytes().cmp(other.hash.as_bytes())
+    }
+}
+
+/// The snapshot of configuration data of [`Config`] (see [`Config::history`], [`Config::checkpoint`] and
+/// [`Config::staged`]).
+#[derive(Clone)]
+pub struct HistoryEntry {
+    value: Arc<Value>,
+    hash: Hash,
+    timestamp: SystemTime,
+}
+
+impl HistoryEntry {
+    /// Get configuration data of the snapshot.
+    #[inline]
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Get hash of the configuration data of the snapshot (same as [`Config::hash`]).
+    #[inline]
+    pub fn hash(&self) -> String {
+        self.hash.named()
+    }
+
+    /// Get time when the snapshot was (re)loaded.
+    #[inline]
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+}
+
+impl Debug for HistoryEntry {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_fmt(format_args!(
+            "HistoryEntry {{ value: {:?}, hash: {:?}, timestamp: {:?} }}",
+            self.value, self.hash, self.timestamp,
+        ))
+    }
+}
+
+/// The metadata of [`Config`] which is serialized alongside its configuration data, so fully resolved configuration
+/// could be shipped between processes (see [`Config::from_parts`]).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigMeta {
+    /// The hash of configuration data (see [`Config::hash`]).
+    pub hash: String,
+    /// The names of layers (see [`Layer::name`]) which supplied configuration data.
+    pub layers: Vec<String>,
+    /// The warnings reported during last (re)load (see [`Config::warnings`]).
+    pub warnings: Vec<String>,
+    /// The key paths of sealed values. The sealed dictionaries are restored as dictionaries with sealed values.
+    pub sealed: Vec<String>,
+    /// The sealed suffix (see [`ConfigBuilder::sealed_suffix`]).
+    pub sealed_suffix: String,
+    /// The keys level delimiter (see [`ConfigBuilder::keys_delimiter`]).
+    pub keys_delimiter: String,
+    /// Case sensitivity of key names.
+    pub case_sensitive: bool,
+}
+
+#[derive(Serialize)]
+struct ConfigPartsRef<'a> {
+    value: &'a InnerValue,
+    meta: ConfigMeta,
+}
+
+#[derive(Deserialize)]
+struct ConfigParts {
+    value: Value,
+    meta: ConfigMeta,
+}
+
+/// The [`Config`] is serialized as dictionary with `value` (configuration data) and `meta` (see [`ConfigMeta`])
+/// entries. The sealed values are obfuscated, so the hash in metadata is calculated for the obfuscated data (it is the
+/// same as [`Config::hash`] if there are no sealed values). Use [`Config::unsealed`] to serialize the sealed values as
+/// is for trusted receivers.
+impl Serialize for Config {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let value = self.value.get_sealed();
+        let mut meta = self.meta();
+        meta.hash = Hash::from(value.to_string().as_bytes()).named();
+        ConfigPartsRef {
+            value: &value,
+            meta,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The view of [`Config`] which is serialized with sealed values as is (see [`Config::unsealed`]).
+#[derive(Clone, Copy)]
+pub struct Unsealed<'a>(&'a Config);
+
+/// Same as [`Serialize`] implementation of [`Config`], but the sealed values are serialized as is, so they could be
+/// used by receiver.
+impl Serialize for Unsealed<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ConfigPartsRef {
+            value: self.0.value.as_inner(),
+            meta: self.0.meta(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The [`Config`] is deserialized by [`Config::from_parts`] from representation produced by its [`Serialize`]
+/// implementation.
+impl<'de> Deserialize<'de> for Config {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let parts = ConfigParts::deserialize(deserializer)?;
+        Config::from_parts(parts.value, parts.meta).map_err(serde::de::Error::custom)
+    }
+}
+
+type SealDetector = Box<dyn Fn(&str, &Value) -> bool>;
+
+type Significance = Box<dyn Fn(&Value, &Value) -> bool>;
+
+/// The name of the layer with default configuration data (see [`ConfigBuilder::defaults`]).
+pub const DEFAULTS_LAYER: &str = "defaults";
+
+/// The name of the layer with overriding configuration data (see [`ConfigBuilder::overrides`]).
+pub const OVERRIDES_LAYER: &str = "overrides";
+
+/// The name of the single layer of [`Config`] created by [`Config::from_parts`].
+pub const RESOLVED_LAYER: &str = "resolved";
+
+/// The parser which always returns the same data.
+pub(crate) struct StaticParser(pub(crate) Value);
+
+impl Case for StaticParser {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.0.is_case_sensitive()
+    }
+}
+
+impl Parse for StaticParser {
+    #[inline]
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        Ok(self.0.clone())
+    }
+}
+
+struct Staged {
+    entry: HistoryEntry,
+    provenance: Option<BTreeMap<String, usize>>,
+}
+
+/// The cheap to clone handle to read configuration data of [`Config`] from any thread (see [`Config::reader`]).
+/// Each read sees complete result of the last (re)load, the (re)load itself does not block readers.
+#[derive(Clone)]
+pub struct ConfigReader {
+    value: Arc<ArcSwap<Value>>,
+    keys_delimiter: String,
+}
+
+impl ConfigReader {
+    /// Get snapshot of the current [`Value`] structure.
+    #[inline]
+    pub fn snapshot(&self) -> Arc<Value> {
+        self.value.load_full()
+    }
+
+    /// Same as [`Config::get_by_key_path`], but for current snapshot.
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    #[inline]
+    pub fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        self.value
+            .load()
+            .get_by_key_path_with_delim(path, &self.keys_delimiter)
+    }
+
+    /// Same as [`Config::get`], but for current snapshot.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur during deserialization then error will be returned.
+    #[inline]
+    pub fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        self.value.load().get()
+    }
+}
+
+impl Debug for ConfigReader {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_fmt(format_args!(
+            "ConfigReader {{ value: {:?}, keys_delimiter: {:?} }}",
+            self.value.load(),
+            self.keys_delimiter,
+        ))
+    }
+}
+
+/// The lazily deserialized section of configuration data (see [`Config::lazy_section`]). The section is deserialized
+/// on first access and cached until configuration data is (re)loaded, so each subsystem could keep its own typed
+/// settings struct. It could be shared between threads (e.g. in [`Arc`]), each access sees complete result of the last
+/// (re)load.
+pub struct LazySection<T> {
+    value: Arc<ArcSwap<Value>>,
+    path: String,
+    keys_delimiter: String,
+    cached: ArcSwap<CachedSection<T>>,
+}
+
+struct CachedSection<T> {
+    snapshot: Arc<Value>,
+    section: OnceLock<Arc<T>>,
+}
+
+impl<T> CachedSection<T> {
+    #[inline]
+    fn new(snapshot: Arc<Value>) -> Self {
+        Self {
+            snapshot,
+            section: OnceLock::new(),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> LazySection<T> {
+    /// Get deserialized section of the current snapshot. The section is deserialized only once per (re)load.
+    ///
+    /// # Errors
+    ///
+    /// If section does not exists or any errors will occur during deserialization then error will be returned. The
+    /// errors are not cached.
+    pub fn get(&self) -> Result<Arc<T>> {
+        let snapshot = self.value.load_full();
+        let mut cached = self.cached.load_full();
+        if !Arc::ptr_eq(&cached.snapshot, &snapshot) {
+            cached = Arc::new(CachedSection::new(snapshot.clone()));
+            self.cached.store(cached.clone());
+        }
+        if let Some(section) = cached.section.get() {
+            return Ok(section.clone());
+        }
+
+        let section: T = snapshot
+            .get_by_key_path_with_delim(&self.path, &self.keys_delimiter)?
+            .ok_or_else(|| {
+                Error::MissingKey(
+                    self.path.clone(),
+                    snapshot.suggest_key_path(&self.path, &self.keys_delimiter),
+                )
+            })?;
+        Ok(cached.section.get_or_init(|| Arc::new(section)).clone())
+    }
+
+    /// Return `true` if section of the current snapshot is already deserialized, otherwise return `false`.
+    #[inline]
+    pub fn is_cached(&self) -> bool {
+        let cached = self.cached.load();
+        Arc::ptr_eq(&cached.snapshot, &self.value.load()) && cached.section.get().is_some()
+    }
+
+    /// Get key path of the section.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl<T> Debug for LazySection<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_fmt(format_args!(
+            "LazySection {{ path: {:?}, keys_delimiter: {:?} }}",
+            self.path, self.keys_delimiter,
+        ))
+    }
+}
+
+/// The read access to configuration data, so libraries could accept `impl ConfigRead` instead of concrete [`Config`]
+/// and be used with [`ConfigReader`], plain [`Value`] (e.g. as test double) or any other backend.
+///
+/// # Example
+///
+/// ```
+/// use irx_config::{json, ConfigRead, Value};
+///
+/// fn port(conf: &impl ConfigRead) -> irx_config::Result<u16> {
+///     Ok(conf.get_by_key_path("server:port")?.unwrap_or(8080))
+/// }
+///
+/// assert_eq!(9090, port(&Value::try_from(json!({"server": {"port": 9090}}))?)?);
+/// ```
+pub trait ConfigRead {
+    /// Returns configuration data value to corresponding key/nested keys (see [`Config::get_by_keys`]).
+    ///
+    /// # Errors
+    ///
+    /// If keys is empty, the error will be returned.
+    fn get_by_keys<I, K, T>(&self, keys: I) -> Result<Option<T>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+        T: DeserializeOwned;
+
+    /// Returns configuration data value to corresponding key path with keys delimiter (see
+    /// [`Config::get_by_key_path`]).
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>;
+
+    /// Deserialize whole configuration data (see [`Config::get`]).
+    ///
+    /// # Errors
+    ///
+    /// In case of any de-serialization problems the corresponding error will be returned.
+    fn get<T: DeserializeOwned>(&self) -> Result<T>;
+}
+
+impl ConfigRead for Config {
+    #[inline]
+    fn get_by_keys<I, K, T>(&self, keys: I) -> Result<Option<T>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        Config::get_by_keys(self, keys)
+    }
+
+    #[inline]
+    fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        Config::get_by_key_path(self, path)
+    }
+
+    #[inline]
+    fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        Config::get(self)
+    }
+}
+
+impl ConfigRead for ConfigReader {
+    #[inline]
+    fn get_by_keys<I, K, T>(&self, keys: I) -> Result<Option<T>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        self.value.load().get_by_keys(keys)
+    }
+
+    #[inline]
+    fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        ConfigReader::get_by_key_path(self, path)
+    }
+
+    #[inline]
+    fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        ConfigReader::get(self)
+    }
+}
+
+impl ConfigRead for Value {
+    #[inline]
+    fn get_by_keys<I, K, T>(&self, keys: I) -> Result<Option<T>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        Value::get_by_keys(self, keys)
+    }
+
+    #[inline]
+    fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        Value::get_by_key_path(self, path)
+    }
+
+    #[inline]
+    fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        Value::get(self)
+    }
+}
+
+/// The read-only view of configuration data which exposes only allowed values (see [`Config::restricted`]).
+#[derive(Clone, Debug)]
+pub struct RestrictedConfig {
+    value: Value,
+    keys_delimiter: String,
+}
+
+impl RestrictedConfig {
+    /// Same as [`Config::get_by_key_path`], but only allowed values are visible.
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    #[inline]
+    pub fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        self.value
+            .get_by_key_path_with_delim(path, &self.keys_delimiter)
+    }
+
+    /// Same as [`Config::get`], but only allowed values are visible.
+    ///
+    /// # Errors
+    ///
+    /// In case of any de-serialization problems the corresponding error will be returned.
+    #[inline]
+    pub fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        self.value.get()
+    }
+
+    /// Get reference to [`Value`] structure with allowed values.
+    #[inline]
+    pub fn get_value(&self) -> &Value {
+        &self.value
+    }
+}
+
+impl ConfigRead for RestrictedConfig {
+    #[inline]
+    fn get_by_keys<I, K, T>(&self, keys: I) -> Result<Option<T>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        self.value.get_by_keys(keys)
+    }
+
+    #[inline]
+    fn get_by_key_path<T, P>(&self, path: P) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        RestrictedConfig::get_by_key_path(self, path)
+    }
+
+    #[inline]
+    fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        RestrictedConfig::get(self)
+    }
+}
+
+/// The builder for [`Config`] structure.
+pub struct ConfigBuilder {
+    parsers: Vec<(String, Source)>,
+    dependencies: Vec<(Option<usize>, String)>,
+    sealed_suffix: String,
+    keys_delimiter: String,
+    auto_case_on: bool,
+    merge_case: MergeCase,
+    array_merge: ArrayMerge,
+    merge_strategies: Vec<(String, MergeStrategy)>,
+    ttls: Vec<(String, Duration)>,
+    #[cfg(feature = "schema")]
+    schema: Option<Schema>,
+    default_templates: bool,
+    interpolation: bool,
+    env_interpolation: bool,
+    seal_detector: Option<SealDetector>,
+    strict_sealing: bool,
+    significance: Vec<(String, Significance)>,
+    provenance: bool,
+    offline: bool,
+    offline_option: Option<String>,
+    history_size: usize,
+    #[cfg(feature = "access")]
+    record_access: bool,
+    profile: Option<String>,
+    defaults: Option<Value>,
+    default_values: Vec<(String, Value)>,
+    overrides: Option<Value>,
+}
+
+impl ConfigBuilder {
+    /// Append a parser to [`Config`]. First appended parser will have highest priority during (re)load merge, the last
+    /// one will have lowest priority.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::parsers::{env, json};
+    /// use irx_config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_parser(
+    ///         json::ParserBuilder::default()
+    ///             .default_path("config.json")
+    ///             .build()?,
+    ///     )
+    ///     .append_parser(
+    ///         env::ParserBuilder::default()
+    ///             .default_prefix("APP_")
+    ///             .build()?,
+    ///     )
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn append_parser<P>(self, parser: P) -> Self
+    where
+        P: Parse + 'static,
+    {
+        let name = format!("parser #{}", self.parsers.len() + 1);
+        self.append_named_parser(name, parser)
+    }
+
+    /// Append a parser to [`Config`] with given layer name (see [`Layer::name`]). The priority is the same as for
+    /// [`ConfigBuilder::append_parser`] method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::parsers::json;
+    /// use irx_config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_named_parser(
+    ///         "config",
+    ///         json::ParserBuilder::default()
+    ///             .default_path("config.json")
+    ///             .build()?,
+    ///     )
+    ///     .load()?;
+    ///
+    /// let layer = config.layer("config").unwrap();
+    /// ```
+    #[inline]
+    pub fn append_named_parser<S, P>(mut self, name: S, parser: P) -> Self
+    where
+        S: Into<String>,
+        P: Parse + 'static,
+    {
+        self.auto_case_on = self.auto_case_on && parser.is_case_sensitive();
+        self.parsers
+            .push((name.into(), Source::Sync(Box::new(parser))));
+        self
+    }
+
+    /// Set default configuration data which are installed as the lowest priority layer named [`DEFAULTS_LAYER`]
+    /// after all appended parsers, regardless of the call order. The previous defaults (if any) are replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::parsers::env;
+    /// use irx_config::{json, ConfigBuilder, Value};
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .defaults(Value::try_from(json!({ "logger": { "level": "info" } }))?)
+    ///     .append_parser(
+    ///         env::ParserBuilder::default()
+    ///             .default_prefix("APP_")
+    ///             .build()?,
+    ///     )
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn defaults(mut self, value: Value) -> Self {
+        self.defaults = Some(value);
+        self
+    }
+
+    /// Set default configuration data (see [`ConfigBuilder::defaults`]) from any type which implements [`Serialize`]
+    /// trait (e.g. `Default` instance of the settings structure).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let config = ConfigBuilder::default()
+    ///     .defaults_from(Settings::default())?
+    ///     .append_parser(parser)
+    ///     .load()?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If given data could not be serialized then error will be returned.
+    #[inline]
+    pub fn defaults_from<T: Serialize>(self, value: T) -> Result<Self> {
+        Ok(self.defaults(Value::try_from(value)?))
+    }
+
+    /// Set default value of any type which implements [`Serialize`] trait for given key path (see
+    /// [`ConfigBuilder::defaults`]). The key path is split by keys delimiter (see [`ConfigBuilder::keys_delimiter`])
+    /// and the keys are matched according to merge case mode (see [`ConfigBuilder::merge_case`]) at build time, so the
+    /// order of calls does not matter. The values are set on top of data given by [`ConfigBuilder::defaults`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let config = ConfigBuilder::default()
+    ///     .set_default("logger:level", "info")?
+    ///     .set_default("db:pool", 4)?
+    ///     .append_parser(parser)
+    ///     .load()?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If given value could not be serialized then error will be returned.
+    pub fn set_default<P, T>(mut self, path: P, value: T) -> Result<Self>
+    where
+        P: Into<String>,
+        T: Serialize,
+    {
+        self.default_values
+            .push((path.into(), Value::try_from(value)?));
+        Ok(self)
+    }
+
+    /// Set overriding configuration data which are installed as the highest priority layer named
+    /// [`OVERRIDES_LAYER`] before all appended parsers, regardless of the call order, so it is the first one in
+    /// [`Config::layers`]. It could be used to force settings programmatically (e.g. in test harnesses). The previous
+    /// overrides (if any) are replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let config = ConfigBuilder::default()
+    ///     .append_parser(parser)
+    ///     .overrides(Value::try_from(json!({ "db": { "url": "sqlite::memory:" } }))?)
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn overrides(mut self, value: Value) -> Self {
+        self.overrides = Some(value);
+        self
+    }
+
+    /// Set overriding configuration data (see [`ConfigBuilder::overrides`]) from any type which implements
+    /// [`Serialize`] trait.
+    ///
+    /// # Errors
+    ///
+    /// If given data could not be serialized then error will be returned.
     #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        self.hash == other.hash
+    pub fn overrides_from<T: Serialize>(self, value: T) -> Result<Self> {
+        Ok(self.overrides(Value::try_from(value)?))
     }
-}
-
-impl Eq for Config {}
 
-impl PartialOrd for Config {
+    /// Append an asynchronous parser (see [`AsyncParse`]) to [`Config`]. The priority is the same as for
+    /// [`ConfigBuilder::append_parser`] method. [`Config`] with such parsers could be (re)loaded only by
+    /// [`ConfigBuilder::load_async`] and [`Config::reload_async`] methods.
+    #[cfg(feature = "async")]
     #[inline]
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    pub fn append_async_parser<P>(self, parser: P) -> Self
+    where
+        P: AsyncParse + 'static,
+    {
+        let name = format!("parser #{}", self.parsers.len() + 1);
+        self.append_named_async_parser(name, parser)
     }
-}
 
-impl Ord for Config {
+    /// Append an asynchronous parser (see [`AsyncParse`]) to [`Config`] with given layer name (see [`Layer::name`]).
+    /// The priority is the same as for [`ConfigBuilder::append_parser`] method.
+    #[cfg(feature = "async")]
     #[inline]
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.hash.as_bytes().cmp(other.hash.as_bytes())
+    pub fn append_named_async_parser<S, P>(mut self, name: S, parser: P) -> Self
+    where
+        S: Into<String>,
+        P: AsyncParse + 'static,
+    {
+        self.auto_case_on = self.auto_case_on && parser.is_case_sensitive();
+        self.parsers
+            .push((name.into(), Source::Async(Box::new(parser))));
+        self
     }
-}
-
-/// The builder for [`Config`] structure.
-pub struct ConfigBuilder {
-    parsers: Vec<AnyParser>,
-    sealed_suffix: String,
-    keys_delimiter: String,
-    auto_case_on: bool,
-    merge_case: MergeCase,
-}
 
-impl ConfigBuilder {
-    /// Append a parser to [`Config`]. First appended parser will have highest priority during (re)load merge, the last
-    /// one will have lowest priority.
+    /// Declare that the last appended parser takes option with given key path (with keys delimiter) from other
+    /// parsers, in addition to options declared by the parser itself (see [`Parse::depends_on`]). The dependent
+    /// parsers are called after all parsers without dependencies and after other dependent parsers which supply the
+    /// option, so the option is available regardless of the appended order. The merge priority is not changed. If
+    /// dependencies are cyclic then [`Error::UnresolvedDependency`] will be returned during (re)load.
     ///
     /// # Example
     ///
@@ -265,8 +2568,10 @@ impl ConfigBuilder {
     ///     .append_parser(
     ///         json::ParserBuilder::default()
     ///             .default_path("config.json")
+    ///             .path_option("config")
     ///             .build()?,
     ///     )
+    ///     .depends_on("config")
     ///     .append_parser(
     ///         env::ParserBuilder::default()
     ///             .default_prefix("APP_")
@@ -275,12 +2580,12 @@ impl ConfigBuilder {
     ///     .load()?;
     /// ```
     #[inline]
-    pub fn append_parser<P>(mut self, parser: P) -> Self
+    pub fn depends_on<S>(mut self, path: S) -> Self
     where
-        P: Parse + 'static,
+        S: Into<String>,
     {
-        self.auto_case_on = self.auto_case_on && parser.is_case_sensitive();
-        self.parsers.push(Box::new(parser));
+        let idx = self.parsers.len().checked_sub(1);
+        self.dependencies.push((idx, path.into()));
         self
     }
 
@@ -312,6 +2617,39 @@ impl ConfigBuilder {
         self
     }
 
+    /// Set detector which decides if value for given key path (with keys delimiter) should be sealed, in addition to
+    /// sealed suffix (see [`ConfigBuilder::sealed_suffix`]). The detector is called for every value which is not a
+    /// dictionary during sealing after merge.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_parser(parser)
+    ///     .seal_if(|path, _| path.ends_with("password") || path.ends_with("token"))
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn seal_if<F>(mut self, detector: F) -> Self
+    where
+        F: Fn(&str, &Value) -> bool + 'static,
+    {
+        self.seal_detector = Some(Box::new(detector));
+        self
+    }
+
+    /// The sealed suffix (see [`ConfigBuilder::sealed_suffix`]) is stripped from key exactly once, so key with the
+    /// same name could be defined both with and without suffix (e.g. `password` and `password_sealed_`). The sealed
+    /// value is used in such case. If set to `true` then such collision is reported as error during (re)load,
+    /// otherwise warnings are reported (see [`Config::warnings`]). Default is `false`.
+    #[inline]
+    pub fn strict_sealing(mut self, on: bool) -> Self {
+        self.strict_sealing = on;
+        self
+    }
+
     /// Set default key level delimiter. Default is [`DEFAULT_KEYS_SEPARATOR`].
     ///
     /// # Example
@@ -344,30 +2682,423 @@ impl ConfigBuilder {
         self
     }
 
+    /// Set merge mode for arrays (see [`ArrayMerge`]). Default is [`ArrayMerge::Replace`].
+    #[inline]
+    pub fn array_merge(mut self, arrays: ArrayMerge) -> Self {
+        self.array_merge = arrays;
+        self
+    }
+
+    /// Set merge strategy (see [`MergeStrategy`]) for given key path, which overrides default merge behavior for that
+    /// key path during (re)load. Could be called several times for different key paths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::parsers::{env, json};
+    /// use irx_config::{ConfigBuilder, MergeStrategy};
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_parser(
+    ///         env::ParserBuilder::default()
+    ///             .default_prefix("APP_")
+    ///             .build()?,
+    ///     )
+    ///     .append_parser(
+    ///         json::ParserBuilder::default()
+    ///             .default_path("config.json")
+    ///             .build()?,
+    ///     )
+    ///     .merge_strategy_for("plugins", MergeStrategy::Append)
+    ///     .merge_strategy_for("logger", MergeStrategy::Replace)
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn merge_strategy_for<P>(mut self, path: P, strategy: MergeStrategy) -> Self
+    where
+        P: Into<String>,
+    {
+        self.merge_strategies.push((path.into(), strategy));
+        self
+    }
+
+    /// Mark subtree for given key path (with keys delimiter) with TTL, e.g. for tokens fetched from secret stores.
+    /// The values of subtree become stale after TTL is elapsed since the last parse of the layer which supplied them
+    /// (see [`Config::is_fresh`] and [`Config::refresh_expiring`]). The TTL of the longest matching key path is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use irx_config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_parser(vault_parser)
+    ///     .append_parser(file_parser)
+    ///     .ttl("auth:token", Duration::from_secs(300))
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn ttl<P>(mut self, path: P, ttl: Duration) -> Self
+    where
+        P: Into<String>,
+    {
+        self.ttls.push((path.into(), ttl));
+        self
+    }
+
+    /// Set predicate which decides whether change of value for given key path (with keys delimiter) is significant,
+    /// it gets old and new values. If all changes found by [`Config::reload`] are insignificant then the current
+    /// configuration data is kept, so noisy sources (e.g. remote ones which re-serialize floats differently) do not
+    /// cause reload churn. The added and removed values are always significant. Could be called several times for
+    /// different key paths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{config::relative_change, ConfigBuilder};
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_parser(parser)
+    ///     .significance("limits:rate", relative_change(0.01))
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn significance<P, F>(mut self, path: P, predicate: F) -> Self
+    where
+        P: Into<String>,
+        F: Fn(&Value, &Value) -> bool + 'static,
+    {
+        self.significance.push((path.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Set schema (see [`Schema`]) which will be used to inject default values and to coerce types of merged
+    /// configuration data during (re)load.
+    #[cfg(feature = "schema")]
+    #[inline]
+    pub fn schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// If set to `true` then [`DEFAULT_TEMPLATE_KEY`] entry of any dictionary will be removed and deeply merged
+    /// into every sibling dictionary entry during (re)load, the sibling values have higher priority. It helps to avoid
+    /// repeating of common settings in per-instance maps. Default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```yaml
+    /// services:
+    ///   __default__:
+    ///     timeout: 5
+    ///   auth:
+    ///     url: http://auth.local
+    ///   billing:
+    ///     url: http://billing.local
+    ///     timeout: 10
+    /// ```
+    ///
+    /// will be loaded as:
+    ///
+    /// ```yaml
+    /// services:
+    ///   auth:
+    ///     url: http://auth.local
+    ///     timeout: 5
+    ///   billing:
+    ///     url: http://billing.local
+    ///     timeout: 10
+    /// ```
+    #[inline]
+    pub fn default_templates(mut self, on: bool) -> Self {
+        self.default_templates = on;
+        self
+    }
+
+    /// If set to `true` then `${key:path}` references in string values will be expanded with values of given key
+    /// paths from merged configuration during (re)load, so the same value does not have to be repeated in several
+    /// places. The key path is split by [`ConfigBuilder::keys_delimiter`]. If string consists of a single reference,
+    /// then it is replaced by referenced value as is (e.g. number or dictionary), otherwise the referenced value is
+    /// inserted as text. The referenced values are expanded too, `$${` could be used to get literal `${`. Missing and
+    /// cyclic references are errors. Default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```yaml
+    /// settings:
+    ///   host: db.local
+    ///   port: 5432
+    /// database:
+    ///   url: postgres://${settings:host}:${settings:port}/app
+    ///   port: ${settings:port}
+    /// ```
+    ///
+    /// will be loaded as:
+    ///
+    /// ```yaml
+    /// settings:
+    ///   host: db.local
+    ///   port: 5432
+    /// database:
+    ///   url: postgres://db.local:5432/app
+    ///   port: 5432
+    /// ```
+    #[inline]
+    pub fn interpolation(mut self, on: bool) -> Self {
+        self.interpolation = on;
+        self
+    }
+
+    /// If set to `true` then `${ENV:NAME}` and `$NAME` references in string values will be expanded with values of
+    /// environment variables during (re)load, independently from environment variables parser. The `$$` could be used
+    /// to get literal `$`. Missing variables are errors. It could be combined with [`ConfigBuilder::interpolation`],
+    /// then `${ENV:...}` references are not treated as key paths. The sealed values (see
+    /// [`ConfigBuilder::sealed_suffix`], [`Parse::sealed_keys`]) are left as is. Default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```yaml
+    /// cache:
+    ///   dir: ${ENV:HOME}/.cache/app
+    ///   owner: $USER
+    /// ```
+    #[inline]
+    pub fn env_interpolation(mut self, on: bool) -> Self {
+        self.env_interpolation = on;
+        self
+    }
+
+    /// Select profile (e.g. `dev`, `staging` or `prod`) which overlays are merged over the base configuration data
+    /// during (re)load. The overlays are:
+    ///
+    /// * [`DEFAULT_PROFILES_KEY`] subtree of each parser's data: `profiles:<profile>` entry is deeply merged over the
+    ///   rest of that data, the whole subtree is removed (even for other profiles);
+    /// * sibling files of file parsers: `config.<profile>.yaml` is loaded (if exists) after `config.yaml` and merged
+    ///   over it (see [`Parse::select_profile`]).
+    ///
+    /// The overlays have the priority of their parsers, so e.g. command-line arguments still win over profile of
+    /// configuration file. Default is no profile, the [`DEFAULT_PROFILES_KEY`] subtree is kept as is.
+    ///
+    /// # Example
+    ///
+    /// ```yaml
+    /// logger:
+    ///   level: debug
+    /// profiles:
+    ///   prod:
+    ///     logger:
+    ///       level: warn
+    /// ```
+    ///
+    /// will be loaded with `prod` profile as:
+    ///
+    /// ```yaml
+    /// logger:
+    ///   level: warn
+    /// ```
+    #[inline]
+    pub fn profile<S>(mut self, profile: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// If set to `true` then for every key path the parser which supplied its value will be recorded during (re)load
+    /// (see [`Config::provenance`]). Default is `false`.
+    #[inline]
+    pub fn provenance(mut self, on: bool) -> Self {
+        self.provenance = on;
+        self
+    }
+
+    /// If set to `true` then key paths read via [`Config`] getters will be recorded (see
+    /// [`Config::accessed_key_paths`]). It is intended for instrumentation runs, so teams could find dead
+    /// configuration keys and generate minimal schemas or restricted views. Default is `false`.
+    #[cfg(feature = "access")]
+    #[inline]
+    pub fn record_access(mut self, on: bool) -> Self {
+        self.record_access = on;
+        self
+    }
+
+    /// Set number of last (re)loaded snapshots of configuration data to be kept in history (see [`Config::history`]
+    /// and [`Config::rollback`]). Default is `0`, history is disabled.
+    #[inline]
+    pub fn history(mut self, n: usize) -> Self {
+        self.history_size = n;
+        self
+    }
+
+    /// If set to `true` then remote parsers (see [`Parse::is_remote`]) will not be called during (re)load, the values
+    /// loaded by them previously (if any) will be used instead and warnings will be reported (see
+    /// [`Config::warnings`]). Default is `false`.
+    #[inline]
+    pub fn offline(mut self, on: bool) -> Self {
+        self.offline = on;
+        self
+    }
+
+    /// Set offline option key path which could be used to get offline mode (see [`ConfigBuilder::offline`]) from
+    /// previous parsing results (e.g. from command-line or environment variables parsers).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::parsers::{cloud_metadata, env};
+    /// use irx_config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_parser(
+    ///         env::ParserBuilder::default()
+    ///             .default_prefix("APP_")
+    ///             .build()?,
+    ///     )
+    ///     .append_parser(cloud_metadata::ParserBuilder::default().build()?)
+    ///     .offline_option("offline")
+    ///     .load()?;
+    /// ```
+    #[inline]
+    pub fn offline_option<S>(mut self, option: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.offline_option = Some(option.into());
+        self
+    }
+
     /// Load all data from all previously appended parsers, merge data according to appended order and return [`Config`].
     ///
     /// # Errors
     ///
-    /// If any errors will occur during parsing/merging then error will be returned.
+    /// If any dependency (see [`ConfigBuilder::depends_on`]) could not be resolved or any errors will occur during
+    /// parsing/merging then error will be returned.
     pub fn load(self) -> Result<Config> {
-        let value = Value::default();
+        let mut config = self.build()?;
+        config.reload()?;
+        Ok(config)
+    }
+
+    /// Same as [`ConfigBuilder::load`], but asynchronous parsers (see [`AsyncParse`]) are awaited.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur during parsing/merging then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::asynchronous::Blocking;
+    /// use irx_config::parsers::json;
+    /// use irx_config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .append_async_parser(Blocking::new(
+    ///         json::ParserBuilder::default()
+    ///             .default_path("config.json")
+    ///             .build()?,
+    ///     ))
+    ///     .load_async()
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn load_async(self) -> Result<Config> {
+        let mut config = self.build()?;
+        config.reload_async().await?;
+        Ok(config)
+    }
+
+    fn build(mut self) -> Result<Config> {
+        if !self.default_values.is_empty() {
+            let case_on = MergeCase::Insensitive != self.merge_case;
+            let mut defaults = self
+                .defaults
+                .take()
+                .unwrap_or_else(|| Value::with_case(case_on));
+            for (path, value) in self.default_values.drain(..) {
+                defaults.set_by_key_path_with_delim(path, &self.keys_delimiter, value)?;
+            }
+            self.defaults = Some(defaults);
+        }
+        if let Some(value) = self.defaults.take() {
+            self.auto_case_on = self.auto_case_on && value.is_case_sensitive();
+            let parser = Source::Sync(Box::new(StaticParser(value)));
+            self.parsers.push((DEFAULTS_LAYER.into(), parser));
+        }
+        let overrides = self.overrides.take().map(|v| {
+            self.auto_case_on = self.auto_case_on && v.is_case_sensitive();
+            let parser = Source::Sync(Box::new(StaticParser(v)));
+            Layer::new(OVERRIDES_LAYER.into(), 0, parser, &self.sealed_suffix)
+        });
+        let mut layers: Vec<_> = self
+            .parsers
+            .into_iter()
+            .enumerate()
+            .map(|(i, (n, p))| Layer::new(n, i + 1, p, &self.sealed_suffix))
+            .collect();
+        if let Some(ref profile) = self.profile {
+            layers
+                .iter_mut()
+                .for_each(|l| l.parser.select_profile(profile));
+        }
+        for (idx, path) in self.dependencies {
+            match idx {
+                Some(i) if !path.is_empty() => layers[i].depends_on.push(path),
+                _ => return Err(Error::UnresolvedDependency(idx.map_or(0, |i| i + 1), path)),
+            }
+        }
+        if let Some(overrides) = overrides {
+            layers.insert(0, overrides);
+        }
+
+        let value = Arc::new(Value::default());
         let hash = Hash::from(value.as_bytes().as_ref());
         let case_on = if MergeCase::Auto == self.merge_case {
             self.auto_case_on
         } else {
             MergeCase::Sensitive == self.merge_case
         };
+        let mut merge_rules = MergeRules::new(self.array_merge);
+        for (path, strategy) in self.merge_strategies {
+            let keys = path.split(&self.keys_delimiter).map(String::from);
+            merge_rules.strategy_for(keys.collect(), strategy);
+        }
 
-        let mut config = Config {
-            parsers: self.parsers,
+        Ok(Config {
+            layers,
+            shared: Arc::new(ArcSwap::new(value.clone())),
             value,
             case_on,
             hash,
             sealed_suffix: self.sealed_suffix,
             keys_delimiter: self.keys_delimiter,
-        };
-        config.reload()?;
-        Ok(config)
+            merge_rules,
+            offline: self.offline,
+            offline_option: self.offline_option,
+            history: Default::default(),
+            history_size: self.history_size,
+            checkpoints: Default::default(),
+            pinned: None,
+            pinned_keys: Default::default(),
+            staged: None,
+            ttls: self.ttls,
+            warnings: Default::default(),
+            provenance: self.provenance.then(Default::default),
+            #[cfg(feature = "schema")]
+            schema: self.schema,
+            default_templates: self.default_templates,
+            interpolation: self.interpolation,
+            env_interpolation: self.env_interpolation,
+            seal_detector: self.seal_detector,
+            strict_sealing: self.strict_sealing,
+            significance: self.significance,
+            suppressed: false,
+            #[cfg(feature = "access")]
+            accessed: self.record_access.then(Default::default),
+            profile: self.profile,
+        })
     }
 
     /// Load data from one parser and return [`Config`].
@@ -405,10 +3136,127 @@ impl Default for ConfigBuilder {
     fn default() -> Self {
         Self {
             parsers: Default::default(),
+            dependencies: Default::default(),
             sealed_suffix: Default::default(),
             keys_delimiter: DEFAULT_KEYS_SEPARATOR.to_string(),
             auto_case_on: true,
             merge_case: Default::default(),
+            array_merge: Default::default(),
+            merge_strategies: Default::default(),
+            ttls: Default::default(),
+            #[cfg(feature = "schema")]
+            schema: None,
+            default_templates: false,
+            interpolation: false,
+            env_interpolation: false,
+            seal_detector: None,
+            strict_sealing: false,
+            significance: Vec::new(),
+            provenance: false,
+            offline: false,
+            offline_option: None,
+            history_size: 0,
+            #[cfg(feature = "access")]
+            record_access: false,
+            profile: None,
+            defaults: None,
+            default_values: Vec::new(),
+            overrides: None,
+        }
+    }
+}
+
+/// Create predicate for [`ConfigBuilder::significance`] which treats change of numeric value as significant if it is
+/// not less than given fraction of the old value (e.g. `0.01` is 1%). Other changes are always significant.
+pub fn relative_change(threshold: f64) -> impl Fn(&Value, &Value) -> bool {
+    move |old, new| match (old.get::<f64>(), new.get::<f64>()) {
+        (Ok(o), Ok(n)) if o != 0.0 => ((n - o) / o).abs() >= threshold,
+        _ => true,
+    }
+}
+
+fn is_option_on(option: Option<&str>, value: &Value, delim: &str) -> bool {
+    option.is_some_and(|o| {
+        value
+            .get_by_key_path_with_delim(o, delim)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    })
+}
+
+#[cfg(any(feature = "unused", feature = "access"))]
+fn path_keys(path: &serde_ignored::Path, keys: &mut Vec<String>) {
+    use serde_ignored::Path;
+
+    match path {
+        Path::Root => (),
+        Path::Seq { parent, index } => {
+            path_keys(parent, keys);
+            keys.push(index.to_string());
+        }
+        Path::Map { parent, key } => {
+            path_keys(parent, keys);
+            keys.push(key.clone());
+        }
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => path_keys(parent, keys),
+    }
+}
+
+struct Provenance<'a> {
+    layers: &'a [Layer],
+    case_on: bool,
+    suffix: CowString<'a>,
+    delim: &'a str,
+    result: BTreeMap<String, usize>,
+}
+
+impl Provenance<'_> {
+    fn walk(&mut self, value: &InnerValue, keys: &mut Vec<String>, path: &mut Vec<String>) {
+        let InnerValue::Object(m) = value else {
+            return;
+        };
+
+        for (k, v) in m {
+            keys.push(k.clone());
+            let key = if self.suffix.is_empty() {
+                k.as_str()
+            } else {
+                k.trim_end_matches(self.suffix.as_ref())
+            };
+            path.push(key.to_string());
+            if let Some(idx) = self.supplier(keys, v) {
+                self.result.insert(path.join(self.delim), idx);
+            }
+            self.walk(v, keys, path);
+            path.pop();
+            keys.pop();
         }
     }
+
+    fn supplier(&self, keys: &[String], value: &InnerValue) -> Option<usize> {
+        let found: Vec<_> = self
+            .layers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, l)| self.find(l.raw_value.as_inner(), keys).map(|v| (i, v)))
+            .collect();
+        found
+            .iter()
+            .find(|(_, v)| value.is_object() || *v == value)
+            .or_else(|| found.first())
+            .map(|(i, _)| *i)
+    }
+
+    fn find<'v>(&self, value: &'v InnerValue, keys: &[String]) -> Option<&'v InnerValue> {
+        keys.iter().try_fold(value, |v, key| match v {
+            InnerValue::Object(m) => m
+                .iter()
+                .find(|(k, _)| crate::normalize_case(k, self.case_on) == key.as_str())
+                .map(|(_, v)| v),
+            _ => None,
+        })
+    }
 }
@@ -0,0 +1,218 @@
+//! This module provide [`convert`] function which transforms configuration data between `JSON`, `JSON5`, `YAML` and
+//! `TOML` formats with optional keys normalization (see [`Options`]). It is a library primitive for commands like
+//! `myapp config convert`.
+//!
+//! The data is loaded by the same loaders as used by file based parsers (see [`Load`]). The formats are available
+//! according to enabled parsers features: `json`, `json5-parser`, `yaml` and `toml-parser`.
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::convert::{Format, KeyCase, Options};
+//! use std::fs::File;
+//!
+//! let options = Options {
+//!     key_case: KeyCase::Snake,
+//! };
+//! irx_config::convert(
+//!     File::open("config.yaml")?,
+//!     Format::Yaml,
+//!     std::io::stdout(),
+//!     Format::Toml,
+//!     &options,
+//! )?;
+//! ```
+//!
+//! [`Load`]: crate::parsers::Load
+
+use crate::{parsers::Load, AnyError};
+use serde_json::{Map, Value as InnerValue};
+use std::{
+    io::{Error as IoError, Read, Write},
+    path::Path,
+    result::Result as StdResult,
+};
+
+/// A result type for conversion errors.
+pub type Result<T> = StdResult<T, Error>;
+
+/// All errors for conversion.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to load data in {1:?} format")]
+    Load(#[source] AnyError, Format),
+    #[error("Failed to write data in {1:?} format")]
+    Write(#[source] AnyError, Format),
+    #[error("Failed to write data")]
+    IO(#[source] IoError),
+}
+
+/// Supported formats of configuration data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// `JSON` format.
+    #[cfg(feature = "json")]
+    Json,
+    /// `JSON5` format. The data is written as `JSON` which is valid `JSON5`.
+    #[cfg(feature = "json5-parser")]
+    Json5,
+    /// `YAML` format.
+    #[cfg(feature = "yaml")]
+    Yaml,
+    /// `TOML` format.
+    #[cfg(feature = "toml-parser")]
+    Toml,
+}
+
+impl Format {
+    /// Detect format by extension of given path. If extension is not known then `None` will be returned.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            #[cfg(feature = "json")]
+            "json" => Some(Self::Json),
+            #[cfg(feature = "json5-parser")]
+            "json5" => Some(Self::Json5),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(Self::Yaml),
+            #[cfg(feature = "toml-parser")]
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    fn load(self, reader: impl Read) -> Result<InnerValue> {
+        let result = match self {
+            #[cfg(feature = "json")]
+            Self::Json => crate::parsers::json::LoadJson.load(reader),
+            #[cfg(feature = "json5-parser")]
+            Self::Json5 => crate::parsers::json5::LoadJson.load(reader),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => crate::parsers::yaml::LoadYaml.load(reader),
+            #[cfg(feature = "toml-parser")]
+            Self::Toml => crate::parsers::toml::LoadToml.load(reader),
+        };
+        result
+            .map(|v| v.as_inner().clone())
+            .map_err(|e| Error::Load(e, self))
+    }
+
+    fn write(self, value: &InnerValue, mut writer: impl Write) -> Result<()> {
+        let data = match self {
+            #[cfg(feature = "json")]
+            Self::Json => serde_json::to_string_pretty(value).map_err(AnyError::from),
+            #[cfg(feature = "json5-parser")]
+            Self::Json5 => serde_json::to_string_pretty(value).map_err(AnyError::from),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => serde_yaml::to_string(value).map_err(AnyError::from),
+            #[cfg(feature = "toml-parser")]
+            Self::Toml => toml::to_string_pretty(value).map_err(AnyError::from),
+        }
+        .map_err(|e| Error::Write(e, self))?;
+
+        writer.write_all(data.as_bytes()).map_err(Error::IO)?;
+        if !data.ends_with('\n') {
+            writer.write_all(b"\n").map_err(Error::IO)?;
+        }
+        writer.flush().map_err(Error::IO)
+    }
+}
+
+/// Normalization modes of keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum KeyCase {
+    /// Keys are kept as is.
+    #[default]
+    Preserve,
+    /// Keys are converted to lower case.
+    Lower,
+    /// Keys are converted to upper case.
+    Upper,
+    /// Keys are converted to `snake_case`.
+    Snake,
+    /// Keys are converted to `kebab-case`.
+    Kebab,
+}
+
+impl KeyCase {
+    fn apply(self, key: &str) -> String {
+        match self {
+            Self::Preserve => key.to_string(),
+            Self::Lower => key.to_lowercase(),
+            Self::Upper => key.to_uppercase(),
+            Self::Snake => words(key).join("_"),
+            Self::Kebab => words(key).join("-"),
+        }
+    }
+}
+
+/// The options of conversion.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Normalization mode of keys.
+    pub key_case: KeyCase,
+}
+
+/// Load configuration data from `reader` in `in_fmt` format and write it to `writer` in `out_fmt` format with given
+/// options.
+///
+/// # Errors
+///
+/// If data could not be loaded or written (e.g. `TOML` does not support `null` values) then error will be returned.
+pub fn convert<R, W>(
+    reader: R,
+    in_fmt: Format,
+    writer: W,
+    out_fmt: Format,
+    options: &Options,
+) -> Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let mut value = in_fmt.load(reader)?;
+    if KeyCase::Preserve != options.key_case {
+        value = normalize(value, options.key_case);
+    }
+    out_fmt.write(&value, writer)
+}
+
+fn normalize(value: InnerValue, case: KeyCase) -> InnerValue {
+    match value {
+        InnerValue::Object(m) => InnerValue::Object(
+            m.into_iter()
+                .map(|(k, v)| (case.apply(&k), normalize(v, case)))
+                .collect::<Map<_, _>>(),
+        ),
+        InnerValue::Array(a) => {
+            InnerValue::Array(a.into_iter().map(|v| normalize(v, case)).collect())
+        }
+        v => v,
+    }
+}
+
+fn words(key: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in key.chars() {
+        if matches!(c, '_' | '-' | ' ' | '.') {
+            if !word.is_empty() {
+                result.push(std::mem::take(&mut word));
+            }
+            prev_lower = false;
+            continue;
+        }
+
+        if c.is_uppercase() && prev_lower {
+            result.push(std::mem::take(&mut word));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        result.push(word);
+    }
+    result
+}
@@ -0,0 +1,217 @@
+//! This module provide [`DbusService`] which exposes configuration of the running application over D-Bus, so Linux
+//! desktop applications and system daemons could be inspected/reconfigured with standard tooling (`busctl`,
+//! `gdbus`, etc.).
+//!
+//! The service implements [`INTERFACE_NAME`] interface with the following members:
+//!
+//! * `Get(key_path: s) -> s` -- return `JSON` representation of the value for given key path (empty key path for whole
+//!   configuration). The sealed values will be obfuscated.
+//! * `Set(key_path: s, value: s)` -- set `JSON` value for given key path to [`PushLayer`] and request reload.
+//! * `Reload()` -- request reload.
+//! * `Changed(hash: s)` -- signal emitted by [`DbusService::publish`] when configuration was changed.
+//!
+//! Since [`Config`] is not shared between threads, the service works with configuration snapshot published via
+//! [`DbusService::publish`] and requests reload via callback, so application could call [`Config::reload`] and
+//! publish new snapshot.
+//!
+//! To enable that module one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["dbus"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::dbus::DbusService;
+//! use irx_config::push::PushLayer;
+//! use irx_config::ConfigBuilder;
+//! use std::sync::mpsc;
+//!
+//! let layer = PushLayer::default();
+//! let (tx, rx) = mpsc::sync_channel(1);
+//! let service = DbusService::session("org.example.MyApp", layer.clone(), move || {
+//!     let _ = tx.try_send(());
+//! })?;
+//!
+//! let mut config = ConfigBuilder::default().append_parser(layer).load()?;
+//! service.publish(&config)?;
+//! while rx.recv().is_ok() {
+//!     config.reload()?;
+//!     service.publish(&config)?;
+//! }
+//! ```
+
+use crate::{push::PushLayer, Config, Value, DEFAULT_KEYS_SEPARATOR};
+use serde_json::Value as InnerValue;
+use std::sync::{Arc, Mutex, PoisonError};
+use zbus::{
+    blocking::{connection::Builder, Connection},
+    fdo, interface,
+    object_server::SignalEmitter,
+};
+
+/// The D-Bus interface name implemented by [`DbusService`].
+pub const INTERFACE_NAME: &str = "org.irx.Config1";
+
+/// The default D-Bus object path for [`DbusService`].
+pub const DEFAULT_OBJECT_PATH: &str = "/org/irx/Config";
+
+/// All errors for D-Bus service.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("D-Bus operation failed")]
+    Dbus(#[source] zbus::Error),
+}
+
+#[derive(Default)]
+struct Snapshot {
+    value: Value,
+    hash: String,
+    keys_delimiter: String,
+}
+
+struct ConfigInterface {
+    layer: PushLayer,
+    snapshot: Arc<Mutex<Snapshot>>,
+    on_reload: Box<dyn Fn() + Send + Sync>,
+}
+
+#[interface(name = "org.irx.Config1")]
+impl ConfigInterface {
+    fn get(&self, key_path: &str) -> fdo::Result<String> {
+        let snapshot = self.snapshot.lock().unwrap_or_else(PoisonError::into_inner);
+        let sealed = snapshot.value.get_sealed();
+        let value = if key_path.is_empty() {
+            Some(sealed.as_ref())
+        } else {
+            key_path
+                .split(snapshot.keys_delimiter.as_str())
+                .try_fold(sealed.as_ref(), |v, k| match v {
+                    InnerValue::Object(m) => {
+                        m.get(crate::normalize_case(k, snapshot.value.is_case_sensitive()).as_ref())
+                    }
+                    _ => None,
+                })
+        };
+
+        value
+            .map(InnerValue::to_string)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("Key path not found: '{key_path}'")))
+    }
+
+    fn set(&self, key_path: &str, value: &str) -> fdo::Result<()> {
+        let value: Value = serde_json::from_str(value)
+            .map_err(|e| fdo::Error::InvalidArgs(format!("Invalid JSON value: {e}")))?;
+        // Same keys delimiter as for `get`, so both address the same key paths.
+        let delim = self
+            .snapshot
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys_delimiter
+            .clone();
+        self.layer
+            .set_with_delim(key_path, delim, &value)
+            .map_err(|e| fdo::Error::InvalidArgs(e.to_string()))?;
+        (self.on_reload)();
+        Ok(())
+    }
+
+    fn reload(&self) {
+        (self.on_reload)();
+    }
+
+    #[zbus(signal)]
+    async fn changed(emitter: &SignalEmitter<'_>, hash: &str) -> zbus::Result<()>;
+}
+
+/// The service which exposes configuration over D-Bus (see module documentation).
+pub struct DbusService {
+    connection: Connection,
+    snapshot: Arc<Mutex<Snapshot>>,
+}
+
+impl DbusService {
+    /// Connect to session bus, request given well-known name and serve configuration at [`DEFAULT_OBJECT_PATH`].
+    ///
+    /// # Errors
+    ///
+    /// If any D-Bus errors will occur then error will be returned.
+    #[inline]
+    pub fn session<F>(name: &str, layer: PushLayer, on_reload: F) -> Result<Self, Error>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        Self::serve(Builder::session(), name, layer, on_reload)
+    }
+
+    /// Connect to system bus, request given well-known name and serve configuration at [`DEFAULT_OBJECT_PATH`].
+    ///
+    /// # Errors
+    ///
+    /// If any D-Bus errors will occur then error will be returned.
+    #[inline]
+    pub fn system<F>(name: &str, layer: PushLayer, on_reload: F) -> Result<Self, Error>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        Self::serve(Builder::system(), name, layer, on_reload)
+    }
+
+    fn serve<F>(
+        builder: zbus::Result<Builder>,
+        name: &str,
+        layer: PushLayer,
+        on_reload: F,
+    ) -> Result<Self, Error>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let snapshot = Arc::new(Mutex::new(Snapshot {
+            keys_delimiter: DEFAULT_KEYS_SEPARATOR.to_string(),
+            ..Default::default()
+        }));
+        let iface = ConfigInterface {
+            layer,
+            snapshot: snapshot.clone(),
+            on_reload: Box::new(on_reload),
+        };
+        let connection = builder
+            .and_then(|b| b.name(name))
+            .and_then(|b| b.serve_at(DEFAULT_OBJECT_PATH, iface))
+            .and_then(|b| b.build())
+            .map_err(Error::Dbus)?;
+        Ok(Self {
+            connection,
+            snapshot,
+        })
+    }
+
+    /// Publish configuration snapshot to be exposed over D-Bus. If configuration hash was changed then `Changed`
+    /// signal will be emitted.
+    ///
+    /// # Errors
+    ///
+    /// If signal could not be emitted then error will be returned.
+    pub fn publish(&self, config: &Config) -> Result<(), Error> {
+        let hash = config.hash();
+        {
+            let mut snapshot = self.snapshot.lock().unwrap_or_else(PoisonError::into_inner);
+            if snapshot.hash == hash {
+                return Ok(());
+            }
+            snapshot.value = config.get_value().clone();
+            snapshot.hash.clone_from(&hash);
+            snapshot.keys_delimiter = config.keys_delimiter().to_string();
+        }
+
+        let iface = self
+            .connection
+            .object_server()
+            .interface::<_, ConfigInterface>(DEFAULT_OBJECT_PATH)
+            .map_err(Error::Dbus)?;
+        zbus::block_on(ConfigInterface::changed(iface.signal_emitter(), &hash)).map_err(Error::Dbus)
+    }
+}
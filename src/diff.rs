@@ -0,0 +1,215 @@
+//! This module define [`ValueDiff`] structure which represent changes between two [`Value`] structures (see
+//! [`Value::diff`]).
+
+use crate::{sealed::OBFUSCATED, Value, DEFAULT_KEYS_SEPARATOR};
+use serde_json::Value as InnerValue;
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Formatter, Result as FmtResult, Write},
+};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// The kinds of value changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// A value was added.
+    Added,
+    /// A value was removed.
+    Removed,
+    /// A value was changed.
+    Changed,
+}
+
+impl Change {
+    /// Return marker of the change: `+`, `-` or `~`.
+    pub fn marker(self) -> char {
+        match self {
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Changed => '~',
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            Self::Added => GREEN,
+            Self::Removed => RED,
+            Self::Changed => YELLOW,
+        }
+    }
+}
+
+/// The single change of a value for given keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffEntry {
+    keys: Vec<String>,
+    change: Change,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+impl DiffEntry {
+    /// Get keys of the changed value.
+    #[inline]
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Get kind of the change.
+    #[inline]
+    pub fn change(&self) -> Change {
+        self.change
+    }
+
+    /// Get old value as `JSON` string (sealed values are obfuscated) if any.
+    #[inline]
+    pub fn old_value(&self) -> Option<&str> {
+        self.old.as_deref()
+    }
+
+    /// Get new value as `JSON` string (sealed values are obfuscated) if any.
+    #[inline]
+    pub fn new_value(&self) -> Option<&str> {
+        self.new.as_deref()
+    }
+
+    fn line(&self, delim: &str) -> String {
+        let path = self.keys.join(delim);
+        match (&self.old, &self.new) {
+            (Some(o), Some(n)) => format!("{} {path} = {o} -> {n}", self.change.marker()),
+            (Some(v), None) | (None, Some(v)) => format!("{} {path} = {v}", self.change.marker()),
+            (None, None) => format!("{} {path}", self.change.marker()),
+        }
+    }
+}
+
+/// This structure represent changes between two [`Value`] structures. Only leaf values (not dictionaries) are
+/// reported, the sealed values are compared as is, but obfuscated in output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValueDiff {
+    entries: Vec<DiffEntry>,
+}
+
+impl ValueDiff {
+    pub(crate) fn new(old: &Value, new: &Value) -> Self {
+        let mut diff = Diff {
+            old_sealed: old.get_sealed().into_owned(),
+            new_sealed: new.get_sealed().into_owned(),
+            keys: Vec::new(),
+            entries: Vec::new(),
+        };
+        diff.walk(Some(old.as_inner()), Some(new.as_inner()));
+        Self {
+            entries: diff.entries,
+        }
+    }
+
+    #[cfg(feature = "golden")]
+    pub(crate) fn retain<F: FnMut(&DiffEntry) -> bool>(&mut self, f: F) {
+        self.entries.retain(f);
+    }
+
+    /// Return `true` if there are no changes, otherwise return `false`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get all changes sorted by keys.
+    #[inline]
+    pub fn entries(&self) -> &[DiffEntry] {
+        &self.entries
+    }
+
+    /// Render changes as plain text, one line per change prefixed with the change marker (see [`Change::marker`]).
+    /// The keys are joined with given delimiter.
+    pub fn render<D: AsRef<str>>(&self, delim: D) -> String {
+        self.entries.iter().fold(String::new(), |mut r, e| {
+            let _ = writeln!(r, "{}", e.line(delim.as_ref()));
+            r
+        })
+    }
+
+    /// Render changes same way as [`ValueDiff::render`], but colored with `ANSI` escape codes: added values are
+    /// green, removed values are red and changed values are yellow.
+    pub fn render_colored<D: AsRef<str>>(&self, delim: D) -> String {
+        self.entries.iter().fold(String::new(), |mut r, e| {
+            let _ = writeln!(r, "{}{}{RESET}", e.change.color(), e.line(delim.as_ref()));
+            r
+        })
+    }
+}
+
+impl Display for ValueDiff {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&self.render(DEFAULT_KEYS_SEPARATOR))
+    }
+}
+
+struct Diff {
+    old_sealed: InnerValue,
+    new_sealed: InnerValue,
+    keys: Vec<String>,
+    entries: Vec<DiffEntry>,
+}
+
+impl Diff {
+    fn walk(&mut self, old: Option<&InnerValue>, new: Option<&InnerValue>) {
+        let (old_map, new_map) = match (old, new) {
+            (Some(InnerValue::Object(o)), Some(InnerValue::Object(n))) => (Some(o), Some(n)),
+            (Some(InnerValue::Object(o)), None) if !o.is_empty() => (Some(o), None),
+            (None, Some(InnerValue::Object(n))) if !n.is_empty() => (None, Some(n)),
+            _ => {
+                self.push(old, new);
+                return;
+            }
+        };
+
+        let keys: BTreeSet<_> = old_map
+            .into_iter()
+            .chain(new_map)
+            .flat_map(|m| m.keys())
+            .collect();
+        for key in keys {
+            self.keys.push(key.clone());
+            self.walk(
+                old_map.and_then(|m| m.get(key)),
+                new_map.and_then(|m| m.get(key)),
+            );
+            self.keys.pop();
+        }
+    }
+
+    fn push(&mut self, old: Option<&InnerValue>, new: Option<&InnerValue>) {
+        let change = match (old, new) {
+            (Some(o), Some(n)) if o == n => return,
+            (Some(_), Some(_)) => Change::Changed,
+            (Some(_), None) => Change::Removed,
+            (None, Some(_)) => Change::Added,
+            (None, None) => return,
+        };
+
+        let old = old.map(|_| display(&self.old_sealed, &self.keys));
+        let new = new.map(|_| display(&self.new_sealed, &self.keys));
+        self.entries.push(DiffEntry {
+            keys: self.keys.clone(),
+            change,
+            old,
+            new,
+        });
+    }
+}
+
+fn display(sealed: &InnerValue, keys: &[String]) -> String {
+    keys.iter()
+        .try_fold(sealed, |v, k| match v {
+            InnerValue::Object(m) => m.get(k),
+            _ => None,
+        })
+        .map_or_else(|| format!("\"{OBFUSCATED}\""), InnerValue::to_string)
+}
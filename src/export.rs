@@ -0,0 +1,131 @@
+//! This module define typed records of flattened configuration data (see [`Config::records`]) which could be
+//! exported in [JSON Lines](https://jsonlines.org/) format (see [`Config::write_records`]) for ingestion into
+//! config-inventory pipelines across a fleet.
+//!
+//! Each line is a `JSON` object with the following fields:
+//!
+//! * `key` - the key path (with keys delimiter);
+//! * `type` - the type of the value: `string`, `integer`, `number`, `boolean`, `null`, `array` or `object`;
+//! * `value` - the value itself, sealed values are obfuscated;
+//! * `origin` - the name of the parser which supplied the value if provenance tracking is enabled (see
+//!   [`ConfigBuilder::provenance`]), otherwise `null`;
+//! * `sealed` - `true` if the value is sealed, otherwise `false`.
+//!
+//! # Example
+//!
+//! ```
+//! conf.write_records(std::io::stdout())?;
+//! ```
+//!
+//! [`Config::records`]: crate::Config::records
+//! [`Config::write_records`]: crate::Config::write_records
+//! [`ConfigBuilder::provenance`]: crate::ConfigBuilder::provenance
+
+use crate::{json, Value};
+use serde_json::Value as InnerValue;
+
+/// The typed record of a single configuration value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    key: String,
+    value: InnerValue,
+    origin: Option<String>,
+    sealed: bool,
+}
+
+impl Record {
+    /// Get key path (with keys delimiter) of the value.
+    #[inline]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get type name of the value: `string`, `integer`, `number`, `boolean`, `null`, `array` or `object`.
+    pub fn type_name(&self) -> &'static str {
+        match self.value {
+            InnerValue::String(_) => "string",
+            InnerValue::Number(ref n) if n.is_f64() => "number",
+            InnerValue::Number(_) => "integer",
+            InnerValue::Bool(_) => "boolean",
+            InnerValue::Null => "null",
+            InnerValue::Array(_) => "array",
+            InnerValue::Object(_) => "object",
+        }
+    }
+
+    /// Get the value, sealed value is obfuscated.
+    #[inline]
+    pub fn value(&self) -> &InnerValue {
+        &self.value
+    }
+
+    /// Get name of the parser which supplied the value if provenance tracking is enabled.
+    #[inline]
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Return `true` if the value is sealed, otherwise return `false`.
+    #[inline]
+    pub fn is_sealed(&self) -> bool {
+        self.sealed
+    }
+
+    /// Convert record to `JSON` object.
+    pub fn to_json(&self) -> InnerValue {
+        json!({
+            "key": self.key,
+            "type": self.type_name(),
+            "value": self.value,
+            "origin": self.origin,
+            "sealed": self.sealed,
+        })
+    }
+}
+
+pub(crate) fn records<F>(value: &Value, delim: &str, origin: F) -> Vec<Record>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn collect<F>(
+        value: &Value,
+        node: &InnerValue,
+        keys: &mut Vec<String>,
+        delim: &str,
+        origin: &F,
+        result: &mut Vec<Record>,
+    ) where
+        F: Fn(&str) -> Option<String>,
+    {
+        match node {
+            InnerValue::Object(m) if !m.is_empty() => {
+                for (k, v) in m {
+                    keys.push(k.clone());
+                    collect(value, v, keys, delim, origin, result);
+                    keys.pop();
+                }
+            }
+            _ if keys.is_empty() => (),
+            _ => {
+                let key = keys.join(delim);
+                result.push(Record {
+                    origin: origin(&key),
+                    key,
+                    value: node.clone(),
+                    sealed: value.is_secret(keys),
+                })
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    collect(
+        value,
+        &value.get_sealed(),
+        &mut Vec::new(),
+        delim,
+        &origin,
+        &mut result,
+    );
+    result
+}
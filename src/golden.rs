@@ -0,0 +1,76 @@
+//! This module define options of comparison of configuration data against golden (checked-in reference) file, see
+//! [`Config::diff_golden`] and [`Config::assert_matches_golden`]. It could be used in integration tests and deployment
+//! verification jobs to catch unexpected changes of effective configuration.
+//!
+//! The golden file is `JSON` document, the volatile values (e.g. generated identifiers or timestamps) could be
+//! excluded from comparison by ignored key paths (see [`GoldenOptions::ignore`]).
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::golden::GoldenOptions;
+//!
+//! let options = GoldenOptions::default().ignore("build:timestamp").ignore("nodes:*:id");
+//! config.assert_matches_golden("tests/golden/service.json", &options);
+//! ```
+
+use crate::{diff::ValueDiff, Error, Result, Value};
+use serde_json::Value as InnerValue;
+use std::{fs::File, io::BufReader, path::Path};
+
+/// Options of comparison against golden file.
+#[derive(Clone, Debug, Default)]
+pub struct GoldenOptions {
+    ignored: Vec<String>,
+}
+
+impl GoldenOptions {
+    /// Ignore differences of values for given key path (with keys delimiter of compared configuration), where `*`
+    /// matches any single key. All nested values of the matched one are ignored too.
+    #[inline]
+    pub fn ignore<P: Into<String>>(mut self, path: P) -> Self {
+        self.ignored.push(path.into());
+        self
+    }
+
+    /// Get all ignored key paths.
+    #[inline]
+    pub fn ignored(&self) -> &[String] {
+        &self.ignored
+    }
+
+    pub(crate) fn diff(&self, value: &Value, path: &Path, delim: &str) -> Result<ValueDiff> {
+        let err = |e| {
+            Error::IO(
+                e,
+                format!("Failed to open golden file: '{}'", path.display()).into(),
+            )
+        };
+        let reader = BufReader::new(File::open(path).map_err(err)?);
+        let golden: InnerValue = serde_json::from_reader(reader).map_err(|e| {
+            Error::SerdeError(
+                e,
+                format!("Failed to read golden file: '{}'", path.display()).into(),
+            )
+        })?;
+        let case_on = value.is_case_sensitive();
+        let golden = Value::try_from_with_case(golden, case_on)?;
+
+        let patterns: Vec<Vec<_>> = self
+            .ignored
+            .iter()
+            .map(|p| {
+                p.split(delim)
+                    .map(|k| crate::normalize_case(k, case_on))
+                    .collect()
+            })
+            .collect();
+        let mut diff = golden.diff(value);
+        diff.retain(|e| {
+            !patterns.iter().any(|p| {
+                p.len() <= e.keys().len() && p.iter().zip(e.keys()).all(|(p, k)| p == "*" || p == k)
+            })
+        });
+        Ok(diff)
+    }
+}
@@ -0,0 +1,214 @@
+//! This module standardize the handoff of resolved configuration from a parent process to spawned workers, so workers
+//! do not have to repeat loading of all layers (and could not get inconsistent data in between). The parent injects
+//! serialized [`Config`] (see [`Config::meta`]) into [`Command`] by [`Handoff::spawn`] and the worker gets it back by
+//! [`Handoff::receive`] with the same [`Handoff`] mode:
+//!
+//! * [`Handoff::Env`] -- `JSON` document in given environment variable.
+//! * [`Handoff::File`] -- `JSON` document in temporary file (readable by owner only on Unix), the path to the file is
+//!   passed in given environment variable. The file is removed when [`Spawned`] is dropped.
+//! * [`Handoff::Stdin`] -- `JSON` document written to inherited standard input of the worker.
+//!
+//! The sealed values are passed in plain (the worker needs them, see [`Config::unsealed`]) and stay sealed in received
+//! [`Config`].
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::handoff::Handoff;
+//! use std::process::Command;
+//!
+//! // Parent
+//! let handoff = Handoff::File("APP_CONFIG_FILE".into());
+//! let spawned = handoff.spawn(&config, Command::new("app-worker").arg("--worker"))?;
+//! let status = spawned.wait()?;
+//!
+//! // Worker
+//! let config = Handoff::File("APP_CONFIG_FILE".into()).receive()?;
+//! ```
+
+use crate::Config;
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Error as IoError, Read, Write},
+    path::PathBuf,
+    process::{Child, ChildStdin, Command, ExitStatus, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread::{self, JoinHandle},
+};
+
+/// The default name of environment variable used by [`Handoff::default`].
+pub const DEFAULT_ENV_VAR: &str = "IRX_CONFIG";
+
+static FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// All errors for configuration handoff.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to serialize configuration")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Failed to write configuration file: '{1}'")]
+    Write(#[source] IoError, PathBuf),
+    #[error("Failed to spawn process")]
+    Spawn(#[source] IoError),
+    #[error("Failed to write configuration to standard input of process")]
+    Stdin(#[source] IoError),
+    #[error("Failed to wait for process")]
+    Wait(#[source] IoError),
+    #[error("Missing or invalid environment variable: '{0}'")]
+    MissingVar(String),
+    #[error("Failed to read configuration from {1}")]
+    Read(#[source] IoError, String),
+    #[error("Failed to deserialize configuration")]
+    Deserialize(#[source] serde_json::Error),
+}
+
+/// The mode of configuration handoff from parent to spawned process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Handoff {
+    /// Pass configuration in environment variable with given name.
+    Env(String),
+    /// Pass configuration in temporary file, the path is passed in environment variable with given name.
+    File(String),
+    /// Pass configuration via standard input of spawned process.
+    Stdin,
+}
+
+impl Default for Handoff {
+    #[inline]
+    fn default() -> Self {
+        Self::Env(DEFAULT_ENV_VAR.to_string())
+    }
+}
+
+/// The process spawned by [`Handoff::spawn`]. Keep it alive until the process has read its configuration, the
+/// temporary file (if any) is removed on drop.
+#[derive(Debug)]
+pub struct Spawned {
+    child: Child,
+    file: Option<PathBuf>,
+    writer: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl Spawned {
+    /// Get spawned child process.
+    #[inline]
+    pub fn child(&mut self) -> &mut Child {
+        &mut self.child
+    }
+
+    /// Wait for spawned process to exit and return its exit status.
+    ///
+    /// # Errors
+    ///
+    /// If configuration could not be written to standard input of the process or waiting failed then error will be
+    /// returned.
+    pub fn wait(mut self) -> Result<ExitStatus, Error> {
+        let status = self.child.wait().map_err(Error::Wait)?;
+        if let Some(writer) = self.writer.take() {
+            writer
+                .join()
+                .map_err(|_| Error::Stdin(io::ErrorKind::Other.into()))?
+                .map_err(Error::Stdin)?;
+        }
+        Ok(status)
+    }
+}
+
+impl Drop for Spawned {
+    fn drop(&mut self) {
+        if let Some(path) = self.file.take() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl Handoff {
+    /// Inject serialized configuration into given command according to the handoff mode and spawn it.
+    ///
+    /// # Errors
+    ///
+    /// If configuration could not be serialized or written, or process could not be spawned then error will be
+    /// returned.
+    pub fn spawn(&self, config: &Config, command: &mut Command) -> Result<Spawned, Error> {
+        let data = serde_json::to_string(&config.unsealed()).map_err(Error::Serialize)?;
+        let mut file = None;
+        match self {
+            Self::Env(name) => {
+                command.env(name, &data);
+            }
+            Self::File(name) => {
+                let path = write_file(data.as_bytes())?;
+                command.env(name, &path);
+                file = Some(path);
+            }
+            Self::Stdin => {
+                command.stdin(Stdio::piped());
+            }
+        }
+
+        let mut spawned = Spawned {
+            child: command.spawn().map_err(Error::Spawn)?,
+            file,
+            writer: None,
+        };
+        if let Some(stdin) = spawned.child.stdin.take().filter(|_| *self == Self::Stdin) {
+            spawned.writer = Some(thread::spawn(move || write_stdin(stdin, data.as_bytes())));
+        }
+        Ok(spawned)
+    }
+
+    /// Receive configuration injected by [`Handoff::spawn`] of parent process with the same handoff mode.
+    ///
+    /// # Errors
+    ///
+    /// If configuration is missing or could not be read or deserialized then error will be returned.
+    pub fn receive(&self) -> Result<Config, Error> {
+        let var = |name: &str| env::var(name).map_err(|_| Error::MissingVar(name.to_string()));
+        let data = match self {
+            Self::Env(name) => var(name)?,
+            Self::File(name) => {
+                let path = var(name)?;
+                fs::read_to_string(&path).map_err(|e| Error::Read(e, format!("file: '{path}'")))?
+            }
+            Self::Stdin => {
+                let mut data = String::new();
+                io::stdin()
+                    .read_to_string(&mut data)
+                    .map_err(|e| Error::Read(e, "standard input".to_string()))?;
+                data
+            }
+        };
+        serde_json::from_str(&data).map_err(Error::Deserialize)
+    }
+}
+
+fn write_file(data: &[u8]) -> Result<PathBuf, Error> {
+    let path = env::temp_dir().join(format!(
+        "irx-config-handoff-{}-{}.json",
+        std::process::id(),
+        FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(&path)
+        .map_err(|e| Error::Write(e, path.clone()))?;
+    match file.write_all(data) {
+        Ok(()) => Ok(path),
+        Err(e) => {
+            let _ = fs::remove_file(&path);
+            Err(Error::Write(e, path))
+        }
+    }
+}
+
+fn write_stdin(mut stdin: ChildStdin, data: &[u8]) -> io::Result<()> {
+    match stdin.write_all(data) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,114 @@
+//! This module define [`Lease`] structure which describe time limited data (e.g. dynamic credentials or tokens
+//! fetched from Vault) returned by a parser (see [`Parse::leases`]).
+//!
+//! [`Config`] tracks expiration of all leases from the last (re)load. The leases which are about to expire are
+//! renewed via [`Parse::renew`] (see [`Config::renew_leases`]). If a lease is not renewable or the parser could not
+//! renew it then only the layer of that parser is reloaded, so rotated secrets are updated in place. The
+//! `watch::ConfigWatcher` does it automatically, without application involvement.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use irx_config::{AnyResult, Case, Lease, Parse, Value};
+//!
+//! struct VaultParser {
+//!     lease: Option<Lease>,
+//! }
+//!
+//! impl Case for VaultParser {}
+//!
+//! impl Parse for VaultParser {
+//!     fn parse(&mut self, _: &Value) -> AnyResult<Value> {
+//!         let (id, ttl, data) = fetch_credentials()?;
+//!         self.lease = Some(
+//!             Lease::new(id, Duration::from_secs(ttl))
+//!                 .renewable(true)
+//!                 .key_path("db:password"),
+//!         );
+//!         Ok(data)
+//!     }
+//!
+//!     fn leases(&self) -> Vec<Lease> {
+//!         self.lease.iter().cloned().collect()
+//!     }
+//!
+//!     fn renew(&mut self, lease: &Lease) -> AnyResult<Option<Lease>> {
+//!         let ttl = renew_lease(lease.id())?;
+//!         Ok(Some(lease.clone().duration(Duration::from_secs(ttl))))
+//!     }
+//! }
+//! ```
+//!
+//! [`Config`]: crate::Config
+//! [`Config::renew_leases`]: crate::Config::renew_leases
+//! [`Parse::leases`]: crate::Parse::leases
+//! [`Parse::renew`]: crate::Parse::renew
+
+use std::time::Duration;
+
+/// The lease of time limited data returned by a parser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lease {
+    id: String,
+    duration: Duration,
+    renewable: bool,
+    key_paths: Vec<String>,
+}
+
+impl Lease {
+    /// Create non-renewable [`Lease`] with given identifier and duration.
+    pub fn new<I: Into<String>>(id: I, duration: Duration) -> Self {
+        Self {
+            id: id.into(),
+            duration,
+            renewable: false,
+            key_paths: Vec::new(),
+        }
+    }
+
+    /// Set duration of the lease starting from the moment it was returned by the parser.
+    #[inline]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set if the lease could be renewed via [`Parse::renew`](crate::Parse::renew).
+    #[inline]
+    pub fn renewable(mut self, renewable: bool) -> Self {
+        self.renewable = renewable;
+        self
+    }
+
+    /// Add key path (with keys delimiter) of the data covered by the lease.
+    #[inline]
+    pub fn key_path<P: Into<String>>(mut self, path: P) -> Self {
+        self.key_paths.push(path.into());
+        self
+    }
+
+    /// Get identifier of the lease.
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get duration of the lease.
+    #[inline]
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Return `true` if the lease could be renewed, otherwise return `false`.
+    #[inline]
+    pub fn is_renewable(&self) -> bool {
+        self.renewable
+    }
+
+    /// Get key paths of the data covered by the lease.
+    #[inline]
+    pub fn key_paths(&self) -> &[String] {
+        &self.key_paths
+    }
+}
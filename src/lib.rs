@@ -1,19 +1,101 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::todo,
+        clippy::unimplemented,
+        clippy::unreachable
+    )
+)]
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
 pub mod config;
+#[cfg(any(
+    feature = "json",
+    feature = "json5-parser",
+    feature = "yaml",
+    feature = "toml-parser"
+))]
+pub mod convert;
+#[cfg(feature = "db")]
+pub mod db;
+#[cfg(all(unix, feature = "dbus"))]
+pub mod dbus;
+#[cfg(feature = "diff")]
+pub mod diff;
+#[cfg(feature = "export")]
+pub mod export;
+#[cfg(feature = "golden")]
+pub mod golden;
+#[cfg(feature = "handoff")]
+pub mod handoff;
+pub mod lease;
+#[cfg(feature = "lint")]
+pub mod lint;
+#[cfg(feature = "locale")]
+pub mod locale;
 #[cfg(feature = "parsers")]
 pub mod parsers;
+#[cfg(feature = "plugin")]
+pub mod plugin;
+#[cfg(any(
+    feature = "preset-cli",
+    feature = "preset-service",
+    feature = "preset-k8s"
+))]
+pub mod presets;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+#[cfg(all(unix, feature = "push"))]
+pub mod push;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(feature = "rotation")]
+pub mod rotation;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod sealed;
+#[cfg(all(unix, feature = "shm"))]
+pub mod shm;
+#[cfg(feature = "simulate")]
+pub mod simulate;
+#[cfg(feature = "sources")]
+pub mod sources;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "tls")]
+pub mod tls;
 pub mod value;
+#[cfg(feature = "watch")]
+pub mod watch;
 
+#[cfg(any(
+    feature = "json",
+    feature = "json5-parser",
+    feature = "yaml",
+    feature = "toml-parser"
+))]
+pub use crate::convert::convert;
+#[cfg(feature = "simulate")]
+pub use crate::simulate::simulate;
 use crate::value::SerdeError;
 pub use crate::{
-    config::{Config, ConfigBuilder},
+    config::{
+        Config, ConfigBuilder, ConfigMeta, ConfigRead, ConfigReader, HistoryEntry, Layer,
+        LazySection, RestrictedConfig,
+    },
+    lease::Lease,
+    sealed::Sealed,
     value::{json, Value},
 };
 use std::{
-    borrow::Cow, error::Error as StdError, fmt::Debug, io::Error as IoError,
+    borrow::Cow, error::Error as StdError, fmt::Debug, io::Error as IoError, path::PathBuf,
     result::Result as StdResult,
 };
 
@@ -27,10 +109,18 @@ pub type AnyResult<T> = StdResult<T, AnyError>;
 
 type CowString<'a> = Cow<'a, str>;
 type AnyParser = Box<dyn Parse>;
+#[cfg(feature = "async")]
+type AnyAsyncParser = Box<dyn asynchronous::AsyncParse>;
 
 /// Default key level separator.
 pub const DEFAULT_KEYS_SEPARATOR: &str = ":";
 
+/// The key of dictionary entry to be merged into every sibling entry (see [`ConfigBuilder::default_templates`]).
+pub const DEFAULT_TEMPLATE_KEY: &str = "__default__";
+
+/// The key of dictionary with profile-specific overlays (see [`ConfigBuilder::profile`]).
+pub const DEFAULT_PROFILES_KEY: &str = "profiles";
+
 /// Error generated during any crate operations.
 #[non_exhaustive]
 #[derive(thiserror::Error, Debug)]
@@ -45,6 +135,63 @@ pub enum Error {
     ParseValue(#[source] AnyError, usize),
     #[error("{1}")]
     IO(#[source] IoError, Cow<'static, str>),
+    #[error("Key path '{0}' is not found{}", .1.as_ref().map(|s| format!(", did you mean '{s}'?")).unwrap_or_default())]
+    MissingKey(String, Option<String>),
+    #[error("Snapshot #{0} is not found in history")]
+    MissingSnapshot(usize),
+    #[error("Checkpoint '{0}' is not found")]
+    MissingCheckpoint(String),
+    #[error("Hash '{0}' does not match current configuration")]
+    HashMismatch(String),
+    #[error("Configuration is pinned to hash '{0}'")]
+    Pinned(String),
+    #[error("Key path '{0}' is pinned")]
+    PinnedKey(String),
+    #[error("Dependency '{1}' of parser #{0} could not be resolved")]
+    UnresolvedDependency(usize, String),
+    #[error("Key path '{0}' is defined both with and without sealed suffix")]
+    SealedKeyCollision(String),
+    #[error("Reference '{1}' in key path '{0}' is not found")]
+    MissingReference(String, String),
+    #[error("Cyclic reference in key path '{0}': {1}")]
+    CyclicReference(String, String),
+    #[error("Environment variable '{1}' referenced in key path '{0}' is not set")]
+    MissingEnvVar(String, String),
+    #[error("There is no staged configuration to commit")]
+    NothingStaged,
+    #[error("Invalid concurrency value {1} for key path '{0}'")]
+    InvalidConcurrency(String, String),
+    #[error("Failed to renew lease '{1}' for parser #{2}")]
+    RenewLease(#[source] AnyError, String, usize),
+    #[cfg(feature = "async")]
+    #[error("Parser #{0} is asynchronous, it could be (re)loaded only asynchronously")]
+    AsyncParser(usize),
+}
+
+/// Merge mode for arrays during (re)load.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ArrayMerge {
+    /// Array from higher priority parser replaces array from lower priority one.
+    #[default]
+    Replace,
+    /// Items of array from lower priority parser are appended to array from higher priority one.
+    Append,
+    /// Same as [`ArrayMerge::Append`], but only items which are not present yet are appended.
+    UniqueUnion,
+}
+
+/// Merge strategy for specific key path during (re)load (see [`ConfigBuilder::merge_strategy_for`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Dictionaries are deeply merged, arrays are merged according to [`ArrayMerge`] mode.
+    #[default]
+    Merge,
+    /// Value from higher priority parser replaces value from lower priority one as whole.
+    Replace,
+    /// Arrays are merged same way as [`ArrayMerge::Append`].
+    Append,
+    /// Arrays are merged same way as [`ArrayMerge::UniqueUnion`].
+    UniqueUnion,
 }
 
 /// Case mode to merging keys during (re)load.
@@ -79,6 +226,60 @@ pub trait Parse: Case {
     ///
     /// If any errors will occur during parsing then error will be returned.
     fn parse(&mut self, value: &Value) -> AnyResult<Value>;
+
+    /// Return `true` if parser fetches data over network, otherwise return `false`. Such parsers will not be called
+    /// in offline mode (see [`ConfigBuilder::offline`]). The `value` parameter has the same meaning as for
+    /// [`Parse::parse`] method, so the decision could depend on options from previous parsing results.
+    #[inline]
+    fn is_remote(&self, _value: &Value) -> bool {
+        false
+    }
+
+    /// Return paths of the files which were used during last parse, changes of such files should trigger (re)load
+    /// (see `watch::ConfigWatcher`).
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Return key paths (with parser's keys delimiter) of options which the parser takes from merged results of other
+    /// parsers (e.g. path option of file parser). Such parser will be called after all parsers without dependencies
+    /// and after other dependent parsers which supply the options, so the options are available regardless of the
+    /// parsers order. The merge priority is not changed.
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Return leases of the time limited data (e.g. dynamic secrets) which were returned during last parse (see
+    /// [`lease`] module).
+    #[inline]
+    fn leases(&self) -> Vec<Lease> {
+        Vec::new()
+    }
+
+    /// Return key paths (represented as keys) of the secret values which were returned during last parse. Such values
+    /// will be sealed regardless of sealed suffix (see [`ConfigBuilder::sealed_suffix`]).
+    #[inline]
+    fn sealed_keys(&self) -> Vec<Vec<String>> {
+        Vec::new()
+    }
+
+    /// Renew given lease which was returned by [`Parse::leases`]. If successful then the renewed lease will be
+    /// returned. If `Ok(None)` will be returned then the data will be parsed again (rotated).
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur during renewal then error will be returned.
+    #[inline]
+    fn renew(&mut self, _lease: &Lease) -> AnyResult<Option<Lease>> {
+        Ok(None)
+    }
+
+    /// Select profile (see [`ConfigBuilder::profile`]), so the parser could load profile-specific data (e.g. file
+    /// parsers load `config.<profile>.yaml` siblings). It is called once before the first load.
+    #[inline]
+    fn select_profile(&mut self, _profile: &str) {}
 }
 
 impl Case for AnyParser {
@@ -93,6 +294,41 @@ impl Parse for AnyParser {
     fn parse(&mut self, value: &Value) -> AnyResult<Value> {
         self.as_mut().parse(value)
     }
+
+    #[inline]
+    fn is_remote(&self, value: &Value) -> bool {
+        self.as_ref().is_remote(value)
+    }
+
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.as_ref().watch_paths()
+    }
+
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        self.as_ref().depends_on()
+    }
+
+    #[inline]
+    fn leases(&self) -> Vec<Lease> {
+        self.as_ref().leases()
+    }
+
+    #[inline]
+    fn sealed_keys(&self) -> Vec<Vec<String>> {
+        self.as_ref().sealed_keys()
+    }
+
+    #[inline]
+    fn renew(&mut self, lease: &Lease) -> AnyResult<Option<Lease>> {
+        self.as_mut().renew(lease)
+    }
+
+    #[inline]
+    fn select_profile(&mut self, profile: &str) {
+        self.as_mut().select_profile(profile)
+    }
 }
 
 #[inline]
@@ -101,7 +337,7 @@ fn unicase(data: &str) -> String {
 }
 
 #[inline]
-fn normalize_case(data: &str, case_on: bool) -> CowString {
+fn normalize_case(data: &str, case_on: bool) -> CowString<'_> {
     if case_on {
         CowString::Borrowed(data)
     } else {
@@ -0,0 +1,318 @@
+//! This module define configuration linting rules engine. The [`Linter`] runs set of rules (see [`Rule`]) over loaded
+//! [`Config`] and returns findings (see [`Finding`]). It could be used to validate configuration repositories in CI.
+//!
+//! The built-in rules are:
+//!
+//! * [`UnusedSealedSuffix`] - sealed suffix is set, but no keys were sealed;
+//! * [`MixedCaseKeys`] - keys on the same level which are differ only by case;
+//! * [`UnsealedSecrets`] - keys which look like secrets (e.g. `password`), but are not sealed;
+//! * [`AbsolutePaths`] - values which look like absolute file system paths.
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::lint::{Finding, Linter, Rule, Severity};
+//! use irx_config::Config;
+//!
+//! struct RequireName;
+//!
+//! impl Rule for RequireName {
+//!     fn name(&self) -> &str {
+//!         "require-name"
+//!     }
+//!
+//!     fn check(&self, config: &Config) -> Vec<Finding> {
+//!         match config.get_by_key_path::<String, _>("name") {
+//!             Ok(Some(_)) => vec![],
+//!             _ => vec![Finding::new(self.name(), Severity::Error, Some("name"), "Name is required")],
+//!         }
+//!     }
+//! }
+//!
+//! let findings = Linter::default().rule(RequireName).lint(&config);
+//! ```
+
+use crate::Config;
+use serde_json::Value as InnerValue;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// The words which make key to look like a secret.
+pub const SECRET_WORDS: [&str; 6] = [
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "private_key",
+];
+
+/// Severity of the finding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Informational finding.
+    Info,
+    /// Potential problem.
+    Warning,
+    /// Definite problem.
+    Error,
+}
+
+/// The single finding reported by a rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    rule: String,
+    severity: Severity,
+    key_path: Option<String>,
+    message: String,
+}
+
+impl Finding {
+    /// Create [`Finding`] for given rule name, severity, optional key path and message.
+    pub fn new<R, P, M>(rule: R, severity: Severity, key_path: Option<P>, message: M) -> Self
+    where
+        R: Into<String>,
+        P: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            rule: rule.into(),
+            severity,
+            key_path: key_path.map(Into::into),
+            message: message.into(),
+        }
+    }
+
+    /// Get name of the rule which reported the finding.
+    #[inline]
+    pub fn rule(&self) -> &str {
+        &self.rule
+    }
+
+    /// Get severity of the finding.
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Get key path related to the finding if any.
+    #[inline]
+    pub fn key_path(&self) -> Option<&str> {
+        self.key_path.as_deref()
+    }
+
+    /// Get message of the finding.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:?} [{}]", self.severity, self.rule)?;
+        if let Some(ref p) = self.key_path {
+            write!(f, " '{p}'")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// The trait to be implemented by linting rules.
+pub trait Rule {
+    /// Return name of the rule.
+    fn name(&self) -> &str;
+
+    /// Check given [`Config`] and return all findings.
+    fn check(&self, config: &Config) -> Vec<Finding>;
+}
+
+/// The linter which runs all registered rules. The default linter contains all built-in rules.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Linter {
+    /// Create linter without any rules.
+    #[inline]
+    pub fn empty() -> Self {
+        Self {
+            rules: Default::default(),
+        }
+    }
+
+    /// Register (custom) rule.
+    #[inline]
+    pub fn rule<R>(mut self, rule: R) -> Self
+    where
+        R: Rule + 'static,
+    {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Run all registered rules over given [`Config`] and return all findings in order of rules registration.
+    pub fn lint(&self, config: &Config) -> Vec<Finding> {
+        self.rules.iter().flat_map(|r| r.check(config)).collect()
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::empty()
+            .rule(UnusedSealedSuffix)
+            .rule(MixedCaseKeys)
+            .rule(UnsealedSecrets)
+            .rule(AbsolutePaths)
+    }
+}
+
+/// The rule which reports sealed suffix which was set, but no keys were sealed with it.
+pub struct UnusedSealedSuffix;
+
+impl Rule for UnusedSealedSuffix {
+    fn name(&self) -> &str {
+        "unused-sealed-suffix"
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        let suffix = config.sealed_suffix();
+        if suffix.is_empty() || config.get_value().has_secrets() {
+            return vec![];
+        }
+
+        vec![Finding::new(
+            self.name(),
+            Severity::Info,
+            None::<String>,
+            format!("Sealed suffix '{suffix}' is not used by any key"),
+        )]
+    }
+}
+
+/// The rule which reports keys on the same level which are differ only by case.
+pub struct MixedCaseKeys;
+
+impl Rule for MixedCaseKeys {
+    fn name(&self) -> &str {
+        "mixed-case-keys"
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        let mut result = Vec::new();
+        walk(config, &mut |keys, value| {
+            let InnerValue::Object(m) = value else {
+                return;
+            };
+
+            let mut seen = BTreeMap::new();
+            for k in m.keys() {
+                if let Some(prev) = seen.insert(k.to_lowercase(), k) {
+                    result.push(Finding::new(
+                        self.name(),
+                        Severity::Warning,
+                        Some(path(config, keys, k)),
+                        format!("Key differs only by case from '{prev}'"),
+                    ));
+                }
+            }
+        });
+        result
+    }
+}
+
+/// The rule which reports keys which look like secrets (see [`SECRET_WORDS`]), but are not sealed.
+pub struct UnsealedSecrets;
+
+impl Rule for UnsealedSecrets {
+    fn name(&self) -> &str {
+        "unsealed-secrets"
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        let mut result = Vec::new();
+        walk(config, &mut |keys, value| {
+            let Some(key) = keys.last() else {
+                return;
+            };
+
+            let key = key.to_lowercase();
+            if !value.is_object()
+                && SECRET_WORDS.iter().any(|w| key.contains(w))
+                && !config.get_value().is_secret(keys)
+            {
+                result.push(Finding::new(
+                    self.name(),
+                    Severity::Warning,
+                    Some(keys.join(config.keys_delimiter())),
+                    "Value looks like a secret, but is not sealed",
+                ));
+            }
+        });
+        result
+    }
+}
+
+/// The rule which reports values which look like absolute file system paths, such configuration is not portable.
+pub struct AbsolutePaths;
+
+impl Rule for AbsolutePaths {
+    fn name(&self) -> &str {
+        "absolute-paths"
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        let mut result = Vec::new();
+        walk(config, &mut |keys, value| {
+            let InnerValue::String(s) = value else {
+                return;
+            };
+
+            let bytes = s.as_bytes();
+            let is_windows = bytes.len() > 2
+                && bytes[0].is_ascii_alphabetic()
+                && b':' == bytes[1]
+                && matches!(bytes[2], b'\\' | b'/');
+            if (s.starts_with('/') && !s.starts_with("//")) || is_windows {
+                result.push(Finding::new(
+                    self.name(),
+                    Severity::Info,
+                    Some(keys.join(config.keys_delimiter())),
+                    format!("Absolute path '{s}' is not portable"),
+                ));
+            }
+        });
+        result
+    }
+}
+
+fn path(config: &Config, keys: &[String], key: &str) -> String {
+    keys.iter()
+        .map(String::as_str)
+        .chain([key])
+        .collect::<Vec<_>>()
+        .join(config.keys_delimiter())
+}
+
+fn walk<F>(config: &Config, f: &mut F)
+where
+    F: FnMut(&[String], &InnerValue),
+{
+    fn inner<F>(value: &InnerValue, keys: &mut Vec<String>, f: &mut F)
+    where
+        F: FnMut(&[String], &InnerValue),
+    {
+        f(keys, value);
+        if let InnerValue::Object(m) = value {
+            for (k, v) in m {
+                keys.push(k.clone());
+                inner(v, keys, f);
+                keys.pop();
+            }
+        }
+    }
+
+    inner(config.get_value().as_inner(), &mut Vec::new(), f);
+}
@@ -0,0 +1,69 @@
+//! This module define [`Locale`] with fallback chain used to resolve localized values of configuration data (see
+//! [`Config::get_localized`] and [`Config::localized`]), so applications could keep localized operational strings in
+//! their configuration files, e.g. the following `YAML` file:
+//!
+//! ```yaml
+//! messages:
+//!   en:
+//!     greeting: Hello
+//!     farewell: Bye
+//!   fr:
+//!     greeting: Bonjour
+//!     farewell: Au revoir
+//!   fr-CA:
+//!     greeting: Allô
+//! ```
+//!
+//! For `fr-CA` locale with `en` fallback, the values are looked up under `fr-CA`, `fr` and `en` keys in that order.
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::locale::Locale;
+//!
+//! let locale = Locale::new("fr-CA").fallback("en");
+//! let farewell: Option<String> = config.get_localized("messages", "farewell", &locale)?;
+//! let bundle = config.localized("messages", &locale)?;
+//! ```
+//!
+//! [`Config::get_localized`]: crate::Config::get_localized
+//! [`Config::localized`]: crate::Config::localized
+
+/// The locale tag (e.g. `fr-CA` or `fr_CA`) with optional fallback tags.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Locale {
+    tags: Vec<String>,
+}
+
+impl Locale {
+    /// Create locale with given tag.
+    #[inline]
+    pub fn new<S: Into<String>>(tag: S) -> Self {
+        Self {
+            tags: vec![tag.into()],
+        }
+    }
+
+    /// Append fallback tag used if value is not found for the locale tag or its more generic tags.
+    #[inline]
+    pub fn fallback<S: Into<String>>(mut self, tag: S) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Get the chain of tags in lookup order: each given tag is followed by its more generic tags (subtags are split
+    /// by `-` or `_`), e.g. `fr-CA`, `fr`, `en` for `fr-CA` locale with `en` fallback. Duplicates are skipped.
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain: Vec<String> = Vec::new();
+        for tag in &self.tags {
+            let mut tag = tag.as_str();
+            while !tag.is_empty() {
+                if !chain.iter().any(|t| t == tag) {
+                    chain.push(tag.to_string());
+                }
+                tag = tag.rfind(['-', '_']).map_or("", |i| &tag[..i]);
+            }
+        }
+        chain
+    }
+}
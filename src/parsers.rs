@@ -1,18 +1,69 @@
 //! This module define base structures ([`FileParser`] and [`FileParserBuilder`]) which help to implement file based
 //! parsers. All embedded file based parsers is using that base structures.
+//!
+//! If path of the file is [`STDIN_PATH`] then the document is read from standard input with the parser's loader, so
+//! rendered configuration could be piped into application (e.g. `render | app --config -`). The standard input is
+//! read only once, the same value is returned on reload.
+//!
+//! The compressed files are decompressed transparently according to their last extension: `.gz` (`gzip` feature) and
+//! `.zst` (`zstd` feature), so `config.json.gz` will be loaded as `config.json`.
+//!
+//! The files in encodings other than `UTF-8` (e.g. `UTF-16LE` or `Windows-1252` produced by Windows tooling) could be
+//! loaded with `encoding` feature (see [`FileParserBuilder::encoding`]).
+//!
+//! If include key is set (see [`FileParserBuilder::include_key`]), then the file could include other files by path(s)
+//! relative to the including file, e.g. `$include: [common.yaml, db.yaml]`. The included files are loaded recursively
+//! and merged in the list order, the later files and the entries of the including file win. The included files are
+//! watched too.
+//!
+//! If profile is selected (see [`crate::ConfigBuilder::profile`]), then existing profile siblings of the files (e.g.
+//! `config.prod.yaml` for `config.yaml`) are loaded after them and merged over them.
 
+#[cfg(feature = "auto")]
+pub mod auto;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "cloud-metadata")]
+pub mod cloud_metadata;
 #[cfg(feature = "cmd")]
 pub mod cmd;
+#[cfg(feature = "consul")]
+pub mod consul;
+pub mod dir;
+#[cfg(feature = "dotenv")]
+pub mod dotenv;
 #[cfg(feature = "env")]
 pub mod env;
+#[cfg(feature = "gcp-secrets")]
+pub mod gcp_secrets;
 #[cfg(feature = "json")]
 pub mod json;
 #[cfg(feature = "json5-parser")]
 pub mod json5;
+#[cfg(feature = "kdl")]
+pub mod kdl;
+#[cfg(feature = "keyring")]
+pub mod keyring;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+#[cfg(feature = "properties")]
+pub mod properties;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "systemd")]
+pub mod systemd;
+#[cfg(feature = "template")]
+pub mod template;
 #[cfg(test)]
 mod tests;
 #[cfg(feature = "toml-parser")]
 pub mod toml;
+#[cfg(feature = "uri")]
+pub mod uri;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 #[cfg(feature = "yaml")]
 pub mod yaml;
 
@@ -21,7 +72,8 @@ use derive_builder::Builder;
 use std::{
     borrow::Cow,
     fs::File,
-    io::{BufReader, Error as IoError, Read},
+    io::{self, BufReader, Error as IoError, Read},
+    iter,
     path::{Path, PathBuf},
     result::Result as StdResult,
 };
@@ -31,6 +83,17 @@ pub type Result<T> = StdResult<T, Error>;
 
 type CowPath<'a> = Cow<'a, Path>;
 
+/// The path which means standard input of the process.
+pub const STDIN_PATH: &str = "-";
+
+const COMPRESSION_EXTENSIONS: [&str; 2] = ["gz", "zst"];
+
+/// The default key of include directive (see [`FileParserBuilder::include_key`]).
+pub const DEFAULT_INCLUDE_KEY: &str = "$include";
+
+/// The default maximum nesting depth of included files.
+pub const DEFAULT_MAX_INCLUDE_DEPTH: usize = 8;
+
 /// All errors for file-based parsers.
 #[non_exhaustive]
 #[derive(thiserror::Error, Debug)]
@@ -43,6 +106,20 @@ pub enum Error {
     Meta(#[source] IoError, PathBuf),
     #[error("Is not a file: '{0}'")]
     NotAFile(PathBuf),
+    #[error("Failed to read standard input")]
+    Stdin(#[source] IoError),
+    #[error("Invalid include directive in file: '{0}'")]
+    InvalidInclude(PathBuf),
+    #[error("Cyclic include of file: '{0}'")]
+    IncludeCycle(PathBuf),
+    #[error("Maximum include depth {1} is exceeded by file: '{0}'")]
+    IncludeDepth(PathBuf, usize),
+    #[cfg(feature = "zstd")]
+    #[error("Failed to decompress file: '{1}'")]
+    Zstd(
+        #[source] ruzstd::decoding::errors::FrameDecoderError,
+        PathBuf,
+    ),
 }
 
 /// The trait to be used by [`FileParser`] to load data from file in specific format.
@@ -53,6 +130,11 @@ pub trait Load: Case {
     ///
     /// If any errors will occur during load then error will be returned.
     fn load(&mut self, reader: impl Read) -> AnyResult<Value>;
+
+    /// Called by [`FileParser`] before loading files with the merged [`Value`] results from previous parsers (see
+    /// [`Parse::parse`]), so loader could use them (e.g. as template context). Default implementation does nothing.
+    #[inline]
+    fn prepare(&mut self, _value: &Value) {}
 }
 
 /// The base structure to implement file based parsers.
@@ -61,7 +143,9 @@ pub trait Load: Case {
 pub struct FileParser<L: Load + Default> {
     /// Set default path to the file to be parsed.
     default_path: PathBuf,
-    /// Set path option name which could be used to get path value from previous parsing [`Value`] results.
+    /// Set path option name which could be used to get path value from previous parsing [`Value`] results. The value
+    /// could be a list of paths (e.g. `--config a.yaml --config b.yaml`), then all files will be loaded and merged in
+    /// the list order, the last file wins.
     #[builder(default = "None")]
     path_option: Option<String>,
     /// Set delimiter used to separate keys levels in path value. Default is [`DEFAULT_KEYS_SEPARATOR`].
@@ -73,6 +157,24 @@ pub struct FileParser<L: Load + Default> {
     /// Set the loader structure which implements [`Load`] trait.
     #[builder(default)]
     loader: L,
+    /// Set encoding of the file (e.g. `encoding_rs::WINDOWS_1252`), the data will be transcoded to `UTF-8` before
+    /// load. The byte order mark (if any) takes precedence. Default is `UTF-8`.
+    #[cfg(feature = "encoding")]
+    #[builder(default = "None")]
+    encoding: Option<&'static encoding_rs::Encoding>,
+    /// Set key of include directive (e.g. [`DEFAULT_INCLUDE_KEY`]), its value is path or list of paths to be included.
+    /// Default is no includes.
+    #[builder(default = "None")]
+    include_key: Option<String>,
+    /// Set maximum nesting depth of included files. Default is [`DEFAULT_MAX_INCLUDE_DEPTH`].
+    #[builder(default = "DEFAULT_MAX_INCLUDE_DEPTH")]
+    max_include_depth: usize,
+    #[builder(setter(skip))]
+    last_paths: Vec<PathBuf>,
+    #[builder(setter(skip))]
+    stdin_value: Option<Value>,
+    #[builder(setter(skip))]
+    profile: Option<String>,
 }
 
 impl<L: Load + Default> Case for FileParser<L> {
@@ -84,38 +186,258 @@ impl<L: Load + Default> Case for FileParser<L> {
 
 impl<L: Load + Default> Parse for FileParser<L> {
     fn parse(&mut self, value: &Value) -> AnyResult<Value> {
-        let path = get_path(
+        let paths = get_paths(
             value,
             &self.path_option,
             &self.default_path,
             &self.keys_delimiter,
         )?;
+        let paths = with_profile(paths, self.profile.as_deref());
+        self.last_paths = paths.iter().map(|p| p.to_path_buf()).collect();
+        self.loader.prepare(value);
+        let mut includes = Includes::new(self.include_key.as_deref(), self.max_include_depth);
+        let result = load_files(
+            &paths,
+            self.ignore_missing_file,
+            &mut self.stdin_value,
+            &mut includes,
+            |_, r| {
+                #[cfg(feature = "encoding")]
+                if let Some(encoding) = self.encoding {
+                    let reader = encoding_rs_io::DecodeReaderBytesBuilder::new()
+                        .encoding(Some(encoding))
+                        .bom_override(true)
+                        .strip_bom(true)
+                        .build(r);
+                    return self.loader.load(reader);
+                }
+                self.loader.load(r)
+            },
+        );
+        self.last_paths.append(&mut includes.loaded);
+        result
+    }
 
-        let file = match try_open_file(path.as_ref()) {
-            Ok(f) => f,
-            Err(_) if self.ignore_missing_file => return Ok(Value::default()),
-            Err(e) => return Err(e.into()),
-        };
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        watched(&self.last_paths)
+    }
 
-        self.loader.load(BufReader::new(file))
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        self.path_option.iter().cloned().collect()
+    }
+
+    #[inline]
+    fn select_profile(&mut self, profile: &str) {
+        self.profile = Some(profile.into());
     }
 }
 
-fn get_path<'a>(
+fn get_paths<'a>(
     value: &Value,
     path_option: &Option<String>,
     default: &'a Path,
     delim: &str,
-) -> Result<CowPath<'a>> {
-    let default = default.into();
+) -> Result<Vec<CowPath<'a>>> {
+    let default = vec![default.into()];
     let Some(option) = path_option else {
         return Ok(default);
     };
 
-    let path: Option<String> = value
-        .get_by_key_path_with_delim(option, delim)
-        .map_err(|e| Error::PathOption(e, option.into()))?;
-    Ok(path.map_or(default, |p| PathBuf::from(p).into()))
+    let paths: Option<Vec<String>> = match value.get_by_key_path_with_delim(option, delim) {
+        Ok(p) => p.map(|p: String| vec![p]),
+        Err(_) => value
+            .get_by_key_path_with_delim(option, delim)
+            .map_err(|e| Error::PathOption(e, option.into()))?,
+    };
+    Ok(paths.map_or(default, |p| {
+        p.into_iter().map(|p| PathBuf::from(p).into()).collect()
+    }))
+}
+
+fn load_files<F>(
+    paths: &[CowPath],
+    ignore_missing: bool,
+    stdin_value: &mut Option<Value>,
+    includes: &mut Includes,
+    mut load: F,
+) -> AnyResult<Value>
+where
+    F: FnMut(&Path, &mut dyn Read) -> AnyResult<Value>,
+{
+    let mut result: Option<Value> = None;
+    for path in paths {
+        let value = if path.as_os_str() == STDIN_PATH {
+            let mut value = match stdin_value {
+                Some(v) => v.clone(),
+                None => {
+                    let mut data = Vec::new();
+                    io::stdin().read_to_end(&mut data).map_err(Error::Stdin)?;
+                    stdin_value
+                        .insert(load(path, &mut data.as_slice())?)
+                        .clone()
+                }
+            };
+            includes.apply(&mut value, Path::new(""), &mut load)?;
+            value
+        } else {
+            let file = match try_open_file(path) {
+                Ok(f) => f,
+                Err(_) if ignore_missing => continue,
+                Err(e) => return Err(e.into()),
+            };
+            read_file(path, file, includes, &mut load)?
+        };
+        match result {
+            Some(ref mut r) => r.merge_from(&value),
+            None => result = Some(value),
+        }
+    }
+    Ok(result.unwrap_or_default())
+}
+
+fn read_file<F>(path: &Path, file: File, includes: &mut Includes, load: &mut F) -> AnyResult<Value>
+where
+    F: FnMut(&Path, &mut dyn Read) -> AnyResult<Value>,
+{
+    let (decompressed, mut reader) = decompress(path, BufReader::new(file))?;
+    let mut value = load(&decompressed, &mut reader)?;
+    includes.apply(&mut value, path, load)?;
+    Ok(value)
+}
+
+struct Includes<'a> {
+    key: Option<&'a str>,
+    max_depth: usize,
+    stack: Vec<PathBuf>,
+    loaded: Vec<PathBuf>,
+}
+
+impl<'a> Includes<'a> {
+    fn new(key: Option<&'a str>, max_depth: usize) -> Self {
+        Self {
+            key,
+            max_depth,
+            stack: Vec::new(),
+            loaded: Vec::new(),
+        }
+    }
+
+    fn apply<F>(&mut self, value: &mut Value, path: &Path, load: &mut F) -> AnyResult<()>
+    where
+        F: FnMut(&Path, &mut dyn Read) -> AnyResult<Value>,
+    {
+        let Some(key) = self.key else {
+            return Ok(());
+        };
+        if !value.as_inner().is_object() {
+            return Ok(());
+        }
+        let Some(directive) = value.remove_by_keys([key])? else {
+            return Ok(());
+        };
+
+        let targets: Vec<String> = match directive.get::<String>() {
+            Ok(target) => vec![target],
+            Err(_) => directive
+                .get()
+                .map_err(|_| Error::InvalidInclude(path.into()))?,
+        };
+        if self.stack.len() >= self.max_depth {
+            return Err(Error::IncludeDepth(path.into(), self.max_depth).into());
+        }
+
+        self.stack.push(canonical(path));
+        let base = path.parent().unwrap_or(Path::new(""));
+        let mut result = Value::with_case(value.is_case_sensitive());
+        for target in targets {
+            let target = base.join(target);
+            if self.stack.contains(&canonical(&target)) {
+                return Err(Error::IncludeCycle(target).into());
+            }
+            let file = try_open_file(&target)?;
+            let included = read_file(&target, file, self, load)?;
+            self.loaded.push(target);
+            result.merge_from(&included);
+        }
+        self.stack.pop();
+
+        result.merge_from(value);
+        *value = result;
+        Ok(())
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.into())
+}
+
+fn decompress(path: &Path, reader: BufReader<File>) -> Result<(CowPath<'_>, Box<dyn Read>)> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    match extension {
+        #[cfg(feature = "gzip")]
+        "gz" => Ok((
+            path.with_extension("").into(),
+            Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+        )),
+        #[cfg(feature = "zstd")]
+        "zst" => {
+            let decoder = ruzstd::decoding::StreamingDecoder::new(reader)
+                .map_err(|e| Error::Zstd(e, path.into()))?;
+            Ok((path.with_extension("").into(), Box::new(decoder)))
+        }
+        _ => Ok((path.into(), Box::new(reader))),
+    }
+}
+
+/// Append existing profile siblings (see [`profile_path`]) after corresponding paths.
+fn with_profile<'a>(paths: Vec<CowPath<'a>>, profile: Option<&str>) -> Vec<CowPath<'a>> {
+    let Some(profile) = profile else {
+        return paths;
+    };
+    paths
+        .into_iter()
+        .flat_map(|p| {
+            let sibling = profile_path(&p, profile).filter(|s| s.is_file());
+            iter::once(p).chain(sibling.map(CowPath::from))
+        })
+        .collect()
+}
+
+/// Return path of profile sibling, e.g. `config.prod.yaml` for `config.yaml` or `config.prod.json.gz` for
+/// `config.json.gz`.
+fn profile_path(path: &Path, profile: &str) -> Option<PathBuf> {
+    if path.as_os_str() == STDIN_PATH {
+        return None;
+    }
+
+    let compression = path
+        .extension()
+        .filter(|e| COMPRESSION_EXTENSIONS.iter().any(|c| e == c));
+    let base = match compression {
+        Some(_) => path.with_extension(""),
+        None => path.to_path_buf(),
+    };
+    let mut name = base.file_stem()?.to_os_string();
+    name.push(".");
+    name.push(profile);
+    for extension in base.extension().into_iter().chain(compression) {
+        name.push(".");
+        name.push(extension);
+    }
+    Some(path.with_file_name(name))
+}
+
+fn watched(paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|p| p.as_os_str() != STDIN_PATH)
+        .cloned()
+        .collect()
 }
 
 fn try_open_file(path: &Path) -> Result<File> {
@@ -0,0 +1,156 @@
+//! This module provide file parser implementation which detects format of the file by its extension at parse time,
+//! so the path could be taken from other parsers (e.g. `--config` command-line argument) without knowing the format
+//! ahead of time. The extensions are matched case insensitively:
+//!
+//! | Extension         | Format        | Feature        |
+//! |-------------------|---------------|----------------|
+//! | `json`            | `JSON`        | `auto`         |
+//! | `json5`           | `JSON5`       | `json5-parser` |
+//! | `yaml`, `yml`     | `YAML`        | `yaml`         |
+//! | `toml`            | `TOML`        | `toml-parser`  |
+//! | `properties`      | `.properties` | `properties`   |
+//! | `kdl`             | `KDL`         | `kdl`          |
+//! | `msgpack`, `mpk`  | `MessagePack` | `msgpack`      |
+//! | `cbor`            | `CBOR`        | `cbor`         |
+//!
+//! The `JSON` format is always enabled, the other formats will be detected only if their features are enabled. For
+//! other extensions error will be returned.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["auto", "yaml", "toml-parser"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::auto::ParserBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_path("config.yaml")
+//!             .path_option("config")
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{
+        get_paths, load_files, watched, with_profile, Includes, Load, DEFAULT_MAX_INCLUDE_DEPTH,
+    },
+    AnyResult, Case, Parse, Value, DEFAULT_KEYS_SEPARATOR,
+};
+use derive_builder::Builder;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// All errors for auto-detection parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Unsupported format of file: '{0}'")]
+    UnknownFormat(PathBuf),
+}
+
+/// The file parser which detects format by file extension.
+#[derive(Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Parser {
+    /// Set default path to the file to be parsed.
+    default_path: PathBuf,
+    /// Set path option name which could be used to get path value from previous parsing [`Value`] results. The value
+    /// could be a list of paths, then all files will be loaded and merged in the list order, the last file wins.
+    #[builder(default = "None")]
+    path_option: Option<String>,
+    /// Set delimiter used to separate keys levels in path value. Default is [`DEFAULT_KEYS_SEPARATOR`].
+    #[builder(default = "DEFAULT_KEYS_SEPARATOR.to_string()")]
+    keys_delimiter: String,
+    /// If file does not exists do not try to load it. The default [`Value`] will be returned. Default is `false`.
+    #[builder(default = "false")]
+    ignore_missing_file: bool,
+    /// Set key of include directive (see [`crate::parsers::FileParserBuilder::include_key`]), the included files
+    /// could be in any detected format. Default is no includes.
+    #[builder(default = "None")]
+    include_key: Option<String>,
+    /// Set maximum nesting depth of included files. Default is [`DEFAULT_MAX_INCLUDE_DEPTH`].
+    #[builder(default = "DEFAULT_MAX_INCLUDE_DEPTH")]
+    max_include_depth: usize,
+    #[builder(setter(skip))]
+    last_paths: Vec<PathBuf>,
+    #[builder(setter(skip))]
+    stdin_value: Option<Value>,
+    #[builder(setter(skip))]
+    profile: Option<String>,
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, value: &Value) -> AnyResult<Value> {
+        let paths = get_paths(
+            value,
+            &self.path_option,
+            &self.default_path,
+            &self.keys_delimiter,
+        )?;
+        let paths = with_profile(paths, self.profile.as_deref());
+        self.last_paths = paths.iter().map(|p| p.to_path_buf()).collect();
+        let mut includes = Includes::new(self.include_key.as_deref(), self.max_include_depth);
+        let result = load_files(
+            &paths,
+            self.ignore_missing_file,
+            &mut self.stdin_value,
+            &mut includes,
+            |p, r| load(p, r),
+        );
+        self.last_paths.append(&mut includes.loaded);
+        result
+    }
+
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        watched(&self.last_paths)
+    }
+
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        self.path_option.iter().cloned().collect()
+    }
+
+    #[inline]
+    fn select_profile(&mut self, profile: &str) {
+        self.profile = Some(profile.into());
+    }
+}
+
+pub(crate) fn load(path: &Path, reader: impl Read) -> AnyResult<Value> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "json" => super::json::LoadJson.load(reader),
+        #[cfg(feature = "json5-parser")]
+        "json5" => super::json5::LoadJson.load(reader),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => super::yaml::LoadYaml.load(reader),
+        #[cfg(feature = "toml-parser")]
+        "toml" => super::toml::LoadToml.load(reader),
+        #[cfg(feature = "properties")]
+        "properties" => super::properties::LoadProperties::default().load(reader),
+        #[cfg(feature = "kdl")]
+        "kdl" => super::kdl::LoadKdl::default().load(reader),
+        #[cfg(feature = "msgpack")]
+        "msgpack" | "mpk" => super::msgpack::LoadMsgpack.load(reader),
+        #[cfg(feature = "cbor")]
+        "cbor" => super::cbor::LoadCbor.load(reader),
+        _ => Err(Error::UnknownFormat(path.to_path_buf()).into()),
+    }
+}
@@ -0,0 +1,77 @@
+//! This module provide [CBOR](https://cbor.io/) parser implementation. The root of the document should be a map.
+//! The tags (e.g. self-described CBOR or COSE tags) are ignored and the integer keys of maps are converted to strings.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["cbor"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::cbor::ParserBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_path("config.cbor")
+//!             .path_option("config")
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{FileParserBuilder, Load},
+    AnyResult, Case, Value,
+};
+use ciborium::Value as CborValue;
+use std::io::{Error as IoError, Read};
+
+/// All errors for `CBOR` parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed parse CBOR")]
+    ParseCbor(#[source] ciborium::de::Error<IoError>),
+    #[error("Failed to convert CBOR value")]
+    Convert(#[source] ciborium::value::Error),
+}
+
+/// Builder for `CBOR` parser.
+pub type ParserBuilder = FileParserBuilder<LoadCbor>;
+
+/// Implements [`Load`] trait for `CBOR` parser.
+#[derive(Clone, Default)]
+pub struct LoadCbor;
+
+impl Case for LoadCbor {}
+
+impl Load for LoadCbor {
+    fn load(&mut self, reader: impl Read) -> AnyResult<Value> {
+        let value: CborValue = ciborium::from_reader(reader).map_err(Error::ParseCbor)?;
+        Ok(normalize(value).deserialized().map_err(Error::Convert)?)
+    }
+}
+
+fn normalize(value: CborValue) -> CborValue {
+    match value {
+        CborValue::Tag(_, v) => normalize(*v),
+        CborValue::Array(a) => CborValue::Array(a.into_iter().map(normalize).collect()),
+        CborValue::Map(m) => CborValue::Map(
+            m.into_iter()
+                .map(|(k, v)| {
+                    let key = match normalize(k) {
+                        CborValue::Integer(i) => CborValue::Text(i128::from(i).to_string()),
+                        k => k,
+                    };
+                    (key, normalize(v))
+                })
+                .collect(),
+        ),
+        v => v,
+    }
+}
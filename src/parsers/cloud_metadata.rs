@@ -0,0 +1,302 @@
+//! This module provide cloud instance metadata parser implementation. It fetches selected facts (region, zone,
+//! instance id, tags) from instance metadata services of `EC2` (`IMDSv2`), `GCE` and `Azure` and stores them under
+//! given key path (default is [`DEFAULT_KEY_PATH`]):
+//!
+//! ```yaml
+//! facts:
+//!   cloud:
+//!     provider: aws
+//!     region: us-east-1
+//!     zone: us-east-1a
+//!     instance_id: i-0123456789abcdef0
+//!     tags:
+//!       team: core
+//! ```
+//!
+//! The providers are probed in given order with short timeouts (default is [`DEFAULT_TIMEOUT`]), the first one which
+//! answered will be used. If no provider is available then empty [`Value`] will be returned, so the application could
+//! run outside of a cloud. The facts will be fetched only once, during first (re)load.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["cloud-metadata"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::cloud_metadata::{Field, ParserBuilder};
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .field(Field::Region)
+//!             .field(Field::Zone)
+//!             .build()?,
+//!     )
+//!     .load()?;
+//!
+//! let region: Option<String> = config.get_by_key_path("facts:cloud:region")?;
+//! ```
+
+use crate::{json, AnyResult, Case, Parse, Value, DEFAULT_KEYS_SEPARATOR};
+use derive_builder::Builder;
+use std::{collections::BTreeMap, time::Duration};
+use ureq::Agent;
+
+/// The default key path to store cloud facts.
+pub const DEFAULT_KEY_PATH: &str = "facts:cloud";
+
+/// The default endpoint of instance metadata services.
+pub const DEFAULT_ENDPOINT: &str = "http://169.254.169.254";
+
+/// The default timeout for each metadata request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// All errors for cloud metadata parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{1} for keys: '{2}'")]
+    Value(#[source] crate::Error, &'static str, String),
+}
+
+/// Cloud providers supported by the parser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Provider {
+    /// Amazon `EC2` instance metadata service (`IMDSv2`).
+    Aws,
+    /// Google Compute Engine metadata server.
+    Gcp,
+    /// Azure instance metadata service.
+    Azure,
+}
+
+impl Provider {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Aws => "aws",
+            Self::Gcp => "gcp",
+            Self::Azure => "azure",
+        }
+    }
+}
+
+/// Facts which could be fetched from instance metadata services.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    /// The region name, stored as `region` key.
+    Region,
+    /// The availability zone name, stored as `zone` key.
+    Zone,
+    /// The instance identifier, stored as `instance_id` key.
+    InstanceId,
+    /// The instance tags, stored as `tags` key.
+    Tags,
+}
+
+/// The cloud metadata parser implementation.
+#[derive(Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Parser {
+    /// Add provider to probe. If no providers were added then all providers will be probed in order: `AWS`, `GCP`,
+    /// `Azure`.
+    #[builder(default, setter(each(name = "provider")))]
+    providers: Vec<Provider>,
+    /// Add fact to fetch. If no fields were added then all facts will be fetched.
+    #[builder(default, setter(each(name = "field")))]
+    fields: Vec<Field>,
+    /// Set key path to store fetched facts. Default is [`DEFAULT_KEY_PATH`].
+    #[builder(default = "DEFAULT_KEY_PATH.to_string()")]
+    key_path: String,
+    /// Set delimiter used to separate keys levels in key path. Default is [`DEFAULT_KEYS_SEPARATOR`].
+    #[builder(default = "DEFAULT_KEYS_SEPARATOR.to_string()")]
+    keys_delimiter: String,
+    /// Set endpoint of instance metadata services. Default is [`DEFAULT_ENDPOINT`].
+    #[builder(default = "DEFAULT_ENDPOINT.to_string()")]
+    endpoint: String,
+    /// Set timeout for each metadata request. Default is [`DEFAULT_TIMEOUT`].
+    #[builder(default = "DEFAULT_TIMEOUT")]
+    timeout: Duration,
+    #[builder(setter(skip))]
+    value: Option<Value>,
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        if let Some(ref v) = self.value {
+            return Ok(v.clone());
+        }
+
+        let agent: Agent = Agent::config_builder()
+            .timeout_global(Some(self.timeout))
+            .build()
+            .into();
+        let providers = if self.providers.is_empty() {
+            &[Provider::Aws, Provider::Gcp, Provider::Azure][..]
+        } else {
+            &self.providers
+        };
+
+        let mut result = Value::default();
+        if let Some((provider, facts)) = providers
+            .iter()
+            .find_map(|p| self.fetch(&agent, *p).map(|f| (*p, f)))
+        {
+            let mut facts = facts;
+            facts.insert("provider".into(), json!(provider.name()));
+            result
+                .set_by_key_path_with_delim(&self.key_path, &self.keys_delimiter, facts)
+                .map_err(|e| Error::Value(e, "Failed to set value", self.key_path.clone()))?;
+        }
+
+        self.value = Some(result.clone());
+        Ok(result)
+    }
+
+    #[inline]
+    fn is_remote(&self, _value: &Value) -> bool {
+        true
+    }
+}
+
+type Facts = BTreeMap<String, serde_json::Value>;
+
+impl Parser {
+    fn has(&self, field: Field) -> bool {
+        self.fields.is_empty() || self.fields.contains(&field)
+    }
+
+    fn fetch(&self, agent: &Agent, provider: Provider) -> Option<Facts> {
+        match provider {
+            Provider::Aws => self.fetch_aws(agent),
+            Provider::Gcp => self.fetch_gcp(agent),
+            Provider::Azure => self.fetch_azure(agent),
+        }
+    }
+
+    fn fetch_aws(&self, agent: &Agent) -> Option<Facts> {
+        let token = agent
+            .put(format!("{}/latest/api/token", self.endpoint))
+            .header("X-aws-ec2-metadata-token-ttl-seconds", "60")
+            .send_empty()
+            .ok()?
+            .body_mut()
+            .read_to_string()
+            .ok()?;
+        let get = |path: &str| {
+            agent
+                .get(format!("{}/latest/meta-data/{path}", self.endpoint))
+                .header("X-aws-ec2-metadata-token", &token)
+                .call()
+                .ok()?
+                .body_mut()
+                .read_to_string()
+                .ok()
+        };
+
+        let mut facts = Facts::new();
+        let id = get("instance-id")?;
+        if self.has(Field::InstanceId) {
+            facts.insert("instance_id".into(), json!(id));
+        }
+        if self.has(Field::Region) {
+            facts.insert("region".into(), json!(get("placement/region")?));
+        }
+        if self.has(Field::Zone) {
+            facts.insert("zone".into(), json!(get("placement/availability-zone")?));
+        }
+        if self.has(Field::Tags) {
+            // Tags are available only if access to tags in instance metadata is allowed.
+            let tags: BTreeMap<_, _> = get("tags/instance")
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|k| Some((k.to_string(), get(&["tags/instance/", k].concat())?)))
+                .collect();
+            facts.insert("tags".into(), json!(tags));
+        }
+        Some(facts)
+    }
+
+    fn fetch_gcp(&self, agent: &Agent) -> Option<Facts> {
+        let get = |path: &str| {
+            agent
+                .get(format!(
+                    "{}/computeMetadata/v1/instance/{path}",
+                    self.endpoint
+                ))
+                .header("Metadata-Flavor", "Google")
+                .call()
+                .ok()?
+                .body_mut()
+                .read_to_string()
+                .ok()
+        };
+
+        let mut facts = Facts::new();
+        let id = get("id")?;
+        if self.has(Field::InstanceId) {
+            facts.insert("instance_id".into(), json!(id));
+        }
+        if self.has(Field::Region) || self.has(Field::Zone) {
+            // Zone has format: `projects/<number>/zones/<zone>`, region is zone without last suffix.
+            let zone = get("zone")?;
+            let zone = zone.rsplit('/').next().unwrap_or_default();
+            if self.has(Field::Region) {
+                let region = zone.rsplit_once('-').map_or(zone, |(r, _)| r);
+                facts.insert("region".into(), json!(region));
+            }
+            if self.has(Field::Zone) {
+                facts.insert("zone".into(), json!(zone));
+            }
+        }
+        if self.has(Field::Tags) {
+            let tags: serde_json::Value = get("tags?alt=json")
+                .and_then(|t| serde_json::from_str(&t).ok())
+                .unwrap_or_else(|| json!([]));
+            facts.insert("tags".into(), tags);
+        }
+        Some(facts)
+    }
+
+    fn fetch_azure(&self, agent: &Agent) -> Option<Facts> {
+        let data = agent
+            .get(format!(
+                "{}/metadata/instance/compute?api-version=2021-02-01",
+                self.endpoint
+            ))
+            .header("Metadata", "true")
+            .call()
+            .ok()?
+            .body_mut()
+            .read_to_string()
+            .ok()?;
+        let compute: serde_json::Value = serde_json::from_str(&data).ok()?;
+
+        let mut facts = Facts::new();
+        if self.has(Field::InstanceId) {
+            facts.insert("instance_id".into(), compute["vmId"].clone());
+        }
+        if self.has(Field::Region) {
+            facts.insert("region".into(), compute["location"].clone());
+        }
+        if self.has(Field::Zone) {
+            facts.insert("zone".into(), compute["zone"].clone());
+        }
+        if self.has(Field::Tags) {
+            let tags: BTreeMap<_, _> = compute["tagsList"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|t| Some((t["name"].as_str()?.to_string(), t["value"].clone())))
+                .collect();
+            facts.insert("tags".into(), json!(tags));
+        }
+        Some(facts)
+    }
+}
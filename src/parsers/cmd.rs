@@ -48,18 +48,50 @@
 //!     .append_parser(ParserBuilder::new(command).global_key_names(false).build()?)
 //!     .load()?;
 //! ```
+//!
+//! The command-line interface defined via `clap` derive API could be used directly (see
+//! [`ParserBuilder::from_derive`]). The arguments ids are used as keys names, so nested keys could be set by `id`
+//! attribute.
+//!
+//! ```
+//! use clap::{Parser, ValueEnum};
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::cmd::ParserBuilder;
+//!
+//! #[derive(Clone, ValueEnum)]
+//! enum Mode {
+//!     Fast,
+//!     Safe,
+//! }
+//!
+//! #[derive(Parser)]
+//! struct Cli {
+//!     #[arg(id = "settings:host", short = 'H', long = "host")]
+//!     host: Option<String>,
+//!     #[arg(long, value_enum)]
+//!     mode: Option<Mode>,
+//! }
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(ParserBuilder::from_derive::<Cli>().build()?)
+//!     .load()?;
+//! ```
 
 use crate::{AnyResult, Case, CowString, Parse, StdResult, Value, DEFAULT_KEYS_SEPARATOR};
 use clap::{
     error::Result as ClapResult, parser::ValueSource, value_parser, Arg, ArgAction, ArgMatches,
-    Command,
+    Command, CommandFactory, ValueHint,
 };
+use serde_json::Value as JsonValue;
 use serde_yaml::Value as YamlValue;
 use std::{
+    any::Any,
     borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
     env,
     ffi::{OsStr, OsString},
-    path::PathBuf,
+    io::Error as IoError,
+    path::{Path, PathBuf},
 };
 
 /// A result type for command-line parser errors.
@@ -78,6 +110,34 @@ pub enum Error {
     Clap(#[source] clap::Error, Cow<'static, str>),
     #[error("{1}")]
     Common(#[source] crate::Error, Cow<'static, str>),
+    #[error("{1}")]
+    IO(#[source] IoError, Cow<'static, str>),
+}
+
+/// The mode of boolean flags handling (see [`ParserBuilder::bool_flags`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoolMode {
+    /// The values of flags are typed in the same way as values of other arguments.
+    #[default]
+    Typed,
+    /// The values of `ArgAction::SetTrue` and `ArgAction::SetFalse` flags are always booleans.
+    Strict,
+    /// Same as [`BoolMode::Strict`], but `ArgAction::Count` flags are booleans too, which are `true` if flag is
+    /// present.
+    Presence,
+}
+
+/// The mode of `ArgAction::Count` flags handling (see [`ParserBuilder::count_as`]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CountMode {
+    /// The value is the number of flag occurrences.
+    #[default]
+    Int,
+    /// The value is `true` if flag is present.
+    Bool,
+    /// The value is the level name from the list indexed by the number of flag occurrences (the last one is used if
+    /// the number exceeds the list), e.g. `["warn", "info", "debug", "trace"]` for `-v`, `-vv` and so on.
+    Level(Vec<String>),
 }
 
 /// The command-line parser implementation.
@@ -110,6 +170,14 @@ pub struct ParserBuilder {
     exit_on_error: bool,
     use_arg_types: bool,
     use_defaults: bool,
+    unknown_args_key: Option<String>,
+    absolute_paths: bool,
+    bool_flags: BoolMode,
+    count_as: Option<CountMode>,
+    arg_count_as: Vec<(String, CountMode)>,
+    subcommand_key: Option<String>,
+    renamed_args: BTreeMap<String, String>,
+    excluded_args: BTreeSet<String>,
 }
 
 impl ParserBuilder {
@@ -126,9 +194,30 @@ impl ParserBuilder {
             exit_on_error: false,
             use_arg_types: true,
             use_defaults: false,
+            unknown_args_key: None,
+            absolute_paths: false,
+            bool_flags: BoolMode::Typed,
+            count_as: None,
+            arg_count_as: Vec::new(),
+            subcommand_key: None,
+            renamed_args: BTreeMap::new(),
+            excluded_args: BTreeSet::new(),
         }
     }
 
+    /// Create [`ParserBuilder`] from type which implements `clap::CommandFactory` trait (e.g. derived by
+    /// `#[derive(clap::Parser)]`). The values of arguments with `ValueEnum` or other possible values (except
+    /// booleans) are treated as strings.
+    ///
+    /// **NOTE:** Types of custom value parsers (e.g. `#[arg(value_parser = parse_duration)]`) are not honored. Only
+    /// values of boolean, integer, floating point and string value parsers are typed (see
+    /// [`ParserBuilder::use_arg_types`]), values of any other types are taken as raw command-line strings and typed
+    /// according to `YAML` format.
+    #[inline]
+    pub fn from_derive<T: CommandFactory>() -> Self {
+        Self::new(T::command())
+    }
+
     /// Set arguments to be parsed, otherwise program command-line arguments will be used.
     #[inline]
     pub fn args<I, T>(&mut self, args: I) -> &mut Self
@@ -172,14 +261,17 @@ impl ParserBuilder {
         self
     }
 
-    /// If set to `true` then exit from program on `clap::Error` during building stage. Default is `false`.
+    /// If set to `true` then exit from program on `clap::Error` during building stage by [`ParserBuilder::build`]
+    /// (see [`ParserBuilder::build_no_exit`]). Default is `false`.
     #[inline]
     pub fn exit_on_error(&mut self, on: bool) -> &mut Self {
         self.exit_on_error = on;
         self
     }
 
-    /// Use `ArgAction` or `ValueParser` type to calculate type of an argument. Default is `true`.
+    /// Use `ArgAction` or `ValueParser` type to calculate type of an argument. The values of arguments with boolean,
+    /// integer or floating point value parsers, or with possible values (e.g. `PossibleValuesParser`) are taken from
+    /// `clap` matches as typed values. Default is `true`.
     #[inline]
     pub fn use_arg_types(&mut self, on: bool) -> &mut Self {
         self.use_arg_types = on;
@@ -196,6 +288,97 @@ impl ParserBuilder {
         self
     }
 
+    /// Set key name to store unknown (external) subcommand with its arguments as an array of strings. The external
+    /// subcommands have to be allowed via `clap::Command::allow_external_subcommands`. The key name will be prefixed
+    /// with (sub)command name(s) in the same way as arguments names (see [`ParserBuilder::global_key_names`] method).
+    /// Default is `None`, unknown arguments will not be stored.
+    ///
+    /// **IMPORTANT:** The arguments which was dropped by `clap` because of `clap::Command::ignore_errors` could not be
+    /// captured, since `clap` does not provide them back.
+    #[inline]
+    pub fn unknown_args_key<S>(&mut self, key: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.unknown_args_key = Some(key.into());
+        self
+    }
+
+    /// Use given key name (could contain keys delimiter) instead of argument id as key name of argument's value, e.g.
+    /// `rename_arg("cfg-file", "settings:config:path")`. The key name will be prefixed with (sub)command name(s) in
+    /// the same way as arguments names (see [`ParserBuilder::global_key_names`] method).
+    #[inline]
+    pub fn rename_arg<I, K>(&mut self, id: I, key: K) -> &mut Self
+    where
+        I: Into<String>,
+        K: Into<String>,
+    {
+        self.renamed_args.insert(id.into(), key.into());
+        self
+    }
+
+    /// Exclude arguments with given ids from parsed [`Value`], so purely operational arguments (e.g. `completions` or
+    /// `dry-run`) do not leak into configuration data. The arguments are excluded in all (sub)commands.
+    #[inline]
+    pub fn exclude_args<I, T>(&mut self, ids: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.excluded_args.extend(ids.into_iter().map(|i| i.into()));
+        self
+    }
+
+    /// Set key name (could contain keys delimiter) to store chain of invoked subcommands names as an array of strings
+    /// (e.g. `["user", "add"]` for `app user add`), so application could branch on it. The key is set only if any
+    /// subcommand is invoked. Default is `None`, subcommands will not be stored.
+    #[inline]
+    pub fn subcommand_key<S>(&mut self, key: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.subcommand_key = Some(key.into());
+        self
+    }
+
+    /// Convert relative paths to absolute ones (relative to current working directory at build stage) for arguments
+    /// with `clap::ValueHint::FilePath`, `clap::ValueHint::DirPath` or `clap::ValueHint::AnyPath` value hint.
+    /// Default is `false`.
+    #[inline]
+    pub fn absolute_paths(&mut self, on: bool) -> &mut Self {
+        self.absolute_paths = on;
+        self
+    }
+
+    /// Set mode of boolean flags handling. In [`BoolMode::Strict`] and [`BoolMode::Presence`] modes the flags values
+    /// are taken from `clap` matches as booleans and never typed according to `YAML` format. Default is
+    /// [`BoolMode::Typed`].
+    #[inline]
+    pub fn bool_flags(&mut self, mode: BoolMode) -> &mut Self {
+        self.bool_flags = mode;
+        self
+    }
+
+    /// Set mode of `ArgAction::Count` flags handling for all arguments (see [`CountMode`]). Default is
+    /// [`CountMode::Int`] or [`CountMode::Bool`] if boolean flags mode is [`BoolMode::Presence`] (see
+    /// [`ParserBuilder::bool_flags`]).
+    #[inline]
+    pub fn count_as(&mut self, mode: CountMode) -> &mut Self {
+        self.count_as = Some(mode);
+        self
+    }
+
+    /// Set mode of `ArgAction::Count` flag handling for argument with given id, it takes precedence over
+    /// [`ParserBuilder::count_as`].
+    #[inline]
+    pub fn arg_count_as<S>(&mut self, id: S, mode: CountMode) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.arg_count_as.push((id.into(), mode));
+        self
+    }
+
     /// Build and return command-line parser [`Parser`].
     ///
     /// # Errors
@@ -203,14 +386,29 @@ impl ParserBuilder {
     /// If any errors will occur during build then error will be returned. If `exit_on_error` was set to `true` then
     /// program will exit if `clap::Error` will occur. Otherwise [`Result`] with proper error will be returned.
     pub fn build(&mut self) -> Result<Parser> {
+        self.build_with(self.exit_on_error)
+    }
+
+    /// Build and return command-line parser [`Parser`]. Unlike [`ParserBuilder::build`] it never exits from program,
+    /// regardless of `exit_on_error` setting, so the caller could handle `clap::Error` (e.g. print help by itself).
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur during build then error will be returned.
+    #[inline]
+    pub fn build_no_exit(&mut self) -> Result<Parser> {
+        self.build_with(false)
+    }
+
+    fn build_with(&mut self, exit_on_error: bool) -> Result<Parser> {
         let result = self.get_matches();
-        let matches = if self.exit_on_error {
+        let matches = if exit_on_error {
             result.unwrap_or_else(|e| e.exit())
         } else {
             result.map_err(|e| Error::Clap(e, "Failed to get matches".into()))?
         };
 
-        let value = self.get_app_arguments(
+        let mut value = self.get_app_arguments(
             Value::with_case(self.case_sensitive),
             &self.command,
             &matches,
@@ -218,6 +416,15 @@ impl ParserBuilder {
             self.max_depth,
         )?;
 
+        if let Some(ref key) = self.subcommand_key {
+            let names = subcommands(&self.command, &matches);
+            if !names.is_empty() {
+                value
+                    .set_by_key_path_with_delim(key, &self.keys_delimiter, names)
+                    .map_err(|e| Error::Common(e, format!("Failed to set path: '{key}'").into()))?;
+            }
+        }
+
         Ok(Parser { value })
     }
 
@@ -244,20 +451,35 @@ impl ParserBuilder {
         };
 
         for arg in command.get_arguments().filter(|a| {
+            if self.excluded_args.contains(a.get_id().as_str()) {
+                return false;
+            }
             let source = matches.value_source(a.get_id().as_str());
             source == Some(ValueSource::CommandLine)
                 || source == Some(ValueSource::EnvVariable)
                 || self.use_defaults && source == Some(ValueSource::DefaultValue)
         }) {
+            let id = arg.get_id().as_str();
+            let key = self.renamed_args.get(id).map_or(id, String::as_str);
             value = self.set_value(
                 value,
                 matches,
-                &[&prefix, arg.get_id().as_str()].concat(),
+                &[&prefix, key].concat(),
                 &self.keys_delimiter,
                 arg,
             )?;
         }
 
+        if let Some(ref key) = self.unknown_args_key {
+            value = set_unknown_args(
+                value,
+                command,
+                matches,
+                &[prefix.as_str(), key].concat(),
+                &self.keys_delimiter,
+            )?;
+        }
+
         if depth == 0 {
             return Ok(value);
         }
@@ -285,9 +507,39 @@ impl ParserBuilder {
         delim: &str,
         arg: &Arg,
     ) -> Result<Value> {
+        if let Some(flag) = self.get_flag_value(matches, arg) {
+            value
+                .set_by_key_path_with_delim(path, delim, flag)
+                .map_err(|e| Error::Common(e, format!("Failed to set path: '{path}'").into()))?;
+            return Ok(value);
+        }
+
+        if let Some(v) = self
+            .use_arg_types
+            .then(|| typed_values(matches, arg))
+            .flatten()
+        {
+            let v = match v.len() {
+                1 if !is_arg_list(arg) => v.into_iter().next().unwrap_or_default(),
+                _ => JsonValue::Array(v),
+            };
+            value
+                .set_by_key_path_with_delim(path, delim, v)
+                .map_err(|e| Error::Common(e, format!("Failed to set path: '{path}'").into()))?;
+            return Ok(value);
+        }
+
         if let Some(v) = matches.get_raw(arg.get_id().as_str()) {
             let is_string = is_arg_string(arg);
+            let v: Vec<_> = if self.absolute_paths && is_arg_path(arg) {
+                let cwd = env::current_dir()
+                    .map_err(|e| Error::IO(e, "Failed to get current directory".into()))?;
+                v.map(|i| absolute_path(&cwd, i)).collect()
+            } else {
+                v.map(Cow::Borrowed).collect()
+            };
             let v: Vec<_> = v
+                .iter()
                 .map(|i| norm_arg_value(i, self.use_arg_types, is_string))
                 .collect();
 
@@ -306,6 +558,107 @@ impl ParserBuilder {
         }
         Ok(value)
     }
+
+    fn get_flag_value(&self, matches: &ArgMatches, arg: &Arg) -> Option<JsonValue> {
+        let id = arg.get_id().as_str();
+        match arg.get_action() {
+            ArgAction::SetTrue | ArgAction::SetFalse if self.bool_flags != BoolMode::Typed => {
+                matches.try_get_one::<bool>(id).ok()?.map(|f| (*f).into())
+            }
+            ArgAction::Count => {
+                let count = usize::from(*matches.try_get_one::<u8>(id).ok()??);
+                match self.get_count_mode(id) {
+                    CountMode::Int => None,
+                    CountMode::Bool => Some((count > 0).into()),
+                    CountMode::Level(levels) => levels
+                        .get(count.min(levels.len().saturating_sub(1)))
+                        .map(|l| l.as_str().into()),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn get_count_mode(&self, id: &str) -> &CountMode {
+        const BOOL: &CountMode = &CountMode::Bool;
+        const INT: &CountMode = &CountMode::Int;
+        self.arg_count_as
+            .iter()
+            .rev()
+            .find_map(|(i, m)| (i == id).then_some(m))
+            .or(self.count_as.as_ref())
+            .unwrap_or(match self.bool_flags {
+                BoolMode::Presence => BOOL,
+                _ => INT,
+            })
+    }
+}
+
+fn subcommands<'a>(mut command: &'a Command, mut matches: &'a ArgMatches) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    while let Some((name, m)) = matches.subcommand() {
+        let Some(c) = command.find_subcommand(name) else {
+            break;
+        };
+        names.push(c.get_name());
+        (command, matches) = (c, m);
+    }
+    names
+}
+
+fn set_unknown_args(
+    mut value: Value,
+    command: &Command,
+    matches: &ArgMatches,
+    path: &str,
+    delim: &str,
+) -> Result<Value> {
+    let Some((name, m)) = matches.subcommand() else {
+        return Ok(value);
+    };
+
+    if command.find_subcommand(name).is_some() {
+        return Ok(value);
+    }
+
+    let args: Vec<_> = std::iter::once(name.to_string())
+        .chain(
+            m.get_raw("")
+                .into_iter()
+                .flatten()
+                .map(|a| a.to_string_lossy().into_owned()),
+        )
+        .collect();
+    value
+        .set_by_key_path_with_delim(path, delim, args)
+        .map_err(|e| Error::Common(e, format!("Failed to set path: '{path}'").into()))?;
+    Ok(value)
+}
+
+fn typed_values(matches: &ArgMatches, arg: &Arg) -> Option<Vec<JsonValue>> {
+    fn get<T>(matches: &ArgMatches, id: &str) -> Option<Vec<JsonValue>>
+    where
+        T: Any + Clone + Send + Sync + Into<JsonValue> + 'static,
+    {
+        let values = matches.try_get_many::<T>(id).ok()??;
+        Some(values.cloned().map(Into::into).collect())
+    }
+
+    macro_rules! typed {
+        ($type_id:expr, $id:expr, $($t:ty),+) => {
+            $(if $type_id == value_parser!($t).type_id() {
+                return get::<$t>(matches, $id);
+            })+
+        };
+    }
+
+    let id = arg.get_id().as_str();
+    let type_id = arg.get_value_parser().type_id();
+    typed!(type_id, id, bool, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+    if type_id == value_parser!(String).type_id() && !arg.get_possible_values().is_empty() {
+        return get::<String>(matches, id);
+    }
+    None
 }
 
 fn is_arg_list(arg: &Arg) -> bool {
@@ -315,14 +668,29 @@ fn is_arg_list(arg: &Arg) -> bool {
     }
 }
 
+fn is_arg_path(arg: &Arg) -> bool {
+    matches!(
+        arg.get_value_hint(),
+        ValueHint::FilePath | ValueHint::DirPath | ValueHint::AnyPath
+    )
+}
+
+fn absolute_path<'a>(cwd: &Path, value: &'a OsStr) -> Cow<'a, OsStr> {
+    if value.is_empty() {
+        return Cow::Borrowed(value);
+    }
+    Cow::Owned(cwd.join(value).into_os_string())
+}
+
 fn is_arg_string(arg: &Arg) -> bool {
     let type_id = arg.get_value_parser().type_id();
     type_id == value_parser!(String).type_id()
         || type_id == value_parser!(OsString).type_id()
         || type_id == value_parser!(PathBuf).type_id()
+        || type_id != value_parser!(bool).type_id() && !arg.get_possible_values().is_empty()
 }
 
-fn norm_arg_value(value: &OsStr, use_type: bool, is_string: bool) -> CowString {
+fn norm_arg_value(value: &OsStr, use_type: bool, is_string: bool) -> CowString<'_> {
     fn quote(c: char) -> bool {
         c == '\'' || c == '"'
     }
@@ -0,0 +1,157 @@
+//! This module provide [Consul](https://developer.hashicorp.com/consul) KV store parser implementation. It pulls all
+//! keys of given subtree (prefix), decodes their values and builds nested [`Value`] structure, where the key levels
+//! are separated by `/`. For example, the following keys under `app/config` prefix:
+//!
+//! ```text
+//! app/config/logger/level = debug
+//! app/config/port = 8080
+//! ```
+//!
+//! will be represented as:
+//!
+//! ```yaml
+//! logger:
+//!   level: debug
+//! port: 8080
+//! ```
+//!
+//! The values are typed via `YAML` same way as in environment variables parser.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["consul"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::parsers::consul::ParserBuilder;
+//! use irx_config::ConfigBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .prefix("app/config")
+//!             .token("secret-token")
+//!             .ignore_missing(true)
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{AnyResult, Case, Parse, Value};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use derive_builder::Builder;
+use serde_yaml::Value as YamlValue;
+use std::time::Duration;
+use ureq::Agent;
+
+/// The default endpoint of Consul agent.
+pub const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:8500";
+
+/// The default timeout of request to Consul agent.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+const KEYS_DELIMITER: &str = "/";
+
+/// All errors for Consul KV parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to fetch keys with prefix: '{1}'")]
+    Request(#[source] ureq::Error, String),
+    #[error("Keys with prefix '{0}' are not found")]
+    Missing(String),
+    #[error("Failed to parse response for prefix: '{1}'")]
+    Response(#[source] serde_json::Error, String),
+    #[error("Failed to decode value of key: '{1}'")]
+    Decode(#[source] base64::DecodeError, String),
+    #[error("Value of key '{1}' is not UTF-8")]
+    Utf8(#[source] std::string::FromUtf8Error, String),
+    #[error("Failed parse as YAML value of key: '{1}'")]
+    ParseYaml(#[source] serde_yaml::Error, String),
+    #[error("{1} for keys: '{2}'")]
+    Value(#[source] crate::Error, &'static str, String),
+}
+
+/// The Consul KV parser implementation.
+#[derive(Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Parser {
+    /// Set endpoint of Consul agent. Default is [`DEFAULT_ENDPOINT`].
+    #[builder(default = "DEFAULT_ENDPOINT.to_string()")]
+    endpoint: String,
+    /// Set ACL token to be sent in `X-Consul-Token` header.
+    #[builder(default)]
+    token: Option<String>,
+    /// Set prefix of the keys subtree to be pulled. Default is the whole KV store.
+    #[builder(default)]
+    prefix: String,
+    /// If set to `true` then missing prefix will produce empty [`Value`], otherwise error will be returned. Default is
+    /// `false`.
+    #[builder(default)]
+    ignore_missing: bool,
+    /// Set timeout of request to Consul agent. Default is [`DEFAULT_TIMEOUT`].
+    #[builder(default = "DEFAULT_TIMEOUT")]
+    timeout: Duration,
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        let agent: Agent = Agent::config_builder()
+            .timeout_global(Some(self.timeout))
+            .build()
+            .into();
+        let prefix = self.prefix.trim_matches('/');
+        let mut request = agent.get(format!(
+            "{}/v1/kv/{prefix}?recurse=true",
+            self.endpoint.trim_end_matches('/')
+        ));
+        if let Some(ref token) = self.token {
+            request = request.header("X-Consul-Token", token);
+        }
+
+        let data = match request.call() {
+            Ok(mut r) => r
+                .body_mut()
+                .read_to_string()
+                .map_err(|e| Error::Request(e, prefix.into()))?,
+            Err(ureq::Error::StatusCode(404)) if self.ignore_missing => return Ok(Value::default()),
+            Err(ureq::Error::StatusCode(404)) => return Err(Error::Missing(prefix.into()).into()),
+            Err(e) => return Err(Error::Request(e, prefix.into()).into()),
+        };
+
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_str(&data).map_err(|e| Error::Response(e, prefix.into()))?;
+        let mut result = Value::default();
+        for entry in &entries {
+            let (Some(key), Some(data)) = (entry["Key"].as_str(), entry["Value"].as_str()) else {
+                continue;
+            };
+            let path = key.strip_prefix(prefix).unwrap_or(key).trim_matches('/');
+            if path.is_empty() {
+                continue;
+            }
+
+            let data = STANDARD
+                .decode(data)
+                .map_err(|e| Error::Decode(e, key.into()))?;
+            let data = String::from_utf8(data).map_err(|e| Error::Utf8(e, key.into()))?;
+            let val: YamlValue =
+                serde_yaml::from_str(&data).map_err(|e| Error::ParseYaml(e, key.into()))?;
+            result
+                .set_by_key_path_with_delim(path, KEYS_DELIMITER, val)
+                .map_err(|e| Error::Value(e, "Failed to set value", key.into()))?;
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    fn is_remote(&self, _value: &Value) -> bool {
+        true
+    }
+}
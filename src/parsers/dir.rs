@@ -0,0 +1,171 @@
+//! This module provide directory (e.g. `conf.d`) parser implementation. It loads all files of the directory with
+//! given loader (see [`Load`]) and merges them in deterministic order (see [`Order`]), the last file wins. The order
+//! does not depend on file system iteration order, the ties are always broken by byte-wise comparison of file names.
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::{dir::{Order, ParserBuilder}, json::LoadJson};
+//!
+//! // `10-base.json` is loaded before `20-site.json`, so values from `20-site.json` win.
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::<LoadJson>::default()
+//!             .directory("/etc/app/conf.d")
+//!             .extension("json")
+//!             .order(Order::NumericPrefix)
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{try_open_file, Load},
+    AnyResult, Case, Parse, Value,
+};
+use derive_builder::Builder;
+use std::{
+    cmp::Ordering,
+    fs,
+    io::{BufReader, Error as IoError},
+    path::{Path, PathBuf},
+};
+
+/// All errors for directory parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to read directory: '{1}'")]
+    ReadDir(#[source] IoError, PathBuf),
+    #[error("Failed to load file: '{1}'")]
+    Load(#[source] crate::AnyError, PathBuf),
+}
+
+/// The order in which files of the directory are merged, the last file wins.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Order {
+    /// Files are ordered by names.
+    #[default]
+    FileName,
+    /// Files are ordered by numeric prefix of names (e.g. `2-site.json` is before `10-local.json`), files without
+    /// numeric prefix are after numbered ones.
+    NumericPrefix,
+    /// Files which names are in the given list are ordered by their positions in the list and are after all other
+    /// files, so the last listed file has the highest priority.
+    Priority(Vec<String>),
+}
+
+impl Order {
+    fn compare(&self, left: &str, right: &str) -> Ordering {
+        match self {
+            Self::FileName => Ordering::Equal,
+            Self::NumericPrefix => {
+                let prefix = |n: &str| {
+                    let digits = n.len() - n.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+                    n[..digits].parse::<u128>().ok()
+                };
+                match (prefix(left), prefix(right)) {
+                    (Some(l), Some(r)) => l.cmp(&r),
+                    (l, r) => r.is_some().cmp(&l.is_some()),
+                }
+            }
+            Self::Priority(names) => {
+                let position = |n: &str| names.iter().position(|p| p == n);
+                position(left).cmp(&position(right))
+            }
+        }
+        .then_with(|| left.cmp(right))
+    }
+}
+
+/// The directory parser implementation.
+#[derive(Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Parser<L: Load + Default> {
+    /// Set directory to be parsed.
+    directory: PathBuf,
+    /// Set extension of the files to be parsed (without leading dot). Default is all files.
+    #[builder(default)]
+    extension: Option<String>,
+    /// Set order in which files are merged (see [`Order`]). Default is [`Order::FileName`].
+    #[builder(default)]
+    order: Order,
+    /// If directory does not exists do not try to load it. The default [`Value`] will be returned. Default is
+    /// `false`.
+    #[builder(default)]
+    ignore_missing: bool,
+    /// Set the loader structure which implements [`Load`] trait.
+    #[builder(default)]
+    loader: L,
+    #[builder(setter(skip))]
+    last_paths: Vec<PathBuf>,
+}
+
+impl<L: Load + Default> Parser<L> {
+    /// Get paths of the files of the directory in order in which they will be merged.
+    ///
+    /// # Errors
+    ///
+    /// If directory could not be read then error will be returned.
+    pub fn ordered_paths(&self) -> Result<Vec<PathBuf>, Error> {
+        let entries =
+            fs::read_dir(&self.directory).map_err(|e| Error::ReadDir(e, self.directory.clone()))?;
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| Error::ReadDir(e, self.directory.clone()))?
+                .path();
+            let is_matched = self
+                .extension
+                .as_ref()
+                .map_or(true, |e| path.extension().is_some_and(|x| x == e.as_str()));
+            if path.is_file() && is_matched {
+                files.push((file_name(&path), path));
+            }
+        }
+
+        files.sort_by(|(l, _), (r, _)| self.order.compare(l, r));
+        Ok(files.into_iter().map(|(_, p)| p).collect())
+    }
+}
+
+impl<L: Load + Default> Case for Parser<L> {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.loader.is_case_sensitive()
+    }
+}
+
+impl<L: Load + Default> Parse for Parser<L> {
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        let case_on = self.loader.is_case_sensitive();
+        let mut result = Value::with_case(case_on);
+        self.last_paths.clear();
+        if self.ignore_missing && !self.directory.is_dir() {
+            return Ok(result);
+        }
+
+        for path in self.ordered_paths()? {
+            let file = try_open_file(&path)?;
+            let value = self
+                .loader
+                .load(BufReader::new(file))
+                .map_err(|e| Error::Load(e, path.clone()))?;
+            result.merge_from(&value);
+            self.last_paths.push(path);
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.last_paths.clone()
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
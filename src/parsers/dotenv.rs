@@ -0,0 +1,124 @@
+//! This module provide `.env` files parser implementation. The variables are handled in the same way as by
+//! [`crate::parsers::env`] parser: only variables with given prefix will be taken, the prefix will be removed from
+//! names and the rest will be splitted to nested keys by `__` delimiter. The unquoted values will be typed according
+//! to `YAML` format, the quoted values will be always strings. The process environment is not touched.
+//!
+//! The empty lines and lines started with `#` will be skipped, optional `export` keyword will be ignored. The text
+//! after ` #` in unquoted values is treated as a comment.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["dotenv"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::dotenv::{LoadDotenv, ParserBuilder};
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_path(".env")
+//!             .ignore_missing_file(true)
+//!             .loader(LoadDotenv::default().prefix("APP_"))
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{
+        env::{parse_env_lines, vars_to_value},
+        FileParserBuilder, Load,
+    },
+    AnyResult, Case, Value,
+};
+use std::io::{Error as IoError, Read};
+
+/// All errors for `.env` files parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed read data to buffer")]
+    Read(#[source] IoError),
+    #[error("Failed to parse .env file")]
+    Env(#[source] crate::parsers::env::Error),
+}
+
+/// Builder for `.env` files parser.
+pub type ParserBuilder = FileParserBuilder<LoadDotenv>;
+
+/// Implements [`Load`] trait for `.env` files parser.
+#[derive(Clone)]
+pub struct LoadDotenv {
+    prefix: String,
+    env_keys_delimiter: String,
+    case_sensitive: bool,
+}
+
+impl LoadDotenv {
+    /// Set prefix for variables to be parsed. Default is empty prefix, all variables will be parsed.
+    #[inline]
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set delimiter used to separate keys levels in variables names. Default is `__`.
+    #[inline]
+    pub fn env_keys_delimiter<S: Into<String>>(mut self, delim: S) -> Self {
+        self.env_keys_delimiter = delim.into();
+        self
+    }
+
+    /// Set parser's case sensitivity for key names. Default is `false`.
+    #[inline]
+    pub fn case_sensitive(mut self, on: bool) -> Self {
+        self.case_sensitive = on;
+        self
+    }
+}
+
+impl Default for LoadDotenv {
+    fn default() -> Self {
+        Self {
+            prefix: Default::default(),
+            env_keys_delimiter: "__".to_string(),
+            case_sensitive: false,
+        }
+    }
+}
+
+impl Case for LoadDotenv {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+impl Load for LoadDotenv {
+    fn load(&mut self, mut reader: impl Read) -> AnyResult<Value> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data).map_err(Error::Read)?;
+        let vars = parse_env_lines(&data).into_iter().map(|(k, v)| {
+            if v.starts_with('\'') {
+                return (k, v);
+            }
+            match v.split_once(" #") {
+                Some((v, _)) => (k, v.trim_end().to_string()),
+                None => (k, v),
+            }
+        });
+        Ok(vars_to_value(
+            vars,
+            &self.prefix,
+            &self.env_keys_delimiter,
+            self.case_sensitive,
+        )
+        .map_err(Error::Env)?)
+    }
+}
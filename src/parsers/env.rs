@@ -1,6 +1,7 @@
 //! This module provide environment variables parser implementation.
 //!
-//! The value of each environment variable parsed will be typed according to `YAML` format.
+//! The value of each environment variable parsed will be typed according to `YAML` format or, optionally, `JSON`
+//! format (see [`ParserBuilder::json_values`]).
 //!
 //! To enable that parser  one has to add the following to Cargo.toml:
 //!
@@ -24,9 +25,45 @@
 //!     )
 //!     .load()?;
 //! ```
+//!
+//! The variables with several prefixes could be parsed by one parser, each prefix could be mounted under its own key
+//! path (see [`Prefix::mount`]):
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::env::{ParserBuilder, Prefix};
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .prefix("APP_")
+//!             .prefix(Prefix::new("MYLIB_").mount("libs:mylib"))
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+//!
+//! The well-known variables without prefix could be mapped to key paths explicitly (see
+//! [`ParserBuilder::map_var`]):
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::env::ParserBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_prefix("APP_")
+//!             .map_var("PORT", "server:port")
+//!             .map_var("DATABASE_URL", "db:url")
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
 
 use crate::{AnyResult, Case, CowString, Parse, Value, DEFAULT_KEYS_SEPARATOR};
 use derive_builder::Builder;
+use serde_json::Value as JsonValue;
 use serde_yaml::Value as YamlValue;
 use std::env;
 
@@ -40,6 +77,46 @@ pub enum Error {
     Value(#[source] crate::Error, &'static str, String),
 }
 
+/// The additional prefix of environment variables to be parsed (see [`ParserBuilder::prefix`]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Prefix {
+    prefix: String,
+    mount: Option<String>,
+}
+
+impl Prefix {
+    /// Create [`Prefix`] with given prefix of environment variables names.
+    #[inline]
+    pub fn new<S: Into<String>>(prefix: S) -> Self {
+        Self {
+            prefix: prefix.into(),
+            mount: None,
+        }
+    }
+
+    /// Mount values of the variables under given key path (with keys delimiter, see
+    /// [`ParserBuilder::keys_delimiter`]). Default is the root.
+    #[inline]
+    pub fn mount<S: Into<String>>(mut self, path: S) -> Self {
+        self.mount = Some(path.into());
+        self
+    }
+}
+
+impl From<&str> for Prefix {
+    #[inline]
+    fn from(prefix: &str) -> Self {
+        Self::new(prefix)
+    }
+}
+
+impl From<String> for Prefix {
+    #[inline]
+    fn from(prefix: String) -> Self {
+        Self::new(prefix)
+    }
+}
+
 /// The environment variable parser implementation.
 #[derive(Builder, Default)]
 #[builder(setter(into, strip_option), default)]
@@ -48,6 +125,15 @@ pub struct Parser {
     default_prefix: String,
     /// Set prefix option name which could be used to get prefix value from previous parsing [`Value`] results.
     prefix_option: Option<String>,
+    /// Add prefix of environment variables to be parsed in addition to default (or option) prefix. The values of the
+    /// variables with default prefix have highest priority, then additional prefixes in the order of adding. If any
+    /// additional prefix is added or any variable is mapped (see [`ParserBuilder::map_var`]), then empty default
+    /// prefix is ignored.
+    #[builder(setter(each(name = "prefix", into)))]
+    prefixes: Vec<Prefix>,
+    /// Map environment variables with given names to given key paths (see [`ParserBuilder::map_var`]).
+    #[builder(setter(custom))]
+    mapped_vars: Vec<(String, String)>,
     /// Set delimiter used to separate keys levels in prefix value. Default is [`DEFAULT_KEYS_SEPARATOR`].
     #[builder(default = "DEFAULT_KEYS_SEPARATOR.to_string()")]
     keys_delimiter: String,
@@ -56,10 +142,31 @@ pub struct Parser {
     env_keys_delimiter: String,
     /// Set parser's case sensitivity for key names.
     case_sensitive: bool,
+    /// If set to `true` then values of environment variables will be typed according to `JSON` format instead of
+    /// `YAML`, so structured payloads (e.g. `APP_FEATURES='{"a":true}'`) keep exact `JSON` semantics (e.g. `on` is
+    /// not a boolean). The values which are not valid `JSON` will be taken as strings. Default is `false`.
+    json_values: bool,
     #[builder(setter(skip))]
     value: Option<Value>,
 }
 
+impl ParserBuilder {
+    /// Map environment variable with given name (e.g. `DATABASE_URL`) to given key path (with keys delimiter, see
+    /// [`ParserBuilder::keys_delimiter`]), regardless of prefixes. The values of mapped variables have highest
+    /// priority. If any variable is mapped, then empty default prefix is ignored, so only mapped variables will be
+    /// parsed unless some prefix is set.
+    pub fn map_var<N, P>(&mut self, name: N, path: P) -> &mut Self
+    where
+        N: Into<String>,
+        P: Into<String>,
+    {
+        self.mapped_vars
+            .get_or_insert_with(Vec::new)
+            .push((name.into(), path.into()));
+        self
+    }
+}
+
 impl Case for Parser {
     #[inline]
     fn is_case_sensitive(&self) -> bool {
@@ -82,26 +189,178 @@ impl Parse for Parser {
         }
         .unwrap_or(CowString::Borrowed(&self.default_prefix));
 
+        let vars: Vec<_> = env::vars_os()
+            .map(|(k, v)| {
+                (
+                    k.to_string_lossy().into_owned(),
+                    v.to_string_lossy().into_owned(),
+                )
+            })
+            .collect();
         let case_on = self.is_case_sensitive();
-        let prefix = crate::normalize_case(&prefix, case_on);
-
         let mut result = Value::with_case(case_on);
-        for (k, v) in env::vars_os().filter_map(|(k, v)| {
-            let k = k.to_string_lossy();
-            let norm_key = crate::normalize_case(&k, case_on);
-            if !norm_key.starts_with(prefix.as_ref()) {
-                return None;
+        for p in self.prefixes.iter().rev() {
+            let mut value = vars_to_value_with(
+                vars.iter().cloned(),
+                &p.prefix,
+                &self.env_keys_delimiter,
+                case_on,
+                self.json_values,
+            )?;
+            if let Some(ref path) = p.mount {
+                let mut mounted = Value::with_case(case_on);
+                mounted
+                    .set_by_key_path_with_delim(path, &self.keys_delimiter, value)
+                    .map_err(|e| Error::Value(e, "Failed to mount prefix", path.into()))?;
+                value = mounted;
             }
-            Some((norm_key.into_owned(), v.to_string_lossy().to_string()))
-        }) {
-            let path = k.trim_start_matches(prefix.as_ref());
-            let val: YamlValue = serde_yaml::from_str(&v).map_err(|e| Error::ParseYaml(e, v))?;
-            result
-                .set_by_key_path_with_delim(path, &self.env_keys_delimiter, val)
-                .map_err(|e| Error::Value(e, "Failed to set value", path.into()))?;
+            result = result.merge(&value);
+        }
+        if !prefix.is_empty() || (self.prefixes.is_empty() && self.mapped_vars.is_empty()) {
+            result = result.merge(&vars_to_value_with(
+                vars.iter().cloned(),
+                &prefix,
+                &self.env_keys_delimiter,
+                case_on,
+                self.json_values,
+            )?);
+        }
+        for (name, path) in &self.mapped_vars {
+            let name = crate::normalize_case(name, case_on);
+            let Some((_, v)) = vars
+                .iter()
+                .find(|(k, _)| crate::normalize_case(k, case_on) == name)
+            else {
+                continue;
+            };
+            set_var(&mut result, path, &self.keys_delimiter, v, self.json_values)?;
         }
 
         self.value = Some(result.clone());
         Ok(result)
     }
+
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        self.prefix_option.iter().cloned().collect()
+    }
+}
+
+/// Convert variables which names start with given prefix to [`Value`] structure. The prefix will be removed from
+/// variables names and the rest will be splitted to nested keys by given delimiter. The variables values will be
+/// typed according to `YAML` format.
+#[cfg(any(feature = "systemd", feature = "dotenv"))]
+#[inline]
+pub(crate) fn vars_to_value<I>(
+    vars: I,
+    prefix: &str,
+    delim: &str,
+    case_on: bool,
+) -> Result<Value, Error>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    vars_to_value_with(vars, prefix, delim, case_on, false)
+}
+
+/// Convert variables which names start with given prefix to [`Value`] structure (see `vars_to_value`). The variables
+/// values will be typed according to `JSON` format if `json` is `true`, otherwise according to `YAML` format.
+fn vars_to_value_with<I>(
+    vars: I,
+    prefix: &str,
+    delim: &str,
+    case_on: bool,
+    json: bool,
+) -> Result<Value, Error>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let prefix = crate::normalize_case(prefix, case_on);
+    let mut result = Value::with_case(case_on);
+    for (k, v) in vars.into_iter().filter_map(|(k, v)| {
+        let norm_key = crate::normalize_case(&k, case_on);
+        if !norm_key.starts_with(prefix.as_ref()) {
+            return None;
+        }
+        Some((norm_key.into_owned(), v))
+    }) {
+        let path = k.trim_start_matches(prefix.as_ref());
+        set_var(&mut result, path, delim, &v, json)?;
+    }
+    Ok(result)
+}
+
+fn set_var(value: &mut Value, path: &str, delim: &str, var: &str, json: bool) -> Result<(), Error> {
+    let result = if json {
+        let val = serde_json::from_str::<JsonValue>(var)
+            .unwrap_or_else(|_| JsonValue::String(var.to_string()));
+        value.set_by_key_path_with_delim(path, delim, val)
+    } else {
+        let val: YamlValue =
+            serde_yaml::from_str(var).map_err(|e| Error::ParseYaml(e, var.to_string()))?;
+        value.set_by_key_path_with_delim(path, delim, val)
+    };
+    result
+        .map(|_| ())
+        .map_err(|e| Error::Value(e, "Failed to set value", path.into()))
+}
+
+/// Parse `KEY=VALUE` lines. The empty lines and lines started with `#` or `;` will be skipped, optional `export`
+/// keyword will be ignored, line ending with `\` will be continued on the next line. The quoted values will be
+/// returned as quoted `YAML` strings, so they will not be typed.
+#[cfg(any(feature = "systemd", feature = "dotenv"))]
+pub(crate) fn parse_env_lines(data: &str) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut lines = data.lines();
+    while let Some(line) = lines.next() {
+        let mut line = line.trim().to_string();
+        while line.ends_with('\\') {
+            line.pop();
+            match lines.next() {
+                Some(next) => line.push_str(next.trim()),
+                None => break,
+            }
+        }
+
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(&line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        result.push((key.trim().to_string(), norm_value(value.trim())));
+    }
+    result
+}
+
+#[cfg(any(feature = "systemd", feature = "dotenv"))]
+fn norm_value(value: &str) -> String {
+    let unquoted = if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let mut result = String::new();
+        let mut chars = value[1..value.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            match (c, c == '\\') {
+                (_, true) => match chars.next() {
+                    Some('n') => result.push('\n'),
+                    Some('t') => result.push('\t'),
+                    Some(n) => result.push(n),
+                    None => result.push(c),
+                },
+                _ => result.push(c),
+            }
+        }
+        result
+    } else if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        value[1..value.len() - 1].to_string()
+    } else {
+        return if value.is_empty() {
+            "''".to_string()
+        } else {
+            value.to_string()
+        };
+    };
+    ["'", &unquoted.replace('\'', "''"), "'"].concat()
 }
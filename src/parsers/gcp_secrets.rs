@@ -0,0 +1,251 @@
+//! This module provide [Google Secret Manager](https://cloud.google.com/secret-manager) parser implementation. The
+//! given secrets (names and versions) will be resolved and placed to given key paths.
+//!
+//! The resolved values will be stored as strings and sealed (see [`Parse::sealed_keys`]), so they will be obfuscated
+//! during display/debugging output regardless of sealed suffix of [`crate::ConfigBuilder`]. If sealed suffix was set
+//! (see [`ParserBuilder::sealed_suffix`]) then it will be appended to the last key of each key path.
+//!
+//! If access token was not set (see [`ParserBuilder::access_token`]) then it will be requested from `GCE` metadata
+//! server for default service account of the instance.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["gcp-secrets"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::gcp_secrets::{ParserBuilder, Secret};
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .project("my-project")
+//!             .secret(Secret::new("db-password", "db:password"))
+//!             .secret(Secret::with_version("api-token", "3", "auth:token"))
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{AnyResult, Case, Parse, Value, DEFAULT_KEYS_SEPARATOR};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use derive_builder::Builder;
+use std::time::Duration;
+use ureq::Agent;
+
+/// The default endpoint of Secret Manager API.
+pub const DEFAULT_ENDPOINT: &str = "https://secretmanager.googleapis.com";
+
+/// The default endpoint of `GCE` metadata server.
+pub const DEFAULT_METADATA_ENDPOINT: &str = "http://metadata.google.internal";
+
+/// The default timeout for each request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The default version of secrets.
+pub const LATEST_VERSION: &str = "latest";
+
+/// All errors for Google Secret Manager parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to get access token")]
+    Token(#[source] ureq::Error),
+    #[error("Failed to access secret: '{1}'")]
+    Request(#[source] ureq::Error, String),
+    #[error("Failed to parse response for: '{1}'")]
+    Response(#[source] serde_json::Error, String),
+    #[error("Response for '{0}' has no data")]
+    NoData(String),
+    #[error("Failed to decode secret: '{1}'")]
+    Decode(#[source] base64::DecodeError, String),
+    #[error("Secret '{1}' is not UTF-8")]
+    Utf8(#[source] std::string::FromUtf8Error, String),
+    #[error("{1} for keys: '{2}'")]
+    Value(#[source] crate::Error, &'static str, String),
+}
+
+/// The secret to be resolved from Secret Manager.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Secret {
+    name: String,
+    version: String,
+    key_path: String,
+}
+
+impl Secret {
+    /// Create [`Secret`] for latest version of given secret name. The name could be short (then project will be taken
+    /// from [`ParserBuilder::project`]) or full: `projects/<project>/secrets/<name>`. The resolved secret will be
+    /// placed to given key path.
+    #[inline]
+    pub fn new<N, P>(name: N, key_path: P) -> Self
+    where
+        N: Into<String>,
+        P: Into<String>,
+    {
+        Self::with_version(name, LATEST_VERSION, key_path)
+    }
+
+    /// Same as [`Secret::new`], but for given version of the secret.
+    #[inline]
+    pub fn with_version<N, V, P>(name: N, version: V, key_path: P) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            key_path: key_path.into(),
+        }
+    }
+
+    fn resource(&self, project: &str) -> String {
+        if self.name.starts_with("projects/") {
+            format!("{}/versions/{}", self.name, self.version)
+        } else {
+            format!(
+                "projects/{project}/secrets/{}/versions/{}",
+                self.name, self.version
+            )
+        }
+    }
+}
+
+/// The Google Secret Manager parser implementation.
+#[derive(Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Parser {
+    /// Add secret to be resolved from Secret Manager.
+    #[builder(default, setter(each(name = "secret")))]
+    secrets: Vec<Secret>,
+    /// Set project of secrets with short names.
+    #[builder(default)]
+    project: String,
+    /// Set `OAuth2` access token. If not set then it will be requested from `GCE` metadata server.
+    #[builder(default)]
+    access_token: Option<String>,
+    /// Set suffix to be appended to the last key of each secret key path. The secrets are sealed regardless of it.
+    #[builder(default)]
+    sealed_suffix: String,
+    /// Set delimiter used to separate keys levels in secret key path. Default is [`DEFAULT_KEYS_SEPARATOR`].
+    #[builder(default = "DEFAULT_KEYS_SEPARATOR.to_string()")]
+    keys_delimiter: String,
+    /// If secret (or its version) does not exists then skip it. Default is `false`.
+    #[builder(default)]
+    ignore_missing: bool,
+    /// Set endpoint of Secret Manager API. Default is [`DEFAULT_ENDPOINT`].
+    #[builder(default = "DEFAULT_ENDPOINT.to_string()")]
+    endpoint: String,
+    /// Set endpoint of `GCE` metadata server. Default is [`DEFAULT_METADATA_ENDPOINT`].
+    #[builder(default = "DEFAULT_METADATA_ENDPOINT.to_string()")]
+    metadata_endpoint: String,
+    /// Set timeout for each request. Default is [`DEFAULT_TIMEOUT`].
+    #[builder(default = "DEFAULT_TIMEOUT")]
+    timeout: Duration,
+    #[builder(setter(skip))]
+    sealed: Vec<Vec<String>>,
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        let mut result = Value::default();
+        self.sealed.clear();
+        if self.secrets.is_empty() {
+            return Ok(result);
+        }
+
+        let agent: Agent = Agent::config_builder()
+            .timeout_global(Some(self.timeout))
+            .build()
+            .into();
+        let token = match self.access_token {
+            Some(ref t) => t.clone(),
+            None => self.fetch_token(&agent)?,
+        };
+
+        for secret in &self.secrets {
+            let resource = secret.resource(&self.project);
+            let data = match agent
+                .get(format!("{}/v1/{resource}:access", self.endpoint))
+                .header("Authorization", &format!("Bearer {token}"))
+                .call()
+            {
+                Ok(mut r) => r
+                    .body_mut()
+                    .read_to_string()
+                    .map_err(|e| Error::Request(e, resource.clone()))?,
+                Err(ureq::Error::StatusCode(404)) if self.ignore_missing => continue,
+                Err(e) => return Err(Error::Request(e, resource).into()),
+            };
+
+            let response: serde_json::Value =
+                serde_json::from_str(&data).map_err(|e| Error::Response(e, resource.clone()))?;
+            let data = response["payload"]["data"]
+                .as_str()
+                .ok_or_else(|| Error::NoData(resource.clone()))?;
+            let data = STANDARD
+                .decode(data)
+                .map_err(|e| Error::Decode(e, resource.clone()))?;
+            let data = String::from_utf8(data).map_err(|e| Error::Utf8(e, resource))?;
+
+            let path = [secret.key_path.as_str(), &self.sealed_suffix].concat();
+            result
+                .set_by_key_path_with_delim(&path, &self.keys_delimiter, data)
+                .map_err(|e| Error::Value(e, "Failed to set value", path.clone()))?;
+
+            // The suffix is stripped only if sealed suffix of config is the same, so both variants are sealed.
+            self.sealed
+                .push(path.split(&self.keys_delimiter).map(String::from).collect());
+            if !self.sealed_suffix.is_empty() {
+                self.sealed.push(
+                    secret
+                        .key_path
+                        .split(&self.keys_delimiter)
+                        .map(String::from)
+                        .collect(),
+                );
+            }
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    fn sealed_keys(&self) -> Vec<Vec<String>> {
+        self.sealed.clone()
+    }
+
+    #[inline]
+    fn is_remote(&self, _value: &Value) -> bool {
+        true
+    }
+}
+
+impl Parser {
+    fn fetch_token(&self, agent: &Agent) -> AnyResult<String> {
+        let url = format!(
+            "{}/computeMetadata/v1/instance/service-accounts/default/token",
+            self.metadata_endpoint
+        );
+        let data = agent
+            .get(&url)
+            .header("Metadata-Flavor", "Google")
+            .call()
+            .and_then(|mut r| r.body_mut().read_to_string())
+            .map_err(Error::Token)?;
+        let response: serde_json::Value =
+            serde_json::from_str(&data).map_err(|e| Error::Response(e, url.clone()))?;
+        Ok(response["access_token"]
+            .as_str()
+            .ok_or(Error::NoData(url))?
+            .to_string())
+    }
+}
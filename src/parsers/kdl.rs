@@ -0,0 +1,580 @@
+//! This module provide [KDL](https://kdl.dev/) documents parser implementation. Both KDL v1 and v2 syntaxes are
+//! accepted. The nodes are mapped to nested keys in the following way:
+//!
+//! * node with single argument is mapped to scalar value;
+//! * node with several arguments is mapped to array;
+//! * node without arguments, properties and children is mapped to `null`;
+//! * properties and children of node are mapped to dictionary, the arguments of such node (if any) are stored under
+//!   arguments key (see [`DEFAULT_ARGS_KEY`]);
+//! * repeated nodes are merged, the last one wins.
+//!
+//! So the following document:
+//!
+//! ```kdl
+//! server host="localhost" port=8080 {
+//!     tls #true
+//! }
+//! hosts "a.local" "b.local"
+//! ```
+//!
+//! will be represented as:
+//!
+//! ```yaml
+//! server:
+//!   host: localhost
+//!   port: 8080
+//!   tls: true
+//! hosts:
+//!   - a.local
+//!   - b.local
+//! ```
+//!
+//! The type annotations are ignored. The `#inf`, `#-inf` and `#nan` keywords are rejected, since they could not be
+//! represented in configuration data.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["kdl"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::kdl::ParserBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_path("config.kdl")
+//!             .path_option("config")
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{FileParserBuilder, Load},
+    AnyResult, Case, Value,
+};
+use serde_json::{Map, Number, Value as InnerValue};
+use std::io::{Error as IoError, Read};
+
+/// The default key to store arguments of node which has properties or children.
+pub const DEFAULT_ARGS_KEY: &str = "_args";
+
+const NON_IDENT: &str = "\\/(){}[]<>;=,\"#";
+
+/// All errors for `KDL` parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed read data to buffer")]
+    Read(#[source] IoError),
+    #[error("Failed parse KDL at line {1}: {0}")]
+    ParseKdl(&'static str, usize),
+    #[error("Failed to convert KDL document")]
+    Value(#[source] crate::Error),
+}
+
+/// Builder for `KDL` parser.
+pub type ParserBuilder = FileParserBuilder<LoadKdl>;
+
+/// Implements [`Load`] trait for `KDL` parser.
+#[derive(Clone)]
+pub struct LoadKdl {
+    args_key: String,
+}
+
+impl LoadKdl {
+    /// Set key to store arguments of node which has properties or children. Default is [`DEFAULT_ARGS_KEY`].
+    #[inline]
+    pub fn args_key<S: Into<String>>(mut self, key: S) -> Self {
+        self.args_key = key.into();
+        self
+    }
+}
+
+impl Default for LoadKdl {
+    fn default() -> Self {
+        Self {
+            args_key: DEFAULT_ARGS_KEY.to_string(),
+        }
+    }
+}
+
+impl Case for LoadKdl {}
+
+impl Load for LoadKdl {
+    fn load(&mut self, mut reader: impl Read) -> AnyResult<Value> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data).map_err(Error::Read)?;
+        let mut document = Document {
+            chars: data.trim_start_matches('\u{FEFF}').chars().collect(),
+            pos: 0,
+            line: 1,
+            args_key: &self.args_key,
+        };
+        let nodes = document.nodes()?;
+        if document.peek().is_some() {
+            return Err(document.error("unexpected '}'").into());
+        }
+        Ok(Value::try_from(InnerValue::Object(nodes)).map_err(Error::Value)?)
+    }
+}
+
+struct Document<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    args_key: &'a str,
+}
+
+impl Document<'_> {
+    fn nodes(&mut self) -> Result<Map<String, InnerValue>, Error> {
+        let mut result = Map::new();
+        loop {
+            self.skip_line_space()?;
+            if matches!(self.peek(), None | Some('}')) {
+                return Ok(result);
+            }
+
+            let discard = self.skip_slashdash()?;
+            let (name, value) = self.node()?;
+            if !discard {
+                merge(&mut result, name, value);
+            }
+        }
+    }
+
+    fn node(&mut self) -> Result<(String, InnerValue), Error> {
+        self.skip_annotation()?;
+        let (InnerValue::String(name), true) = self.token()? else {
+            return Err(self.error("node name is expected"));
+        };
+
+        let mut args = Vec::new();
+        let mut props = Map::new();
+        let mut children = None;
+        loop {
+            let spaced = self.skip_node_space()?;
+            match self.peek() {
+                None | Some('}') => break,
+                Some('\n' | ';') => {
+                    self.next();
+                    break;
+                }
+                Some('/') if self.is_at("//") => break,
+                _ if !spaced => return Err(self.error("whitespace is expected")),
+                _ => (),
+            }
+
+            let discard = self.skip_slashdash()?;
+            if Some('{') == self.peek() {
+                self.next();
+                let nodes = self.nodes()?;
+                if Some('}') != self.next() {
+                    return Err(self.error("'}' is expected"));
+                }
+                if !discard {
+                    children = Some(nodes);
+                }
+                continue;
+            }
+
+            self.skip_annotation()?;
+            let (value, is_string) = self.token()?;
+            match value {
+                InnerValue::String(key) if is_string && self.skip_equals()? => {
+                    self.skip_annotation()?;
+                    let (value, _) = self.token()?;
+                    if !discard {
+                        props.insert(key, value);
+                    }
+                }
+                value if !discard => args.push(value),
+                _ => (),
+            }
+        }
+
+        let args = match args.len() {
+            0 => None,
+            1 => args.pop(),
+            _ => Some(InnerValue::Array(args)),
+        };
+        if props.is_empty() && children.is_none() {
+            return Ok((name, args.unwrap_or(InnerValue::Null)));
+        }
+
+        let mut map = props;
+        for (key, value) in children.unwrap_or_default() {
+            merge(&mut map, key, value);
+        }
+        if let Some(args) = args {
+            map.insert(self.args_key.to_string(), args);
+        }
+        Ok((name, InnerValue::Object(map)))
+    }
+
+    /// Return value and `true` if it could be used as a name (node name or property key).
+    fn token(&mut self) -> Result<(InnerValue, bool), Error> {
+        match self.peek() {
+            Some('"') => Ok((InnerValue::String(self.quoted()?), true)),
+            Some('#') if matches!(self.peek_at(1), Some('#' | '"')) => {
+                Ok((InnerValue::String(self.raw()?), true))
+            }
+            Some('r') if matches!(self.peek_at(1), Some('#' | '"')) => {
+                self.next();
+                Ok((InnerValue::String(self.raw()?), true))
+            }
+            Some('#') => {
+                self.next();
+                let word = self.word();
+                let value = keyword(&word).ok_or_else(|| self.error("unknown keyword"))?;
+                Ok((value, false))
+            }
+            Some(c) if c.is_ascii_digit() => Ok((self.number()?, false)),
+            Some('+' | '-') if self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) => {
+                Ok((self.number()?, false))
+            }
+            Some(_) => {
+                let word = self.word();
+                if word.is_empty() {
+                    return Err(self.error("value is expected"));
+                }
+                match word.as_str() {
+                    "true" | "false" | "null" => Ok((keyword(&word).unwrap_or_default(), false)),
+                    _ => Ok((InnerValue::String(word), true)),
+                }
+            }
+            None => Err(self.error("value is expected")),
+        }
+    }
+
+    fn word(&mut self) -> String {
+        let mut result = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || NON_IDENT.contains(c) {
+                break;
+            }
+            result.push(c);
+            self.next();
+        }
+        result
+    }
+
+    fn number(&mut self) -> Result<InnerValue, Error> {
+        let word: String = self.word().chars().filter(|c| '_' != *c).collect();
+        let (sign, digits) = match word.strip_prefix('-') {
+            Some(d) => (-1, d),
+            None => (1, word.strip_prefix('+').unwrap_or(&word)),
+        };
+        let radix = match digits.get(..2) {
+            Some("0x") => Some(16),
+            Some("0o") => Some(8),
+            Some("0b") => Some(2),
+            _ => None,
+        };
+
+        let number = match radix {
+            Some(radix) => i128::from_str_radix(&digits[2..], radix)
+                .ok()
+                .map(|n| sign * n)
+                .and_then(int),
+            None if digits.contains(['.', 'e', 'E']) => {
+                word.parse::<f64>().ok().and_then(Number::from_f64)
+            }
+            None => word.parse::<i128>().ok().and_then(int),
+        };
+        number
+            .map(InnerValue::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+
+    fn quoted(&mut self) -> Result<String, Error> {
+        if self.is_at("\"\"\"") {
+            self.pos += 3;
+            let mut body = String::new();
+            while !self.is_at("\"\"\"") {
+                match self.next() {
+                    Some('\\') => {
+                        body.push('\\');
+                        body.push(self.next().ok_or_else(|| self.error("unclosed string"))?);
+                    }
+                    Some(c) => body.push(c),
+                    None => return Err(self.error("unclosed string")),
+                }
+            }
+            self.pos += 3;
+            let body = dedent(&body).ok_or_else(|| self.error("invalid multi-line string"))?;
+            return unescape(&body).ok_or_else(|| self.error("invalid escape sequence"));
+        }
+
+        self.next();
+        let mut body = String::new();
+        loop {
+            match self.next() {
+                Some('"') => break,
+                Some('\\') => {
+                    body.push('\\');
+                    body.push(self.next().ok_or_else(|| self.error("unclosed string"))?);
+                }
+                Some(c) => body.push(c),
+                None => return Err(self.error("unclosed string")),
+            }
+        }
+        unescape(&body).ok_or_else(|| self.error("invalid escape sequence"))
+    }
+
+    fn raw(&mut self) -> Result<String, Error> {
+        let mut hashes = 0;
+        while Some('#') == self.peek() {
+            hashes += 1;
+            self.next();
+        }
+        if Some('"') != self.peek() {
+            return Err(self.error("raw string is expected"));
+        }
+
+        let fence = ["\"", &"#".repeat(hashes)].concat();
+        let multiline = self.is_at("\"\"\"");
+        self.pos += if multiline { 3 } else { 1 };
+        let mut body = String::new();
+        loop {
+            if multiline && self.is_at(&["\"\"", &fence].concat()) {
+                self.pos += 2 + fence.len();
+                return dedent(&body).ok_or_else(|| self.error("invalid multi-line string"));
+            }
+            if !multiline && self.is_at(&fence) {
+                self.pos += fence.len();
+                return Ok(body);
+            }
+            body.push(self.next().ok_or_else(|| self.error("unclosed string"))?);
+        }
+    }
+
+    /// Skip type annotation (e.g. `(u8)`) and whitespace after it.
+    fn skip_annotation(&mut self) -> Result<(), Error> {
+        if Some('(') != self.peek() {
+            return Ok(());
+        }
+
+        self.next();
+        self.skip_node_space()?;
+        let (_, true) = self.token()? else {
+            return Err(self.error("type name is expected"));
+        };
+        self.skip_node_space()?;
+        if Some(')') != self.next() {
+            return Err(self.error("')' is expected"));
+        }
+        self.skip_node_space()?;
+        Ok(())
+    }
+
+    /// Skip `=` of property and whitespace around it, return `true` if it was found. Otherwise position is kept.
+    fn skip_equals(&mut self) -> Result<bool, Error> {
+        let (pos, line) = (self.pos, self.line);
+        self.skip_node_space()?;
+        if Some('=') != self.peek() {
+            (self.pos, self.line) = (pos, line);
+            return Ok(false);
+        }
+
+        self.next();
+        self.skip_node_space()?;
+        Ok(true)
+    }
+
+    fn skip_slashdash(&mut self) -> Result<bool, Error> {
+        if !self.is_at("/-") {
+            return Ok(false);
+        }
+
+        self.pos += 2;
+        self.skip_line_space()?;
+        Ok(true)
+    }
+
+    /// Skip whitespace, block comments and line continuations, return `true` if anything was skipped.
+    fn skip_node_space(&mut self) -> Result<bool, Error> {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() && '\n' != c => {
+                    self.next();
+                }
+                Some('/') if self.is_at("/*") => self.skip_block_comment()?,
+                Some('\\') => {
+                    self.next();
+                    while self.peek().is_some_and(|c| c.is_whitespace() && '\n' != c) {
+                        self.next();
+                    }
+                    if self.is_at("//") {
+                        self.skip_line_comment();
+                    }
+                    if Some('\n') != self.next() {
+                        return Err(self.error("new line is expected after '\\'"));
+                    }
+                }
+                _ => return Ok(self.pos != start),
+            }
+        }
+    }
+
+    fn skip_line_space(&mut self) -> Result<(), Error> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() || ';' == c => {
+                    self.next();
+                }
+                Some('/') if self.is_at("//") => self.skip_line_comment(),
+                Some('/') if self.is_at("/*") => self.skip_block_comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn skip_line_comment(&mut self) {
+        while self.peek().is_some_and(|c| '\n' != c) {
+            self.next();
+        }
+    }
+
+    fn skip_block_comment(&mut self) -> Result<(), Error> {
+        let mut depth = 0;
+        loop {
+            if self.is_at("/*") {
+                depth += 1;
+                self.pos += 2;
+            } else if self.is_at("*/") {
+                depth -= 1;
+                self.pos += 2;
+                if 0 == depth {
+                    return Ok(());
+                }
+            } else if self.next().is_none() {
+                return Err(self.error("unclosed comment"));
+            }
+        }
+    }
+
+    fn is_at(&self, pattern: &str) -> bool {
+        pattern
+            .chars()
+            .enumerate()
+            .all(|(idx, c)| Some(c) == self.peek_at(idx))
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    #[inline]
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let result = self.peek();
+        if result.is_some() {
+            self.pos += 1;
+        }
+        if Some('\n') == result {
+            self.line += 1;
+        }
+        result
+    }
+
+    #[inline]
+    fn error(&self, message: &'static str) -> Error {
+        Error::ParseKdl(message, self.line)
+    }
+}
+
+fn keyword(word: &str) -> Option<InnerValue> {
+    match word {
+        "true" => Some(InnerValue::Bool(true)),
+        "false" => Some(InnerValue::Bool(false)),
+        "null" => Some(InnerValue::Null),
+        _ => None,
+    }
+}
+
+fn int(number: i128) -> Option<Number> {
+    match u64::try_from(number) {
+        Ok(n) => Some(n.into()),
+        Err(_) => i64::try_from(number).ok().map(Number::from),
+    }
+}
+
+fn merge(map: &mut Map<String, InnerValue>, key: String, value: InnerValue) {
+    match (map.get_mut(&key), value) {
+        (Some(InnerValue::Object(current)), InnerValue::Object(value)) => {
+            for (k, v) in value {
+                merge(current, k, v);
+            }
+        }
+        (_, value) => {
+            map.insert(key, value);
+        }
+    }
+}
+
+fn dedent(body: &str) -> Option<String> {
+    let body = body.replace("\r\n", "\n");
+    let (first, rest) = body.split_once('\n')?;
+    let (lines, indent) = rest.rsplit_once('\n').unwrap_or(("", rest));
+    if !first.trim().is_empty() || !indent.trim().is_empty() {
+        return None;
+    }
+    if lines.is_empty() && rest.find('\n').is_none() {
+        return Some(String::new());
+    }
+
+    lines
+        .split('\n')
+        .map(|l| match l.strip_prefix(indent) {
+            Some(l) => Some(l),
+            None if l.trim().is_empty() => Some(""),
+            None => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|l| l.join("\n"))
+}
+
+fn unescape(data: &str) -> Option<String> {
+    let mut result = String::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        if '\\' != c {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            'b' => result.push('\x08'),
+            'f' => result.push('\x0C'),
+            's' => result.push(' '),
+            '\\' => result.push('\\'),
+            '"' => result.push('"'),
+            '/' => result.push('/'),
+            'u' => {
+                if Some('{') != chars.next() {
+                    return None;
+                }
+                let code: String = chars.by_ref().take_while(|c| '}' != *c).collect();
+                result.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+            c if c.is_whitespace() => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            _ => return None,
+        }
+    }
+    Some(result)
+}
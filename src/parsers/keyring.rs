@@ -0,0 +1,136 @@
+//! This module provide OS keyring parser implementation based on [`keyring`](https://docs.rs/keyring/latest/keyring/)
+//! crate. The secrets will be resolved from the platform credential store (Secret Service on Linux, Keychain on macOS
+//! and Credential Manager on Windows) and placed to given key paths.
+//!
+//! The resolved values will be stored as strings and sealed (see [`Parse::sealed_keys`]), so they will be obfuscated
+//! during display/debugging output regardless of sealed suffix of [`crate::ConfigBuilder`]. If sealed suffix was set
+//! (see [`ParserBuilder::sealed_suffix`]) then it will be appended to the last key of each key path.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["keyring"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::keyring::{Entry, ParserBuilder};
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .entry(Entry::new("myapp", "api", "auth:token"))
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{AnyResult, Case, Parse, Value, DEFAULT_KEYS_SEPARATOR};
+use derive_builder::Builder;
+
+/// All errors for OS keyring parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to get secret for service: '{1}', user: '{2}'")]
+    Keyring(#[source] keyring::Error, String, String),
+    #[error("{1} for keys: '{2}'")]
+    Value(#[source] crate::Error, &'static str, String),
+}
+
+/// The entry to be resolved from OS keyring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    service: String,
+    user: String,
+    key_path: String,
+}
+
+impl Entry {
+    /// Create [`Entry`] for given service and user names. The resolved secret will be placed to given key path.
+    #[inline]
+    pub fn new<S, U, P>(service: S, user: U, key_path: P) -> Self
+    where
+        S: Into<String>,
+        U: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            service: service.into(),
+            user: user.into(),
+            key_path: key_path.into(),
+        }
+    }
+}
+
+/// The OS keyring parser implementation.
+#[derive(Builder, Default)]
+#[builder(setter(into, strip_option), default)]
+pub struct Parser {
+    /// Add entry to be resolved from OS keyring.
+    #[builder(setter(each(name = "entry")))]
+    entries: Vec<Entry>,
+    /// Set suffix to be appended to the last key of each entry key path.
+    sealed_suffix: String,
+    /// Set delimiter used to separate keys levels in entry key path. Default is [`DEFAULT_KEYS_SEPARATOR`].
+    #[builder(default = "DEFAULT_KEYS_SEPARATOR.to_string()")]
+    keys_delimiter: String,
+    /// If entry does not exists in OS keyring then skip it. Default is `false`.
+    ignore_missing: bool,
+    /// Set parser's case sensitivity for key names.
+    case_sensitive: bool,
+    #[builder(setter(skip))]
+    sealed: Vec<Vec<String>>,
+}
+
+impl Case for Parser {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+impl Parse for Parser {
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        let mut result = Value::with_case(self.case_sensitive);
+        self.sealed.clear();
+        for entry in &self.entries {
+            let secret = match keyring::Entry::new(&entry.service, &entry.user)
+                .and_then(|e| e.get_password())
+            {
+                Ok(s) => s,
+                Err(keyring::Error::NoEntry) if self.ignore_missing => continue,
+                Err(e) => {
+                    return Err(Error::Keyring(e, entry.service.clone(), entry.user.clone()).into())
+                }
+            };
+
+            let path = [entry.key_path.as_str(), &self.sealed_suffix].concat();
+            result
+                .set_by_key_path_with_delim(&path, &self.keys_delimiter, secret)
+                .map_err(|e| Error::Value(e, "Failed to set value", path.clone()))?;
+
+            // The suffix is stripped only if sealed suffix of config is the same, so both variants are sealed.
+            self.sealed
+                .push(path.split(&self.keys_delimiter).map(String::from).collect());
+            if !self.sealed_suffix.is_empty() {
+                self.sealed.push(
+                    entry
+                        .key_path
+                        .split(&self.keys_delimiter)
+                        .map(String::from)
+                        .collect(),
+                );
+            }
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    fn sealed_keys(&self) -> Vec<Vec<String>> {
+        self.sealed.clone()
+    }
+}
@@ -0,0 +1,55 @@
+//! This module provide [MessagePack](https://msgpack.org/) parser implementation. The root of the document should be
+//! a map with string keys.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["msgpack"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::msgpack::ParserBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_path("config.msgpack")
+//!             .path_option("config")
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{FileParserBuilder, Load},
+    AnyResult, Case, Value,
+};
+use std::io::Read;
+
+/// All errors for `MessagePack` parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed parse MessagePack")]
+    ParseMsgpack(#[source] rmp_serde::decode::Error),
+}
+
+/// Builder for `MessagePack` parser.
+pub type ParserBuilder = FileParserBuilder<LoadMsgpack>;
+
+/// Implements [`Load`] trait for `MessagePack` parser.
+#[derive(Clone, Default)]
+pub struct LoadMsgpack;
+
+impl Case for LoadMsgpack {}
+
+impl Load for LoadMsgpack {
+    #[inline]
+    fn load(&mut self, reader: impl Read) -> AnyResult<Value> {
+        Ok(rmp_serde::from_read(reader).map_err(Error::ParseMsgpack)?)
+    }
+}
@@ -0,0 +1,212 @@
+//! This module provide Java-style `.properties` files parser implementation. The keys are splitted to nested keys by
+//! keys delimiter (`.` by default), so the following file:
+//!
+//! ```properties
+//! # Server settings
+//! server.port=8080
+//! server.host = localhost
+//! logger.level: debug
+//! ```
+//!
+//! will be represented as:
+//!
+//! ```yaml
+//! server:
+//!   port: 8080
+//!   host: localhost
+//! logger:
+//!   level: debug
+//! ```
+//!
+//! The format follows `java.util.Properties`: the lines started with `#` or `!` are comments, key and value are
+//! separated by `=`, `:` or whitespace, line ending with `\` is continued on the next line and escape sequences
+//! (including `\uXXXX`) are supported. The values will be typed according to `YAML` format if they are non-empty
+//! scalars, otherwise they will be strings.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["properties"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::properties::ParserBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_path("application.properties")
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{FileParserBuilder, Load},
+    AnyResult, Case, Value,
+};
+use serde_yaml::Value as YamlValue;
+use std::io::{Error as IoError, Read};
+
+/// The default delimiter of keys levels in `.properties` files.
+pub const DEFAULT_KEYS_DELIMITER: &str = ".";
+
+const WHITESPACE: [char; 3] = [' ', '\t', '\x0C'];
+
+/// All errors for `.properties` files parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed read data to buffer")]
+    Read(#[source] IoError),
+    #[error("Invalid escape sequence in line {0}")]
+    Escape(usize),
+    #[error("{1} for key: '{2}'")]
+    Value(#[source] crate::Error, &'static str, String),
+}
+
+/// Builder for `.properties` files parser.
+pub type ParserBuilder = FileParserBuilder<LoadProperties>;
+
+/// Implements [`Load`] trait for `.properties` files parser.
+#[derive(Clone)]
+pub struct LoadProperties {
+    keys_delimiter: String,
+    case_sensitive: bool,
+}
+
+impl LoadProperties {
+    /// Set delimiter used to separate keys levels. Default is [`DEFAULT_KEYS_DELIMITER`].
+    #[inline]
+    pub fn keys_delimiter<S: Into<String>>(mut self, delim: S) -> Self {
+        self.keys_delimiter = delim.into();
+        self
+    }
+
+    /// Set parser's case sensitivity for key names. Default is `true`.
+    #[inline]
+    pub fn case_sensitive(mut self, on: bool) -> Self {
+        self.case_sensitive = on;
+        self
+    }
+}
+
+impl Default for LoadProperties {
+    fn default() -> Self {
+        Self {
+            keys_delimiter: DEFAULT_KEYS_DELIMITER.to_string(),
+            case_sensitive: true,
+        }
+    }
+}
+
+impl Case for LoadProperties {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+impl Load for LoadProperties {
+    fn load(&mut self, mut reader: impl Read) -> AnyResult<Value> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data).map_err(Error::Read)?;
+
+        let mut result = Value::with_case(self.case_sensitive);
+        for (key, value) in parse_lines(&data)? {
+            let val = match serde_yaml::from_str::<YamlValue>(&value) {
+                Ok(v)
+                    if !value.is_empty()
+                        && !matches!(v, YamlValue::Mapping(_) | YamlValue::Sequence(_)) =>
+                {
+                    v
+                }
+                _ => YamlValue::String(value),
+            };
+            result
+                .set_by_key_path_with_delim(&key, &self.keys_delimiter, val)
+                .map_err(|e| Error::Value(e, "Failed to set value", key))?;
+        }
+        Ok(result)
+    }
+}
+
+fn parse_lines(data: &str) -> Result<Vec<(String, String)>, Error> {
+    let mut result = Vec::new();
+    let mut lines = data.lines().enumerate();
+    while let Some((idx, line)) = lines.next() {
+        let mut line = line.trim_start_matches(WHITESPACE).to_string();
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+
+        while ends_with_continuation(&line) {
+            line.pop();
+            match lines.next() {
+                Some((_, next)) => line.push_str(next.trim_start_matches(WHITESPACE)),
+                None => break,
+            }
+        }
+
+        let (key, value) = split_entry(&line);
+        let key = unescape(key).ok_or(Error::Escape(idx + 1))?;
+        let value = unescape(value).ok_or(Error::Escape(idx + 1))?;
+        if !key.is_empty() {
+            result.push((key, value));
+        }
+    }
+    Ok(result)
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| '\\' == *c).count() % 2 == 1
+}
+
+fn split_entry(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' | ':' | ' ' | '\t' | '\x0C' => {
+                let rest = line[idx..].trim_start_matches(WHITESPACE);
+                let rest = rest.strip_prefix(['=', ':']).unwrap_or(rest);
+                return (&line[..idx], rest.trim_start_matches(WHITESPACE));
+            }
+            _ => (),
+        }
+    }
+    (line, "")
+}
+
+fn unescape(data: &str) -> Option<String> {
+    let mut result = String::with_capacity(data.len());
+    let mut chars = data.chars();
+    while let Some(c) = chars.next() {
+        if '\\' != c {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('f') => result.push('\x0C'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                let code = u32::from_str_radix(&code, 16)
+                    .ok()
+                    .filter(|_| 4 == code.len())?;
+                result.push(char::from_u32(code)?);
+            }
+            Some(n) => result.push(n),
+            None => (),
+        }
+    }
+    Some(result)
+}
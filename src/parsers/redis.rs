@@ -0,0 +1,191 @@
+//! This module provide [Redis](https://redis.io/) parser implementation. It reads all fields of given hash or all
+//! keys of given namespace (prefix) and builds nested [`Value`] structure, where the key levels are separated by
+//! keys delimiter (`:` by default). For example, the following keys under `app:config` prefix:
+//!
+//! ```text
+//! app:config:logger:level = debug
+//! app:config:port = 8080
+//! ```
+//!
+//! will be represented as:
+//!
+//! ```yaml
+//! logger:
+//!   level: debug
+//! port: 8080
+//! ```
+//!
+//! The values are typed via `YAML` same way as in environment variables parser.
+//!
+//! If refresh interval is set, then the parser returns lease (see [`Lease`]) with that duration, so data will be
+//! polled again via [`Config::renew_leases`](crate::Config::renew_leases) or automatically by `watch::ConfigWatcher`.
+//! The renew margin of the watcher should be less than refresh interval.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["redis"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use irx_config::parsers::redis::ParserBuilder;
+//! use irx_config::ConfigBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(file_parser)
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .url("redis://127.0.0.1:6379/1")
+//!             .hash("app:features")
+//!             .refresh(Duration::from_secs(60))
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{AnyResult, Case, Lease, Parse, Value, DEFAULT_KEYS_SEPARATOR};
+use derive_builder::Builder;
+use redis::{Client, Commands, Connection};
+use serde_yaml::Value as YamlValue;
+use std::time::Duration;
+
+/// The default URL of Redis server.
+pub const DEFAULT_URL: &str = "redis://127.0.0.1:6379";
+
+/// The default timeout of Redis connection and commands.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// All errors for Redis parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to read data of: '{1}'")]
+    Redis(#[source] redis::RedisError, String),
+    #[error("Data of '{0}' is not found")]
+    Missing(String),
+    #[error("Failed parse as YAML value of key: '{1}'")]
+    ParseYaml(#[source] serde_yaml::Error, String),
+    #[error("{1} for key: '{2}'")]
+    Value(#[source] crate::Error, &'static str, String),
+}
+
+/// The Redis parser implementation.
+#[derive(Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Parser {
+    /// Set URL of Redis server. Default is [`DEFAULT_URL`].
+    #[builder(default = "DEFAULT_URL.to_string()")]
+    url: String,
+    /// Set name of the hash to be read. If set then prefix is ignored.
+    #[builder(default)]
+    hash: Option<String>,
+    /// Set prefix of the keys namespace to be read. Default is all keys.
+    #[builder(default)]
+    prefix: String,
+    /// Set delimiter used to separate keys levels. Default is [`DEFAULT_KEYS_SEPARATOR`].
+    #[builder(default = "DEFAULT_KEYS_SEPARATOR.to_string()")]
+    keys_delimiter: String,
+    /// If set to `true` then missing hash or keys will produce empty [`Value`], otherwise error will be returned.
+    /// Default is `false`.
+    #[builder(default)]
+    ignore_missing: bool,
+    /// Set interval to poll data again (see [`Lease`]). Default is no polling.
+    #[builder(default)]
+    refresh: Option<Duration>,
+    /// Set timeout of Redis connection and commands. Default is [`DEFAULT_TIMEOUT`].
+    #[builder(default = "DEFAULT_TIMEOUT")]
+    timeout: Duration,
+}
+
+impl Parser {
+    fn source(&self) -> &str {
+        self.hash.as_deref().unwrap_or(&self.prefix)
+    }
+
+    fn connect(&self) -> Result<Connection, Error> {
+        let err = |e| Error::Redis(e, self.source().into());
+        let client = Client::open(self.url.as_str()).map_err(err)?;
+        let con = client
+            .get_connection_with_timeout(self.timeout)
+            .map_err(err)?;
+        con.set_read_timeout(Some(self.timeout)).map_err(err)?;
+        con.set_write_timeout(Some(self.timeout)).map_err(err)?;
+        Ok(con)
+    }
+
+    fn read(&self, con: &mut Connection) -> Result<Vec<(String, String)>, Error> {
+        let err = |e| Error::Redis(e, self.source().into());
+        if let Some(ref hash) = self.hash {
+            return redis::cmd("HGETALL").arg(hash).query(con).map_err(err);
+        }
+
+        let prefix = self.prefix.trim_end_matches(&self.keys_delimiter);
+        let pattern = if prefix.is_empty() {
+            "*".to_string()
+        } else {
+            [prefix, &self.keys_delimiter, "*"].concat()
+        };
+        let keys: Vec<String> = con.scan_match::<_, String>(pattern).map_err(err)?.collect();
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let values: Vec<Option<String>> = redis::cmd("MGET").arg(&keys).query(con).map_err(err)?;
+        let result = keys
+            .into_iter()
+            .zip(values)
+            .filter_map(|(k, v)| {
+                let path = k
+                    .strip_prefix(prefix)?
+                    .trim_start_matches(&self.keys_delimiter);
+                Some((path.to_string(), v?))
+            })
+            .collect();
+        Ok(result)
+    }
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        let mut con = self.connect()?;
+        let entries = self.read(&mut con)?;
+        if entries.is_empty() {
+            if self.ignore_missing {
+                return Ok(Value::default());
+            }
+            return Err(Error::Missing(self.source().into()).into());
+        }
+
+        let mut result = Value::default();
+        for (key, data) in entries {
+            if key.is_empty() {
+                continue;
+            }
+
+            let val: YamlValue =
+                serde_yaml::from_str(&data).map_err(|e| Error::ParseYaml(e, key.clone()))?;
+            result
+                .set_by_key_path_with_delim(&key, &self.keys_delimiter, val)
+                .map_err(|e| Error::Value(e, "Failed to set value", key))?;
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    fn is_remote(&self, _value: &Value) -> bool {
+        true
+    }
+
+    fn leases(&self) -> Vec<Lease> {
+        self.refresh
+            .map(|d| Lease::new(self.source(), d))
+            .into_iter()
+            .collect()
+    }
+}
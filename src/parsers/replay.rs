@@ -0,0 +1,154 @@
+//! This module provide record/replay wrapper for any parser (mostly useful for remote ones). In record mode the
+//! results of wrapped parser are written to the given directory, in replay mode they are served from it without
+//! calling wrapped parser at all. That makes integration tests of services which use remote configuration sources
+//! hermetic.
+//!
+//! Each record is stored as `JSON` file named `<name>.json`. **NOTE:** records are stored as is, without sealing, so
+//! they should not contain real secrets.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["replay"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::cloud_metadata;
+//! use irx_config::parsers::replay::{Mode, ParserBuilder};
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .parser(cloud_metadata::ParserBuilder::default().build()?)
+//!             .directory("tests/records")
+//!             .name("cloud")
+//!             .mode(Mode::Replay)
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{AnyResult, Case, Lease, Parse, Value};
+use derive_builder::Builder;
+use std::{
+    fs,
+    io::Error as IoError,
+    path::{Path, PathBuf},
+};
+
+/// All errors for record/replay parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to read record: '{1}'")]
+    Read(#[source] IoError, PathBuf),
+    #[error("Failed to write record: '{1}'")]
+    Write(#[source] IoError, PathBuf),
+    #[error("Failed to (de)serialize record: '{1}'")]
+    Format(#[source] serde_json::Error, PathBuf),
+}
+
+/// Record/replay modes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Call wrapped parser and write its results to the record file.
+    Record,
+    /// Read results from the record file, wrapped parser will not be called.
+    Replay,
+    /// Replay if the record file exists, otherwise record.
+    #[default]
+    Auto,
+}
+
+/// The record/replay parser implementation.
+#[derive(Builder)]
+#[builder(pattern = "owned", setter(into, strip_option))]
+pub struct Parser<P: Parse> {
+    /// Set wrapped parser.
+    #[builder(setter(into = false))]
+    parser: P,
+    /// Set directory to store records.
+    directory: PathBuf,
+    /// Set record name, the record file name will be `<name>.json`.
+    name: String,
+    /// Set record/replay mode. Default is [`Mode::Auto`].
+    #[builder(default)]
+    mode: Mode,
+}
+
+impl<P: Parse> Parser<P> {
+    fn record_path(&self) -> PathBuf {
+        self.directory.join(format!("{}.json", self.name))
+    }
+
+    fn is_replay(&self, path: &Path) -> bool {
+        match self.mode {
+            Mode::Record => false,
+            Mode::Replay => true,
+            Mode::Auto => path.is_file(),
+        }
+    }
+}
+
+impl<P: Parse> Case for Parser<P> {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.parser.is_case_sensitive()
+    }
+}
+
+impl<P: Parse> Parse for Parser<P> {
+    fn parse(&mut self, value: &Value) -> AnyResult<Value> {
+        let path = self.record_path();
+        if self.is_replay(&path) {
+            let data = fs::read(&path).map_err(|e| Error::Read(e, path.clone()))?;
+            return Ok(serde_json::from_slice(&data).map_err(|e| Error::Format(e, path))?);
+        }
+
+        let result = self.parser.parse(value)?;
+        let data =
+            serde_json::to_vec_pretty(&result).map_err(|e| Error::Format(e, path.clone()))?;
+        fs::create_dir_all(&self.directory).map_err(|e| Error::Write(e, path.clone()))?;
+        fs::write(&path, data).map_err(|e| Error::Write(e, path))?;
+        Ok(result)
+    }
+
+    #[inline]
+    fn is_remote(&self, value: &Value) -> bool {
+        !self.is_replay(&self.record_path()) && self.parser.is_remote(value)
+    }
+
+    #[inline]
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.parser.watch_paths()
+    }
+
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        self.parser.depends_on()
+    }
+
+    #[inline]
+    fn leases(&self) -> Vec<Lease> {
+        self.parser.leases()
+    }
+
+    #[inline]
+    fn sealed_keys(&self) -> Vec<Vec<String>> {
+        self.parser.sealed_keys()
+    }
+
+    #[inline]
+    fn renew(&mut self, lease: &Lease) -> AnyResult<Option<Lease>> {
+        self.parser.renew(lease)
+    }
+
+    #[inline]
+    fn select_profile(&mut self, profile: &str) {
+        self.parser.select_profile(profile)
+    }
+}
@@ -0,0 +1,229 @@
+//! This module provide parsers for configuration delivered by `systemd` to a service: credentials (see
+//! `LoadCredential=`/`SetCredential=` options, files in `$CREDENTIALS_DIRECTORY` directory) and `EnvironmentFile=`
+//! style files.
+//!
+//! Each credential file name will be splitted to nested keys by keys delimiter (default is `__`) and the content of
+//! the file (without trailing newline characters) will be stored as a string value and sealed (see
+//! [`Parse::sealed_keys`]), so credentials will be obfuscated during display/debugging output regardless of sealed
+//! suffix of [`crate::ConfigBuilder`]. If sealed suffix was set (see [`CredentialsParserBuilder::sealed_suffix`]) then
+//! it will be appended to the last key.
+//!
+//! The variables from `EnvironmentFile=` style files will be handled in the same way as by
+//! [`crate::parsers::env`] parser: only variables with given prefix will be taken, names will be splitted to nested
+//! keys by `__` delimiter and unquoted values will be typed according to `YAML` format. The quoted values will be
+//! always strings.
+//!
+//! To enable that parsers one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["systemd"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::systemd::{
+//!     CredentialsParserBuilder, EnvironmentFileParserBuilder, LoadEnvironmentFile,
+//! };
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(CredentialsParserBuilder::default().build()?)
+//!     .append_parser(
+//!         EnvironmentFileParserBuilder::default()
+//!             .default_path("/etc/default/myapp")
+//!             .loader(LoadEnvironmentFile::default().prefix("APP_"))
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{
+        env::{parse_env_lines, vars_to_value},
+        FileParserBuilder, Load,
+    },
+    AnyResult, Case, Parse, Value,
+};
+use derive_builder::Builder;
+use std::{
+    borrow::Cow,
+    env, fs,
+    io::{Error as IoError, Read},
+    path::PathBuf,
+};
+
+/// The name of environment variable which holds path to credentials directory.
+pub const CREDENTIALS_DIRECTORY: &str = "CREDENTIALS_DIRECTORY";
+
+/// All errors for `systemd` parsers.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{1}: '{2}'")]
+    IoError(#[source] IoError, Cow<'static, str>, PathBuf),
+    #[error("Credentials directory is not set")]
+    NoCredentialsDirectory,
+    #[error("Failed to parse environment file")]
+    Env(#[source] crate::parsers::env::Error),
+    #[error("{1} for keys: '{2}'")]
+    Value(#[source] crate::Error, &'static str, String),
+}
+
+/// The `systemd` credentials parser implementation.
+#[derive(Builder, Default)]
+#[builder(setter(into, strip_option), default)]
+pub struct CredentialsParser {
+    /// Set credentials directory, otherwise directory from `CREDENTIALS_DIRECTORY` environment variable will be used.
+    directory: Option<PathBuf>,
+    /// Set delimiter used to separate keys levels in credentials names. Default is `__`.
+    #[builder(default = "\"__\".to_string()")]
+    keys_delimiter: String,
+    /// Set suffix to be appended to the last key of each credential. The credentials are sealed regardless of it.
+    sealed_suffix: String,
+    /// If credentials directory is not set or does not exists then do not try to load it. The default [`Value`] will
+    /// be returned. Default is `false`.
+    ignore_missing: bool,
+    /// Set parser's case sensitivity for key names.
+    case_sensitive: bool,
+    #[builder(setter(skip))]
+    sealed: Vec<Vec<String>>,
+}
+
+impl Case for CredentialsParser {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+impl Parse for CredentialsParser {
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        let mut result = Value::with_case(self.case_sensitive);
+        self.sealed.clear();
+        let Some(dir) = self
+            .directory
+            .clone()
+            .or_else(|| env::var_os(CREDENTIALS_DIRECTORY).map(PathBuf::from))
+        else {
+            if self.ignore_missing {
+                return Ok(result);
+            }
+            return Err(Error::NoCredentialsDirectory.into());
+        };
+
+        let entries = match fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) if self.ignore_missing => return Ok(result),
+            Err(e) => return Err(Error::IoError(e, "Failed to read directory".into(), dir).into()),
+        };
+
+        let mut paths = entries
+            .map(|e| e.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::IoError(e, "Failed to read directory".into(), dir))?;
+        paths.sort();
+
+        for path in paths.into_iter().filter(|p| p.is_file()) {
+            let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            let data = fs::read_to_string(&path)
+                .map_err(|e| Error::IoError(e, "Failed to read credential".into(), path))?;
+            let key_path = [name.as_str(), &self.sealed_suffix].concat();
+            result
+                .set_by_key_path_with_delim(
+                    &key_path,
+                    &self.keys_delimiter,
+                    data.trim_end_matches(['\r', '\n']),
+                )
+                .map_err(|e| Error::Value(e, "Failed to set value", key_path.clone()))?;
+
+            // The suffix is stripped only if sealed suffix of config is the same, so both variants are sealed.
+            self.sealed.push(
+                key_path
+                    .split(&self.keys_delimiter)
+                    .map(String::from)
+                    .collect(),
+            );
+            if !self.sealed_suffix.is_empty() {
+                self.sealed
+                    .push(name.split(&self.keys_delimiter).map(String::from).collect());
+            }
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    fn sealed_keys(&self) -> Vec<Vec<String>> {
+        self.sealed.clone()
+    }
+}
+
+/// Builder for `EnvironmentFile=` style files parser.
+pub type EnvironmentFileParserBuilder = FileParserBuilder<LoadEnvironmentFile>;
+
+/// Implements [`Load`] trait for `EnvironmentFile=` style files parser.
+#[derive(Clone)]
+pub struct LoadEnvironmentFile {
+    prefix: String,
+    env_keys_delimiter: String,
+    case_sensitive: bool,
+}
+
+impl LoadEnvironmentFile {
+    /// Set prefix for variables to be parsed. Default is empty prefix, all variables will be parsed.
+    #[inline]
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set delimiter used to separate keys levels in variables names. Default is `__`.
+    #[inline]
+    pub fn env_keys_delimiter<S: Into<String>>(mut self, delim: S) -> Self {
+        self.env_keys_delimiter = delim.into();
+        self
+    }
+
+    /// Set parser's case sensitivity for key names. Default is `false`.
+    #[inline]
+    pub fn case_sensitive(mut self, on: bool) -> Self {
+        self.case_sensitive = on;
+        self
+    }
+}
+
+impl Default for LoadEnvironmentFile {
+    fn default() -> Self {
+        Self {
+            prefix: Default::default(),
+            env_keys_delimiter: "__".to_string(),
+            case_sensitive: false,
+        }
+    }
+}
+
+impl Case for LoadEnvironmentFile {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+impl Load for LoadEnvironmentFile {
+    fn load(&mut self, mut reader: impl Read) -> AnyResult<Value> {
+        let mut data = String::new();
+        reader
+            .read_to_string(&mut data)
+            .map_err(|e| Error::IoError(e, "Failed read data to buffer".into(), PathBuf::new()))?;
+        Ok(vars_to_value(
+            parse_env_lines(&data),
+            &self.prefix,
+            &self.env_keys_delimiter,
+            self.case_sensitive,
+        )
+        .map_err(Error::Env)?)
+    }
+}
@@ -0,0 +1,137 @@
+//! This module provide loader wrapper which renders raw file contents through
+//! [Handlebars](https://handlebarsjs.com/) template engine before parsing them by wrapped loader, so configuration
+//! files could contain conditional and looped sections, e.g. the following `YAML` file:
+//!
+//! ```yaml
+//! workers:
+//! {{#each regions}}
+//!   - name: worker-{{this}}
+//!     region: {{this}}
+//! {{/each}}
+//! {{#if debug}}
+//! logger:
+//!   level: debug
+//! {{/if}}
+//! ```
+//!
+//! The template context is caller-supplied [`Value`] (see [`LoadTemplate::context`]) or, if it is not set, the merged
+//! [`Value`] results from previous parsers (see [`crate::Parse::parse`]). The rendered values are not `HTML` escaped.
+//!
+//! To enable that loader one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["template", "yaml"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::{json, ConfigBuilder, Value};
+//! use irx_config::parsers::{template::{LoadTemplate, ParserBuilder}, yaml::LoadYaml};
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_path("config.yaml.hbs")
+//!             .loader(
+//!                 LoadTemplate::new(LoadYaml)
+//!                     .context(Value::try_from(json!({ "regions": ["eu", "us"], "debug": true }))?),
+//!             )
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{FileParserBuilder, Load},
+    AnyResult, Case, Value,
+};
+use handlebars::{Handlebars, RenderError};
+use std::io::{Error as IoError, Read};
+
+/// All errors for template loader.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed read data to buffer")]
+    Read(#[source] IoError),
+    #[error("Failed to render template")]
+    Render(#[source] Box<RenderError>),
+}
+
+/// Builder for file parser with templates rendering.
+pub type ParserBuilder<L> = FileParserBuilder<LoadTemplate<L>>;
+
+/// Implements [`Load`] trait which renders template and loads the result by wrapped loader.
+#[derive(Clone)]
+pub struct LoadTemplate<L> {
+    loader: L,
+    context: Option<Value>,
+    previous: Value,
+    strict: bool,
+}
+
+impl<L> LoadTemplate<L> {
+    /// Create template loader which wraps given loader.
+    #[inline]
+    pub fn new(loader: L) -> Self {
+        Self {
+            loader,
+            context: None,
+            previous: Value::default(),
+            strict: false,
+        }
+    }
+
+    /// Set context of templates. Default is the merged [`Value`] results from previous parsers.
+    #[inline]
+    pub fn context(mut self, context: Value) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// If set to `true` then rendering of missing context values will fail, otherwise they will be rendered as empty
+    /// strings. Default is `false`.
+    #[inline]
+    pub fn strict(mut self, on: bool) -> Self {
+        self.strict = on;
+        self
+    }
+}
+
+impl<L: Default> Default for LoadTemplate<L> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(L::default())
+    }
+}
+
+impl<L: Case> Case for LoadTemplate<L> {
+    #[inline]
+    fn is_case_sensitive(&self) -> bool {
+        self.loader.is_case_sensitive()
+    }
+}
+
+impl<L: Load> Load for LoadTemplate<L> {
+    fn load(&mut self, mut reader: impl Read) -> AnyResult<Value> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data).map_err(Error::Read)?;
+
+        let mut engine = Handlebars::new();
+        engine.register_escape_fn(handlebars::no_escape);
+        engine.set_strict_mode(self.strict);
+        let context = self.context.as_ref().unwrap_or(&self.previous);
+        let data = engine
+            .render_template(&data, context)
+            .map_err(|e| Error::Render(Box::new(e)))?;
+        self.loader.load(data.as_bytes())
+    }
+
+    #[inline]
+    fn prepare(&mut self, value: &Value) {
+        self.previous = value.clone();
+        self.loader.prepare(value);
+    }
+}
@@ -15,23 +15,76 @@ macro_rules! resource_path {
     };
 }
 
+#[cfg(any(
+    feature = "cloud-metadata",
+    feature = "consul",
+    feature = "gcp-secrets"
+))]
+type Routes = &'static [(&'static str, (&'static str, &'static str))];
+
+/// Spawn HTTP/1.1 server on a local port which answers `count` requests. The status and body of the reply are taken
+/// from the first route which is a part of the request line, `404 Not Found` is sent if no route matches. Return
+/// endpoint of the server and handle to join, which yields lines (request line and headers) of every served request.
+#[cfg(any(
+    feature = "cloud-metadata",
+    feature = "consul",
+    feature = "gcp-secrets"
+))]
+fn serve_http(
+    count: usize,
+    routes: Routes,
+) -> AnyResult<(String, std::thread::JoinHandle<Vec<Vec<String>>>)> {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..count {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                request.push(line.trim_end().to_string());
+                line.clear();
+            }
+
+            let (status, body) = routes
+                .iter()
+                .find(|(route, _)| request[0].contains(route))
+                .map_or(("404 Not Found", ""), |(_, reply)| *reply);
+            write!(
+                &stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    Ok((endpoint, server))
+}
+
 #[cfg(feature = "env")]
 mod env_test {
     use super::*;
-    use crate::parsers::env::ParserBuilder;
-    use std::env;
+    use crate::{parsers::env::ParserBuilder, testing::with_env};
 
-    fn setup() {
-        env::set_var("HOME", "/home/joe");
-        env::set_var("APP_ID", "42");
-        env::set_var("APP_NODE1__ID", "1");
-        env::set_var("APP_NODE1__NAMES", "[master, '1']");
-    }
+    const VARS: [(&str, &str); 4] = [
+        ("HOME", "/home/joe"),
+        ("APP_ID", "42"),
+        ("APP_NODE1__ID", "1"),
+        ("APP_NODE1__NAMES", "[master, '1']"),
+    ];
 
     #[test]
     fn parser() -> AnyResult<()> {
-        setup();
-
         let expected = Value::try_from(json!(
         {
             "id": 42,
@@ -45,15 +98,122 @@ mod env_test {
             .default_prefix("APP_")
             .prefix_option("prefix")
             .build()?];
-        let conf = ConfigBuilder::load_from(parsers)?;
+        let conf = with_env(&VARS, || ConfigBuilder::load_from(parsers))?;
         assert_eq!(expected, conf.get::<Value>()?);
         Ok(())
     }
 
     #[test]
-    fn parser_case_sensitive() -> AnyResult<()> {
-        setup();
+    fn prefixes() -> AnyResult<()> {
+        use crate::parsers::env::Prefix;
+
+        let expected = Value::try_from(json!({
+            "id": 42,
+            "node1": { "id": 1, "names": ["master", "1"] },
+            "libs": { "mylib": { "id": 7 } },
+            "level": "debug"
+        }))?;
+
+        let vars = [
+            ("APP_ID", "42"),
+            ("APP_NODE1__ID", "1"),
+            ("APP_NODE1__NAMES", "[master, '1']"),
+            ("MYLIB_ID", "7"),
+            ("LOG_ID", "0"),
+            ("LOG_LEVEL", "debug"),
+        ];
+        let parser = ParserBuilder::default()
+            .prefix("APP_")
+            .prefix(Prefix::new("MYLIB_").mount("libs:mylib"))
+            .prefix("LOG_")
+            .build()?;
+        let conf = with_env(&vars, || ConfigBuilder::load_one(parser))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn map_var() -> AnyResult<()> {
+        let expected = Value::try_from(json!({
+            "id": 42,
+            "node1": { "id": 1, "names": ["master", "1"] },
+            "db": { "url": "postgres://db" },
+            "server": { "port": 8080 }
+        }))?;
 
+        let vars = [
+            ("APP_ID", "42"),
+            ("APP_NODE1__ID", "1"),
+            ("APP_NODE1__NAMES", "[master, '1']"),
+            ("APP_SERVER__PORT", "80"),
+            ("PORT", "8080"),
+            ("DATABASE_URL", "postgres://db"),
+        ];
+        let parser = ParserBuilder::default()
+            .default_prefix("APP_")
+            .map_var("PORT", "server:port")
+            .map_var("DATABASE_URL", "db:url")
+            .map_var("RUST_LOG", "logger:level")
+            .build()?;
+        let conf = with_env(&vars, || ConfigBuilder::load_one(parser))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn map_var_only() -> AnyResult<()> {
+        let expected = Value::try_from(json!({
+            "db": { "url": "postgres://db" },
+            "server": { "port": 8080 }
+        }))?;
+
+        let vars = [
+            ("APP_ID", "42"),
+            ("PORT", "8080"),
+            ("DATABASE_URL", "postgres://db"),
+        ];
+        let parser = ParserBuilder::default()
+            .map_var("PORT", "server:port")
+            .map_var("DATABASE_URL", "db:url")
+            .build()?;
+        let conf = with_env(&vars, || ConfigBuilder::load_one(parser))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn json_values() -> AnyResult<()> {
+        let vars = [
+            ("APP_FEATURES", r#"{"a": true, "b": [1, "2"]}"#),
+            ("APP_DEBUG", "on"),
+            ("APP_NAME", "joe"),
+            ("APP_ID", "42"),
+        ];
+        let parser = |json| {
+            ParserBuilder::default()
+                .default_prefix("APP_")
+                .json_values(json)
+                .build()
+        };
+
+        let expected = Value::try_from(json!({
+            "features": { "a": true, "b": [1, "2"] },
+            "debug": "on",
+            "name": "joe",
+            "id": 42
+        }))?;
+        let json_parser = parser(true)?;
+        let conf = with_env(&vars, || ConfigBuilder::load_one(json_parser))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let yaml_parser = parser(false)?;
+        let conf = with_env(&vars, || ConfigBuilder::load_one(yaml_parser))?;
+        assert_eq!(Some(true), conf.get_by_key_path("features:a")?);
+        Ok(())
+    }
+
+    #[test]
+    fn parser_case_sensitive() -> AnyResult<()> {
         let expected = Value::try_from(json!(
         {
             "ID": 42,
@@ -68,12 +228,118 @@ mod env_test {
             .prefix_option("prefix")
             .case_sensitive(true)
             .build()?;
-        let conf = ConfigBuilder::load_one(parser)?;
+        let conf = with_env(&VARS, || ConfigBuilder::load_one(parser))?;
         assert_eq!(expected, conf.get::<Value>()?);
         Ok(())
     }
 }
 
+#[cfg(feature = "gcp-secrets")]
+mod gcp_secrets_test {
+    use super::*;
+    use crate::parsers::gcp_secrets::{ParserBuilder, Secret};
+
+    const ROUTES: Routes = &[
+        (
+            "/token ",
+            (
+                "200 OK",
+                r#"{"access_token": "meta-token", "expires_in": 3599}"#,
+            ),
+        ),
+        // Base64 encoded `s3cr3t`.
+        (
+            "/secrets/db-password/versions/latest:access ",
+            ("200 OK", r#"{"payload": {"data": "czNjcjN0"}}"#),
+        ),
+    ];
+
+    #[test]
+    fn secrets() -> AnyResult<()> {
+        let (endpoint, server) = serve_http(3, ROUTES)?;
+
+        let conf = ConfigBuilder::default()
+            .append_parser(
+                ParserBuilder::default()
+                    .project("my-project")
+                    .secret(Secret::new("db-password", "db:password"))
+                    .secret(Secret::with_version(
+                        "projects/other/secrets/missing",
+                        "2",
+                        "auth:token",
+                    ))
+                    .sealed_suffix("_sealed_")
+                    .ignore_missing(true)
+                    .endpoint(endpoint.clone())
+                    .metadata_endpoint(endpoint)
+                    .build()?,
+            )
+            .sealed_suffix("_sealed_")
+            .load()?;
+        let requests = server.join().unwrap();
+
+        assert!(requests[0]
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("metadata-flavor: google")));
+        assert!(requests[1][0].starts_with(
+            "GET /v1/projects/my-project/secrets/db-password/versions/latest:access "
+        ));
+        assert!(requests[1].iter().any(|h| h.ends_with("Bearer meta-token")));
+        assert!(
+            requests[2][0].starts_with("GET /v1/projects/other/secrets/missing/versions/2:access ")
+        );
+
+        assert_eq!(
+            Some("s3cr3t".to_string()),
+            conf.get_by_key_path("db:password")?
+        );
+        assert_eq!(None, conf.get_by_key_path::<String, _>("auth:token")?);
+        assert!(!conf.to_string().contains("s3cr3t"));
+        Ok(())
+    }
+
+    #[test]
+    fn secrets_sealed_without_suffix() -> AnyResult<()> {
+        let (endpoint, server) = serve_http(1, ROUTES)?;
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .project("my-project")
+                .secret(Secret::new("db-password", "db:password"))
+                .access_token("token")
+                .endpoint(endpoint)
+                .build()?,
+        )?;
+        server.join().unwrap();
+
+        assert_eq!(
+            Some("s3cr3t".to_string()),
+            conf.get_by_key_path("db:password")?
+        );
+        assert!(!conf.to_string().contains("s3cr3t"));
+        assert!(!format!("{:?}", conf.get_value()).contains("s3cr3t"));
+        assert!(!serde_json::to_string(&conf)?.contains("s3cr3t"));
+        Ok(())
+    }
+
+    #[test]
+    fn missing() -> AnyResult<()> {
+        let (endpoint, server) = serve_http(1, ROUTES)?;
+
+        let result = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .secret(Secret::new("projects/p/secrets/missing", "token"))
+                .access_token("token")
+                .endpoint(endpoint)
+                .build()?,
+        );
+        let requests = server.join().unwrap();
+        assert!(requests[0].iter().any(|h| h.ends_with("Bearer token")));
+        assert!(result.is_err());
+        Ok(())
+    }
+}
+
 #[cfg(feature = "json")]
 mod json_test {
     use super::*;
@@ -97,6 +363,38 @@ mod json_test {
         Ok(())
     }
 
+    #[test]
+    fn parser_path_list() -> AnyResult<()> {
+        let dir = std::env::temp_dir().join(format!("irx-config-paths-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let (first, second, main) = (
+            dir.join("a.json"),
+            dir.join("b.json"),
+            dir.join("main.json"),
+        );
+        fs::write(&first, r#"{"id": 1, "name": "first"}"#)?;
+        fs::write(&second, r#"{"id": 2}"#)?;
+        let paths = json!({"config": [first, dir.join("missing.json"), second]});
+        fs::write(&main, paths.to_string())?;
+
+        let conf = ConfigBuilder::default()
+            .append_parser(
+                ParserBuilder::default()
+                    .default_path(resource_path!("config.json"))
+                    .path_option("config")
+                    .ignore_missing_file(true)
+                    .build()?,
+            )
+            .append_parser(ParserBuilder::default().default_path(&main).build()?)
+            .load()?;
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(Some(2), conf.get_by_key_path("id")?);
+        assert_eq!(Some("first".to_string()), conf.get_by_key_path("name")?);
+        assert_eq!(4, conf.watch_paths().len());
+        Ok(())
+    }
+
     #[test]
     fn parser_ignore_missing_file() -> AnyResult<()> {
         let path = resource_path!("missing.json");
@@ -142,105 +440,1665 @@ mod json_test {
     }
 
     #[test]
-    #[should_panic(expected = "NotAFile")]
-    #[cfg_attr(windows, ignore)]
-    fn parse_not_a_file_unix() {
-        parse_not_a_file()
+    #[should_panic(expected = "NotAFile")]
+    #[cfg_attr(windows, ignore)]
+    fn parse_not_a_file_unix() {
+        parse_not_a_file()
+    }
+
+    #[test]
+    #[should_panic(expected = "Access is denied")]
+    #[cfg_attr(unix, ignore)]
+    fn parse_not_a_file_windows() {
+        parse_not_a_file()
+    }
+}
+
+#[cfg(feature = "json5-parser")]
+mod json5_test {
+    use super::*;
+    use crate::parsers::json5::ParserBuilder;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let path = resource_path!("config.json5");
+        let expected_path = resource_path!("config.json");
+        let expected = fs::read_to_string(expected_path)?;
+        let expected: Value = serde_json::from_str(&expected)?;
+
+        let conf = ConfigBuilder::default()
+            .append_parser(
+                ParserBuilder::default()
+                    .default_path(path)
+                    .path_option("config")
+                    .build()?,
+            )
+            .load()?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod yaml_test {
+    use super::*;
+    use crate::parsers::yaml::ParserBuilder;
+    use std::path::Path;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let path = Path::new(resource_dir!()).join("config.yaml");
+        let expected = fs::read_to_string(&path)?;
+        let expected: Value = serde_yaml::from_str(&expected)?;
+
+        let conf =
+            ConfigBuilder::load_from(vec![ParserBuilder::default().default_path(path).build()?])?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "keyring")]
+mod keyring_test {
+    use super::*;
+    use crate::{
+        parsers::keyring::{Entry, ParserBuilder},
+        sealed::OBFUSCATED,
+    };
+    use keyring::credential::{Credential, CredentialApi, CredentialBuilderApi};
+    use std::{any::Any, collections::BTreeMap, sync::Mutex};
+
+    // Unlike `keyring::mock` store, the secrets are shared between entries with the same service and user.
+    static STORE: Mutex<BTreeMap<(String, String), Vec<u8>>> = Mutex::new(BTreeMap::new());
+
+    #[derive(Debug)]
+    struct MemoryCredential(String, String);
+
+    impl CredentialApi for MemoryCredential {
+        fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+            let key = (self.0.clone(), self.1.clone());
+            STORE.lock().unwrap().insert(key, secret.to_vec());
+            Ok(())
+        }
+
+        fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+            let key = (self.0.clone(), self.1.clone());
+            STORE
+                .lock()
+                .unwrap()
+                .get(&key)
+                .cloned()
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn delete_credential(&self) -> keyring::Result<()> {
+            let key = (self.0.clone(), self.1.clone());
+            STORE
+                .lock()
+                .unwrap()
+                .remove(&key)
+                .map(|_| ())
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[derive(Debug)]
+    struct MemoryBuilder;
+
+    impl CredentialBuilderApi for MemoryBuilder {
+        fn build(
+            &self,
+            _target: Option<&str>,
+            service: &str,
+            user: &str,
+        ) -> keyring::Result<Box<Credential>> {
+            Ok(Box::new(MemoryCredential(service.into(), user.into())))
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    fn setup() {
+        keyring::set_default_credential_builder(Box::new(MemoryBuilder));
+    }
+
+    #[test]
+    fn parser_ignore_missing() -> AnyResult<()> {
+        setup();
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .entry(Entry::new("irx-config-test", "api", "auth:token"))
+                .ignore_missing(true)
+                .build()?,
+        )?;
+        assert_eq!(Value::default(), conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn parser_sealed() -> AnyResult<()> {
+        setup();
+        keyring::Entry::new("irx-config-test", "db")?.set_password("secret")?;
+
+        let parser = ParserBuilder::default()
+            .entry(Entry::new("irx-config-test", "db", "db:password"))
+            .build()?;
+        let conf = ConfigBuilder::default()
+            .append_named_parser("keyring", parser)
+            .defaults_from(json!({ "db": { "user": "admin" } }))?
+            .load()?;
+        assert_eq!(
+            Some("secret".to_string()),
+            conf.get_by_key_path("db:password")?
+        );
+        assert!(conf
+            .get_value()
+            .is_secret(&["db".into(), "password".into()]));
+        assert!(!conf.get_value().is_secret(&["db".into(), "user".into()]));
+        for output in [format!("{conf:?}"), format!("{:?}", conf.layer("keyring"))] {
+            assert!(!output.contains("secret"));
+            assert!(output.contains(OBFUSCATED));
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Keyring(NoEntry")]
+    fn parser_missing() {
+        setup();
+
+        ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .entry(Entry::new("irx-config-test", "api", "auth:token"))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(feature = "replay")]
+mod replay_test {
+    use super::*;
+    use crate::parsers::replay::{Mode, ParserBuilder};
+    use crate::{Case, Parse};
+    use std::env;
+
+    struct Remote(Option<Value>);
+
+    impl Case for Remote {}
+
+    impl Parse for Remote {
+        fn parse(&mut self, _: &Value) -> AnyResult<Value> {
+            self.0
+                .take()
+                .ok_or_else(|| "Remote is not available".into())
+        }
+
+        fn is_remote(&self, _value: &Value) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn record_replay() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-records-{}", std::process::id()));
+        let expected = Value::try_from(json!({"name": "remote", "id": 42}))?;
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .parser(Remote(Some(expected.clone())))
+                .directory(&dir)
+                .name("remote")
+                .build()?,
+        )?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        assert!(dir.join("remote.json").is_file());
+
+        let mut conf = ConfigBuilder::default()
+            .append_parser(
+                ParserBuilder::default()
+                    .parser(Remote(None))
+                    .directory(&dir)
+                    .name("remote")
+                    .build()?,
+            )
+            .offline(true)
+            .load()?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        assert!(conf.warnings().is_empty());
+        conf.reload()?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let result = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .parser(Remote(None))
+                .directory(&dir)
+                .name("remote")
+                .mode(Mode::Record)
+                .build()?,
+        );
+        fs::remove_dir_all(&dir)?;
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn forward() -> AnyResult<()> {
+        use crate::Lease;
+        use std::time::Duration;
+
+        struct Dependent;
+
+        impl Case for Dependent {}
+
+        impl Parse for Dependent {
+            fn parse(&mut self, _: &Value) -> AnyResult<Value> {
+                Ok(Value::default())
+            }
+
+            fn depends_on(&self) -> Vec<String> {
+                vec!["config:path".to_string()]
+            }
+
+            fn leases(&self) -> Vec<Lease> {
+                vec![Lease::new("secret", Duration::from_secs(60))]
+            }
+        }
+
+        let parser = ParserBuilder::default()
+            .parser(Dependent)
+            .directory(resource_path!("missing"))
+            .name("dependent")
+            .build()?;
+        assert_eq!(vec!["config:path"], parser.depends_on());
+        assert_eq!(
+            vec!["secret"],
+            parser.leases().iter().map(Lease::id).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn replay_missing() {
+        let result = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .parser(Remote(None))
+                .directory(resource_path!("missing"))
+                .name("remote")
+                .mode(Mode::Replay)
+                .build()
+                .unwrap(),
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(feature = "systemd")]
+mod systemd_test {
+    use super::*;
+    use crate::parsers::systemd::{
+        CredentialsParserBuilder, EnvironmentFileParserBuilder, LoadEnvironmentFile,
+    };
+    use std::env;
+
+    #[test]
+    fn credentials() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-creds-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("db__password"), "secret\n")?;
+        fs::write(dir.join("token"), "token")?;
+
+        let conf = ConfigBuilder::default()
+            .append_parser(
+                CredentialsParserBuilder::default()
+                    .directory(&dir)
+                    .sealed_suffix("_sealed_")
+                    .build()?,
+            )
+            .sealed_suffix("_sealed_")
+            .load()?;
+        fs::remove_dir_all(&dir)?;
+
+        let expected = Value::try_from(json!({
+            "db": {
+                "password": "secret"
+            },
+            "token": "token"
+        }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        assert!(!conf.to_string().contains("secret"));
+        Ok(())
+    }
+
+    #[test]
+    fn credentials_sealed_without_suffix() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-creds-nosuffix-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("db__password"), "secret\n")?;
+        fs::write(dir.join("token"), "t0ken")?;
+
+        let conf = ConfigBuilder::load_one(
+            CredentialsParserBuilder::default()
+                .directory(&dir)
+                .build()?,
+        );
+        fs::remove_dir_all(&dir)?;
+        let conf = conf?;
+
+        assert_eq!(
+            Some("secret".to_string()),
+            conf.get_by_key_path("db:password")?
+        );
+        let output = conf.to_string();
+        assert!(!output.contains("secret"));
+        assert!(!output.contains("t0ken"));
+        Ok(())
+    }
+
+    #[test]
+    fn credentials_ignore_missing() -> AnyResult<()> {
+        let conf = ConfigBuilder::load_one(
+            CredentialsParserBuilder::default()
+                .directory(resource_path!("missing"))
+                .ignore_missing(true)
+                .build()?,
+        )?;
+        assert_eq!(Value::default(), conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn environment_file() -> AnyResult<()> {
+        let expected = Value::try_from(json!({
+            "id": 42,
+            "long": "first second",
+            "node1": {
+                "id": 1,
+                "names": ["master", "1"],
+                "tag": "quoted \"tag\"",
+                "port": "8080"
+            }
+        }))?;
+
+        let conf = ConfigBuilder::load_one(
+            EnvironmentFileParserBuilder::default()
+                .default_path(resource_path!("app.env"))
+                .loader(LoadEnvironmentFile::default().prefix("APP_"))
+                .build()?,
+        )?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+mod dir_test {
+    use super::*;
+    use crate::parsers::{
+        dir::{Order, ParserBuilder},
+        json::LoadJson,
+    };
+    use std::env;
+
+    #[test]
+    fn order() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-dir-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("10-base.json"), r#"{"id": 10, "base": true}"#)?;
+        fs::write(dir.join("2-site.json"), r#"{"id": 2, "site": true}"#)?;
+        fs::write(dir.join("local.json"), r#"{"id": 0, "local": true}"#)?;
+        fs::write(dir.join("notes.txt"), "not a json")?;
+
+        let load = |order: Order| -> AnyResult<(Option<u32>, Value)> {
+            let conf = ConfigBuilder::load_one(
+                ParserBuilder::<LoadJson>::default()
+                    .directory(&dir)
+                    .extension("json")
+                    .order(order)
+                    .build()?,
+            )?;
+            Ok((conf.get_by_key_path("id")?, conf.get::<Value>()?))
+        };
+
+        let (id, value) = load(Order::FileName)?;
+        assert_eq!(Some(0), id);
+        assert_eq!(
+            Value::try_from(json!({"id": 0, "base": true, "site": true, "local": true}))?,
+            value
+        );
+        assert_eq!(Some(0), load(Order::NumericPrefix)?.0);
+        assert_eq!(
+            Some(10),
+            load(Order::Priority(vec![
+                "local.json".into(),
+                "10-base.json".into()
+            ]))?
+            .0
+        );
+
+        let parser = ParserBuilder::<LoadJson>::default()
+            .directory(&dir)
+            .order(Order::NumericPrefix)
+            .build()?;
+        let names: Vec<_> = parser
+            .ordered_paths()?
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            vec!["2-site.json", "10-base.json", "local.json", "notes.txt"],
+            names
+        );
+        fs::remove_dir_all(&dir)?;
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::<LoadJson>::default()
+                .directory(dir)
+                .ignore_missing(true)
+                .build()?,
+        )?;
+        assert_eq!(Value::default(), conf.get::<Value>()?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "dotenv")]
+mod dotenv_test {
+    use super::*;
+    use crate::parsers::dotenv::{LoadDotenv, ParserBuilder};
+    use std::env;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let expected = Value::try_from(json!({
+            "id": 42,
+            "db": {
+                "host": "localhost",
+                "port": 5432,
+                "password": "p#ss word"
+            },
+            "name": "# not a comment"
+        }))?;
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(resource_path!("dotenv.env"))
+                .loader(LoadDotenv::default().prefix("APP_"))
+                .build()?,
+        )?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        assert!(env::var_os("APP_DB__HOST").is_none());
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(resource_path!("dotenv.env"))
+                .build()?,
+        )?;
+        assert_eq!(Some("/home/joe".to_string()), conf.get_by_key_path("home")?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "toml-parser")]
+mod toml_test {
+    use super::*;
+    use crate::parsers::toml::ParserBuilder;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let path = resource_path!("config.toml");
+        let expected = Value::try_from(json!({
+            "id": 42,
+            "node1": {
+                "id": 1,
+                "names": ["master", "1"],
+                "timestamp": "2021-09-12T20:11:45Z",
+                "date": "2021-09-12",
+                "time": "20:11:45",
+                "factor": 1.3
+            },
+            "persons": [
+                {
+                    "name": "John Doe",
+                    "age": 42
+                },
+                {
+                    "name": "Jane Doe",
+                    "age": 24
+                }
+            ]
+        }))?;
+        println!("expected: {expected:?}");
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(path)
+                .path_option("config")
+                .build()?,
+        )?;
+        let calculated: Value = conf.get()?;
+        println!("calculated: {calculated:?}");
+
+        assert_eq!(expected, calculated);
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    feature = "auto",
+    feature = "env",
+    feature = "toml-parser",
+    feature = "testing"
+))]
+mod auto_test {
+    use super::*;
+    use crate::{
+        parsers::{auto::ParserBuilder, env::ParserBuilder as EnvParserBuilder, toml},
+        testing::with_env,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let path = resource_path!("config.json");
+        let expected: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().default_path(path).build()?)?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let path = resource_path!("config.toml");
+        let expected =
+            ConfigBuilder::load_one(toml::ParserBuilder::default().default_path(path).build()?)?;
+        let vars = [("APP_CONFIG", path)];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(ConfigBuilder::default()
+                .append_parser(
+                    ParserBuilder::default()
+                        .default_path(resource_path!("config.json"))
+                        .path_option("config")
+                        .build()?,
+                )
+                .append_parser(EnvParserBuilder::default().default_prefix("APP_").build()?)
+                .load()?)
+        })?;
+        assert_eq!(
+            expected.get_by_key_path::<Value, _>("node1")?,
+            conf.get_by_key_path("node1")?
+        );
+        assert_eq!(vec![PathBuf::from(path)], conf.watch_paths());
+        Ok(())
+    }
+
+    #[test]
+    fn parser_unknown_format() -> AnyResult<()> {
+        let result = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(resource_path!("app.env"))
+                .build()?,
+        );
+        assert!(result.is_err_and(|e| format!("{e:?}").contains("UnknownFormat")));
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "uri", feature = "env", feature = "testing"))]
+mod uri_test {
+    use super::*;
+    use crate::{
+        parsers::{
+            env::ParserBuilder as EnvParserBuilder,
+            uri::{ParserBuilder, Registry},
+        },
+        testing::with_env,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let path = resource_path!("config.json");
+        let expected: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        for uri in [path.to_string(), format!("file://{path}")] {
+            let conf = ConfigBuilder::load_one(ParserBuilder::default().default_uri(uri).build()?)?;
+            assert_eq!(expected, conf.get::<Value>()?);
+            assert_eq!(vec![PathBuf::from(path)], conf.watch_paths());
+        }
+
+        let vars = [("APP_URI", "env://APP_DATA"), ("APP_DATA", r#"{"id": 42}"#)];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(ConfigBuilder::default()
+                .append_parser(
+                    ParserBuilder::default()
+                        .default_uri(path)
+                        .uri_option("uri")
+                        .build()?,
+                )
+                .append_parser(EnvParserBuilder::default().default_prefix("APP_").build()?)
+                .load()?)
+        })?;
+        assert_eq!(Some(42), conf.get_by_key_path("id")?);
+
+        let registry = Registry::default().scheme("mem", |l: &str| {
+            Ok(Value::try_from(json!({ "location": l }))?)
+        });
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_uri("mem://settings")
+                .registry(registry)
+                .build()?,
+        )?;
+        assert_eq!(
+            Some("settings".to_string()),
+            conf.get_by_key_path("location")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parser_offline() -> AnyResult<()> {
+        use crate::Parse;
+        use std::{cell::Cell, rc::Rc};
+
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let registry = Registry::default().remote_scheme("mem", move |_: &str| {
+            counter.set(counter.get() + 1);
+            Ok(Value::try_from(json!({ "id": 42 }))?)
+        });
+        let parser = ParserBuilder::default()
+            .default_uri("mem://settings")
+            .registry(registry)
+            .build()?;
+        assert!(parser.is_remote(&Value::default()));
+        let conf = ConfigBuilder::default()
+            .append_named_parser("remote", parser)
+            .offline(true)
+            .load()?;
+        assert_eq!(0, calls.get());
+        assert_eq!(None, conf.get_by_key_path::<i32, _>("id")?);
+        assert_eq!(1, conf.warnings().len());
+
+        let parser = ParserBuilder::default()
+            .default_uri(resource_path!("config.json"))
+            .build()?;
+        assert!(!parser.is_remote(&Value::default()));
+        Ok(())
+    }
+
+    #[cfg(feature = "uri-http")]
+    #[test]
+    fn parser_offline_http() -> AnyResult<()> {
+        let conf = ConfigBuilder::default()
+            .append_parser(
+                ParserBuilder::default()
+                    .default_uri("https://config.invalid/config.json")
+                    .build()?,
+            )
+            .offline(true)
+            .load()?;
+        assert_eq!(Value::default(), conf.get::<Value>()?);
+        assert_eq!(1, conf.warnings().len());
+        Ok(())
+    }
+
+    #[cfg(feature = "uri-http")]
+    #[test]
+    fn parser_offline_option() -> AnyResult<()> {
+        const REMOTE: &str = "https://config.invalid/config.json";
+        let path = resource_path!("config.json");
+        let load = |uri: &str, default_uri: &str| {
+            with_env(&[("APP_URI", uri)], || -> AnyResult<_> {
+                Ok(ConfigBuilder::default()
+                    .append_parser(
+                        ParserBuilder::default()
+                            .default_uri(default_uri)
+                            .uri_option("uri")
+                            .build()?,
+                    )
+                    .append_parser(EnvParserBuilder::default().default_prefix("APP_").build()?)
+                    .offline(true)
+                    .load()?)
+            })
+        };
+
+        // Remote URI from option over local default one is not fetched.
+        let conf = load(REMOTE, path)?;
+        assert_eq!(1, conf.warnings().len());
+        assert_eq!(None, conf.get_by_key_path::<i32, _>("settings:id")?);
+
+        // Local URI from option over remote default one is parsed.
+        let conf = load(path, REMOTE)?;
+        assert!(conf.warnings().is_empty());
+        assert_eq!(Some(42), conf.get_by_key_path("settings:id")?);
+        Ok(())
+    }
+
+    #[test]
+    fn parser_unknown_scheme() -> AnyResult<()> {
+        let result = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_uri("s3://bucket/config.json")
+                .build()?,
+        );
+        assert!(result.is_err_and(|e| format!("{e:?}").contains("UnknownScheme")));
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "sources", feature = "env", feature = "testing"))]
+mod sources_test {
+    use super::*;
+    use crate::{sources::Sources, testing::with_env};
+
+    #[test]
+    fn parse() -> AnyResult<()> {
+        let path = resource_path!("config.json");
+        let descriptor = format!("env:APP_, json:{path}");
+        let vars = [("APP_SETTINGS__ID", "7")];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(Sources::default().parse(&descriptor)?.load()?)
+        })?;
+        assert_eq!(2, conf.layers().len());
+        assert_eq!(Some(7), conf.get_by_key_path("settings:id")?);
+        assert_eq!(
+            Some("node json from file".to_string()),
+            conf.get_by_key_path("settings:name")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse = |d| Sources::default().parse(d).err().map(|e| format!("{e:?}"));
+        assert!(parse("env:APP_,,json:a.json").is_some_and(|e| e.contains("EmptyEntry")));
+        assert!(parse("ini:app.ini").is_some_and(|e| e.contains("UnknownKind")));
+        assert!(parse("json").is_some_and(|e| e.contains("MissingArgument")));
+    }
+}
+
+#[cfg(all(feature = "gzip", feature = "zstd", feature = "auto"))]
+mod compression_test {
+    use super::*;
+    use crate::parsers::{auto, json::ParserBuilder};
+    use flate2::{write::GzEncoder, Compression};
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+    use std::{env, io::Write};
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let data = fs::read(resource_path!("config.json"))?;
+        let expected: Value = serde_json::from_slice(&data)?;
+        let dir = env::temp_dir().join(format!("irx-config-compressed-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&data)?;
+        let gzip = dir.join("config.json.gz");
+        fs::write(&gzip, encoder.finish()?)?;
+        let zstd = dir.join("config.json.zst");
+        fs::write(
+            &zstd,
+            compress_to_vec(data.as_slice(), CompressionLevel::Fastest),
+        )?;
+
+        for path in [&gzip, &zstd] {
+            let conf =
+                ConfigBuilder::load_one(ParserBuilder::default().default_path(path).build()?)?;
+            assert_eq!(expected, conf.get::<Value>()?);
+            let conf = ConfigBuilder::load_one(
+                auto::ParserBuilder::default().default_path(path).build()?,
+            )?;
+            assert_eq!(expected, conf.get::<Value>()?);
+        }
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "encoding", feature = "json"))]
+mod encoding_test {
+    use super::*;
+    use crate::parsers::json::ParserBuilder;
+    use std::env;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-encoding-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let (ansi, utf16) = (dir.join("ansi.json"), dir.join("utf16.json"));
+        fs::write(&ansi, b"{\"name\": \"caf\xe9\"}")?;
+        let data: Vec<u8> = "\u{feff}{\"name\": \"caf\u{e9}\"}"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        fs::write(&utf16, data)?;
+
+        for path in [&ansi, &utf16] {
+            let conf = ConfigBuilder::load_one(
+                ParserBuilder::default()
+                    .default_path(path)
+                    .encoding(encoding_rs::WINDOWS_1252)
+                    .build()?,
+            )?;
+            assert_eq!(Some("caf\u{e9}".to_string()), conf.get_by_key_path("name")?);
+        }
+        assert!(
+            ConfigBuilder::load_one(ParserBuilder::default().default_path(&ansi).build()?).is_err()
+        );
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "wasm", feature = "env"))]
+mod wasm_test {
+    use super::*;
+    use crate::{
+        parsers::{env::ParserBuilder as EnvParserBuilder, wasm::ParserBuilder},
+        testing::with_env,
+    };
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let vars = [("APP_ID", "42"), ("APP_NODE__NAME", "master")];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(ConfigBuilder::default()
+                .append_parser(
+                    ParserBuilder::default()
+                        .path(resource_path!("plugin_echo.wat"))
+                        .fuel(1_000_000u64)
+                        .build()?,
+                )
+                .append_parser(EnvParserBuilder::default().default_prefix("APP_").build()?)
+                .load()?)
+        })?;
+        let expected = Value::try_from(json!({ "id": 42, "node": { "name": "master" } }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn parser_out_of_fuel() -> AnyResult<()> {
+        let result = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .path(resource_path!("plugin_loop.wat"))
+                .fuel(100_000u64)
+                .build()?,
+        );
+        assert!(result.is_err_and(|e| format!("{e:?}").contains("Wasm")));
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "auto"))]
+mod include_test {
+    use super::*;
+    use crate::{
+        parsers::{auto, yaml::ParserBuilder, Error, DEFAULT_INCLUDE_KEY},
+        Parse,
+    };
+    use std::{env, path::PathBuf};
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-include-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested"))?;
+        let files = [
+            (
+                "main.yaml",
+                "$include: [common.yaml, nested/db.yaml]\nname: main\nport: 80",
+            ),
+            ("common.yaml", "port: 8080\nlevel: info"),
+            (
+                "nested/db.yaml",
+                "$include: ../shared.json\ndb: {host: db.local}",
+            ),
+            ("shared.json", r#"{"db": {"port": 5432}, "level": "debug"}"#),
+            ("a.yaml", "$include: b.yaml"),
+            ("b.yaml", "$include: a.yaml"),
+        ];
+        for (name, data) in files {
+            fs::write(dir.join(name), data)?;
+        }
+
+        let expected = Value::try_from(json!({
+            "name": "main",
+            "port": 80,
+            "level": "debug",
+            "db": { "host": "db.local", "port": 5432 }
+        }))?;
+        let main = dir.join("main.yaml");
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(&main)
+                .include_key(DEFAULT_INCLUDE_KEY)
+                .build()?,
+        )?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        let mut watched = conf.watch_paths();
+        watched.sort();
+        let mut paths: Vec<PathBuf> = ["common.yaml", "main.yaml", "nested/db.yaml"]
+            .iter()
+            .map(|p| dir.join(p))
+            .collect();
+        paths.push(dir.join("nested/../shared.json"));
+        paths.sort();
+        assert_eq!(paths, watched);
+
+        let conf = ConfigBuilder::load_one(
+            auto::ParserBuilder::default()
+                .default_path(&main)
+                .include_key(DEFAULT_INCLUDE_KEY)
+                .build()?,
+        )?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().default_path(&main).build()?)?;
+        assert_eq!(
+            Some(vec![
+                "common.yaml".to_string(),
+                "nested/db.yaml".to_string()
+            ]),
+            conf.get_by_keys(["$include"])?
+        );
+
+        let mut parser = ParserBuilder::default()
+            .default_path(&main)
+            .include_key(DEFAULT_INCLUDE_KEY)
+            .max_include_depth(1usize)
+            .build()?;
+        let result = parser.parse(&Value::default());
+        assert!(result.is_err_and(|e| matches!(e.downcast_ref(), Some(Error::IncludeDepth(_, 1)))));
+
+        let mut parser = ParserBuilder::default()
+            .default_path(dir.join("a.yaml"))
+            .include_key(DEFAULT_INCLUDE_KEY)
+            .build()?;
+        let result = parser.parse(&Value::default());
+        fs::remove_dir_all(&dir)?;
+        assert!(result.is_err_and(|e| matches!(e.downcast_ref(), Some(Error::IncludeCycle(_)))));
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "template", feature = "yaml", feature = "env"))]
+mod template_test {
+    use super::*;
+    use crate::{
+        parsers::{
+            env::ParserBuilder as EnvParserBuilder,
+            template::{LoadTemplate, ParserBuilder},
+            yaml::LoadYaml,
+        },
+        testing::with_env,
+    };
+    use std::env;
+
+    const TEMPLATE: &str = r#"workers:
+{{#each regions}}
+  - name: worker-{{this}}
+    region: {{this}}
+{{/each}}
+{{#if debug}}
+level: debug
+{{/if}}
+url: "<{{host}}>"
+"#;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-template-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("config.yaml.hbs");
+        fs::write(&path, TEMPLATE)?;
+
+        let context =
+            Value::try_from(json!({ "regions": ["eu", "us"], "debug": true, "host": "a&b" }))?;
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(&path)
+                .loader(LoadTemplate::new(LoadYaml).context(context))
+                .build()?,
+        )?;
+        let expected = Value::try_from(json!({
+            "workers": [
+                { "name": "worker-eu", "region": "eu" },
+                { "name": "worker-us", "region": "us" }
+            ],
+            "level": "debug",
+            "url": "<a&b>"
+        }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let vars = [("APP_REGIONS", "[eu]"), ("APP_HOST", "local")];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(ConfigBuilder::default()
+                .append_parser(EnvParserBuilder::default().default_prefix("APP_").build()?)
+                .append_parser(
+                    ParserBuilder::<LoadYaml>::default()
+                        .default_path(&path)
+                        .build()?,
+                )
+                .load()?)
+        })?;
+        let expected = Value::try_from(json!({
+            "regions": ["eu"],
+            "host": "local",
+            "workers": [{ "name": "worker-eu", "region": "eu" }],
+            "url": "<local>"
+        }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let result = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(&path)
+                .loader(LoadTemplate::new(LoadYaml).strict(true))
+                .build()?,
+        );
+        assert!(result.is_err_and(|e| format!("{e:?}").contains("Render")));
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "yaml", feature = "auto"))]
+mod profile_test {
+    use super::*;
+    use crate::parsers::{auto, yaml::ParserBuilder};
+    use std::env;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-profile-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let files = [
+            ("config.yaml", "name: main\nport: 80\nlevel: info"),
+            (
+                "config.prod.yaml",
+                "port: 8080\nprofiles: {prod: {level: warn}}",
+            ),
+            ("other.yaml", "name: other"),
+        ];
+        for (name, data) in files {
+            fs::write(dir.join(name), data)?;
+        }
+
+        let path = dir.join("config.yaml");
+        let conf = ConfigBuilder::default()
+            .append_parser(ParserBuilder::default().default_path(&path).build()?)
+            .profile("prod")
+            .load()?;
+        let expected = Value::try_from(json!({ "name": "main", "port": 8080, "level": "warn" }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        assert_eq!(
+            vec![path.clone(), dir.join("config.prod.yaml")],
+            conf.watch_paths()
+        );
+
+        let conf = ConfigBuilder::default()
+            .append_parser(
+                auto::ParserBuilder::default()
+                    .default_path(dir.join("other.yaml"))
+                    .build()?,
+            )
+            .append_parser(ParserBuilder::default().default_path(&path).build()?)
+            .profile("dev")
+            .load()?;
+        let expected = Value::try_from(json!({ "name": "other", "port": 80, "level": "info" }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "cbor")]
+mod cbor_test {
+    use super::*;
+    use crate::parsers::cbor::ParserBuilder;
+    use ciborium::Value as CborValue;
+    use std::env;
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let expected = fs::read_to_string(resource_path!("config.json"))?;
+        let expected: Value = serde_json::from_str(&expected)?;
+        let path = env::temp_dir().join(format!("irx-config-{}.cbor", std::process::id()));
+        let mut data = Vec::new();
+        ciborium::into_writer(&ciborium::tag::Captured(Some(55799), &expected), &mut data)?;
+        fs::write(&path, data)?;
+
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().default_path(&path).build()?);
+        fs::remove_file(&path)?;
+        assert_eq!(expected, conf?.get::<Value>()?);
+
+        let mut data = Vec::new();
+        let value = CborValue::Map(vec![(1.into(), CborValue::Tag(0, Box::new("id".into())))]);
+        ciborium::into_writer(&value, &mut data)?;
+        fs::write(&path, data)?;
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().default_path(&path).build()?);
+        assert_eq!(Some("id".to_string()), conf?.get_by_key_path("1")?);
+
+        fs::write(&path, [0xff])?;
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().default_path(&path).build()?);
+        fs::remove_file(&path)?;
+        assert!(conf.is_err());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "cloud-metadata")]
+mod cloud_metadata_test {
+    use super::*;
+    use crate::parsers::cloud_metadata::{Field, ParserBuilder, Provider};
+    use std::net::TcpListener;
+
+    const AZURE_COMPUTE: &str = r#"{
+        "location": "westeurope",
+        "zone": "1",
+        "vmId": "vm-42",
+        "tagsList": [{"name": "team", "value": "core"}]
+    }"#;
+
+    #[test]
+    fn azure() -> AnyResult<()> {
+        let (endpoint, server) = serve_http(1, &[("", ("200 OK", AZURE_COMPUTE))])?;
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .provider(Provider::Azure)
+                .field(Field::Region)
+                .field(Field::Tags)
+                .endpoint(endpoint)
+                .build()?,
+        )?;
+        server.join().unwrap();
+
+        let expected = Value::try_from(json!({
+            "facts": {
+                "cloud": {
+                    "provider": "azure",
+                    "region": "westeurope",
+                    "tags": {
+                        "team": "core"
+                    }
+                }
+            }
+        }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn not_available() -> AnyResult<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let endpoint = format!("http://{}", listener.local_addr()?);
+        drop(listener);
+
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().endpoint(endpoint).build()?)?;
+        assert_eq!(Value::default(), conf.get::<Value>()?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "consul")]
+mod consul_test {
+    use super::*;
+    use crate::parsers::consul::ParserBuilder;
+
+    // Values are base64 encoded: `debug`, `8080` and `true`.
+    const KV: &str = r#"[
+        {"Key": "app/config/", "Value": null},
+        {"Key": "app/config/logger/level", "Value": "ZGVidWc="},
+        {"Key": "app/config/port", "Value": "ODA4MA=="},
+        {"Key": "app/config/debug", "Value": "dHJ1ZQ=="}
+    ]"#;
+
+    #[test]
+    fn kv() -> AnyResult<()> {
+        let (endpoint, server) = serve_http(1, &[("/v1/kv/app/config", ("200 OK", KV))])?;
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .endpoint(endpoint)
+                .prefix("app/config")
+                .token("secret")
+                .build()?,
+        )?;
+        let requests = server.join().unwrap();
+        let request = &requests[0];
+        assert_eq!("GET /v1/kv/app/config?recurse=true HTTP/1.1", request[0]);
+        assert!(request
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("x-consul-token: secret")));
+
+        let expected = Value::try_from(json!({
+            "logger": {
+                "level": "debug"
+            },
+            "port": 8080,
+            "debug": true
+        }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn missing() -> AnyResult<()> {
+        for ignore_missing in [true, false] {
+            let (endpoint, server) = serve_http(1, &[])?;
+
+            let result = ConfigBuilder::load_one(
+                ParserBuilder::default()
+                    .endpoint(endpoint)
+                    .prefix("missing")
+                    .ignore_missing(ignore_missing)
+                    .build()?,
+            );
+            server.join().unwrap();
+            assert_eq!(ignore_missing, result.is_ok());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "kdl")]
+mod kdl_test {
+    use super::*;
+    use crate::parsers::{
+        kdl::{LoadKdl, ParserBuilder},
+        Load,
+    };
+
+    #[test]
+    fn parser() -> AnyResult<()> {
+        let expected = Value::try_from(json!({
+            "title": "Demo \u{1F600} app",
+            "server": {
+                "host": "localhost",
+                "port": 9090,
+                "tls": true,
+                "workers": 16,
+                "ratio": 15.0
+            },
+            "hosts": ["a.local", "b.local", "c.local"],
+            "limits": { "_args": 3, "max": 1000, "soft": null },
+            "legacy": { "enabled": true, "path": "C:\\temp\\" },
+            "pattern": "\\d+",
+            "motd": "Hello,\n  World!",
+            "empty": null,
+            "long_line": ["first", "second"]
+        }))?;
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(resource_path!("config.kdl"))
+                .build()?,
+        )?;
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(resource_path!("config.kdl"))
+                .loader(LoadKdl::default().args_key("value"))
+                .build()?,
+        )?;
+        assert_eq!(Some(3), conf.get_by_key_path("limits:value")?);
+        Ok(())
+    }
+
+    #[test]
+    fn parser_spec() -> AnyResult<()> {
+        let mut loader = LoadKdl::default();
+        for (data, expected) in [
+            // Spacing around property `=`
+            ("node key = 1", json!({"node": {"key": 1}})),
+            (
+                "node key\t=\t\"a\" k2= #true k3 =#null",
+                json!({"node": {"key": "a", "k2": true, "k3": null}}),
+            ),
+            (
+                "node \"quoted key\" = 2",
+                json!({"node": {"quoted key": 2}}),
+            ),
+            ("node key \\\n  = 3", json!({"node": {"key": 3}})),
+            ("node key /* comment */ = 4", json!({"node": {"key": 4}})),
+            (
+                "node a b=1 c",
+                json!({"node": {"b": 1, "_args": ["a", "c"]}}),
+            ),
+            // Raw strings
+            ("node #\"C:\\temp\"#", json!({"node": "C:\\temp"})),
+            ("node ##\"say \"#hi\"#\"##", json!({"node": "say \"#hi\"#"})),
+            ("node r#\"a \"b\"\"#", json!({"node": "a \"b\""})),
+            (
+                "node #\"\"\"\n  a\\n\n  b\n  \"\"\"#",
+                json!({"node": "a\\n\nb"}),
+            ),
+            (
+                "node #\"key\"# = #\"value\"#",
+                json!({"node": {"key": "value"}}),
+            ),
+            // Slashdash
+            ("/- node 1\nother 2", json!({"other": 2})),
+            ("/-\nnode 1\nother 2", json!({"other": 2})),
+            ("node 1 /- 2 3", json!({"node": [1, 3]})),
+            ("node /- key = 1 other=2", json!({"node": {"other": 2}})),
+            (
+                "node /- { child 1 } { child 2 }",
+                json!({"node": {"child": 2}}),
+            ),
+            (
+                "node { child 1; /- child 2 }",
+                json!({"node": {"child": 1}}),
+            ),
+            // Type annotations
+            ("(t)node (u8)1", json!({"node": 1})),
+            ("( t ) node ( u8 ) 1", json!({"node": 1})),
+            (
+                "node key=(u8)1 other = (\"ip v4\") \"127.0.0.1\"",
+                json!({"node": {"key": 1, "other": "127.0.0.1"}}),
+            ),
+            // Byte order mark
+            ("\u{FEFF}node 1", json!({"node": 1})),
+        ] {
+            assert_eq!(
+                Value::try_from(expected)?,
+                loader.load(data.as_bytes())?,
+                "{data}"
+            );
+        }
+        Ok(())
     }
 
     #[test]
-    #[should_panic(expected = "Access is denied")]
-    #[cfg_attr(unix, ignore)]
-    fn parse_not_a_file_windows() {
-        parse_not_a_file()
+    fn parser_error() {
+        let mut loader = LoadKdl::default();
+        for data in [
+            "node \"unclosed",
+            "node {",
+            "node }",
+            "#true 1",
+            "node a=#bad",
+            "node a =",
+            "node 1 = 2",
+            "node (1)2",
+            "node (t 1",
+            "node #inf",
+            "node key = \\ 1",
+        ] {
+            assert!(loader.load(data.as_bytes()).is_err(), "{data}");
+        }
     }
 }
 
-#[cfg(feature = "json5-parser")]
-mod json5_test {
+#[cfg(feature = "properties")]
+mod properties_test {
     use super::*;
-    use crate::parsers::json5::ParserBuilder;
+    use crate::parsers::properties::{LoadProperties, ParserBuilder};
 
     #[test]
     fn parser() -> AnyResult<()> {
-        let path = resource_path!("config.json5");
-        let expected_path = resource_path!("config.json");
-        let expected = fs::read_to_string(expected_path)?;
-        let expected: Value = serde_json::from_str(&expected)?;
+        let expected = Value::try_from(json!({
+            "server": { "port": 8080, "host": "localhost" },
+            "logger": { "level": "debug", "enabled": true },
+            "greeting": "Hello: World",
+            "path": "C:\\temp\\app",
+            "list": "a, b, c",
+            "key with spaces": "ABC",
+            "empty": ""
+        }))?;
 
-        let conf = ConfigBuilder::default()
-            .append_parser(
-                ParserBuilder::default()
-                    .default_path(path)
-                    .path_option("config")
-                    .build()?,
-            )
-            .load()?;
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(resource_path!("app.properties"))
+                .build()?,
+        )?;
         assert_eq!(expected, conf.get::<Value>()?);
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .default_path(resource_path!("app.properties"))
+                .loader(LoadProperties::default().keys_delimiter("/"))
+                .build()?,
+        )?;
+        assert_eq!(Some(8080), conf.get_by_key_path("server.port")?);
         Ok(())
     }
 }
 
-#[cfg(feature = "yaml")]
-mod yaml_test {
+#[cfg(feature = "msgpack")]
+mod msgpack_test {
     use super::*;
-    use crate::parsers::yaml::ParserBuilder;
-    use std::path::Path;
+    use crate::parsers::msgpack::ParserBuilder;
+    use std::env;
 
     #[test]
     fn parser() -> AnyResult<()> {
-        let path = Path::new(resource_dir!()).join("config.yaml");
-        let expected = fs::read_to_string(&path)?;
-        let expected: Value = serde_yaml::from_str(&expected)?;
+        let expected = fs::read_to_string(resource_path!("config.json"))?;
+        let expected: Value = serde_json::from_str(&expected)?;
+        let path = env::temp_dir().join(format!("irx-config-{}.msgpack", std::process::id()));
+        fs::write(&path, rmp_serde::to_vec_named(&expected)?)?;
 
-        let conf =
-            ConfigBuilder::load_from(vec![ParserBuilder::default().default_path(path).build()?])?;
-        assert_eq!(expected, conf.get::<Value>()?);
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().default_path(&path).build()?);
+        fs::remove_file(&path)?;
+        assert_eq!(expected, conf?.get::<Value>()?);
+
+        fs::write(&path, [0xc1])?;
+        let conf = ConfigBuilder::load_one(ParserBuilder::default().default_path(&path).build()?);
+        fs::remove_file(&path)?;
+        assert!(conf.is_err());
         Ok(())
     }
 }
 
-#[cfg(feature = "toml-parser")]
-mod toml_test {
+#[cfg(any(feature = "preset-service", feature = "preset-k8s"))]
+mod presets_test {
     use super::*;
-    use crate::parsers::toml::ParserBuilder;
+    use crate::{presets, testing::with_env};
+    use std::env;
 
+    #[cfg(feature = "preset-service")]
     #[test]
-    fn parser() -> AnyResult<()> {
-        let path = resource_path!("config.toml");
-        let expected = Value::try_from(json!({
-            "id": 42,
-            "node1": {
-                "id": 1,
-                "names": ["master", "1"],
-                "timestamp": "2021-09-12T20:11:45Z",
-                "date": "2021-09-12",
-                "time": "20:11:45",
-                "factor": 1.3
-            },
-            "persons": [
-                {
-                    "name": "John Doe",
-                    "age": 42
-                },
-                {
-                    "name": "Jane Doe",
-                    "age": 24
+    fn service() -> AnyResult<()> {
+        let vars = [("SVC_SETTINGS__ID", "42")];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(presets::service("SVC_", resource_path!("config.yaml"))?.load()?)
+        })?;
+        assert_eq!(Some(42), conf.get_by_key_path("settings:id")?);
+        assert_eq!(
+            Some("yaml.localhost".to_string()),
+            conf.get_by_key_path("settings:logger:address")?
+        );
+
+        let vars = [("SVC_CONFIG", resource_path!("missing.yaml"))];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(presets::service("SVC_", resource_path!("config.yaml"))?.load()?)
+        })?;
+        assert_eq!(None::<u32>, conf.get_by_key_path("settings:id")?);
+        Ok(())
+    }
+
+    #[cfg(feature = "preset-k8s")]
+    #[test]
+    fn k8s() -> AnyResult<()> {
+        let dir = env::temp_dir().join(format!("irx-config-k8s-{}", std::process::id()));
+        let (config_dir, secrets_dir) = (dir.join("config"), dir.join("secrets"));
+        fs::create_dir_all(&config_dir)?;
+        fs::create_dir_all(&secrets_dir)?;
+        fs::write(
+            config_dir.join("app.yaml"),
+            "db:\n  host: db.local\n  port: 5432\n",
+        )?;
+        fs::write(secrets_dir.join("db__password"), "secret\n")?;
+
+        let vars = [("K8S_DB__PORT", "6432")];
+        let conf = with_env(&vars, || -> AnyResult<_> {
+            Ok(presets::k8s("K8S_", &config_dir, &secrets_dir)?.load()?)
+        });
+        fs::remove_dir_all(&dir)?;
+
+        let conf = conf?;
+        assert_eq!(
+            Some("db.local".to_string()),
+            conf.get_by_key_path("db:host")?
+        );
+        assert_eq!(Some(6432), conf.get_by_key_path("db:port")?);
+        assert_eq!(
+            Some("secret".to_string()),
+            conf.get_by_key_path("db:password")?
+        );
+        assert!(!conf.to_string().contains("secret"));
+
+        let conf = presets::k8s("K8S_", dir.join("missing"), dir.join("missing"))?.load()?;
+        assert_eq!(None::<String>, conf.get_by_key_path("db:host")?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "redis")]
+mod redis_test {
+    use super::*;
+    use crate::parsers::redis::ParserBuilder;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    fn bulk(data: &str) -> String {
+        format!("${}\r\n{data}\r\n", data.len())
+    }
+
+    fn array(items: &[&str]) -> String {
+        items
+            .iter()
+            .fold(format!("*{}\r\n", items.len()), |r, i| r + &bulk(i))
+    }
+
+    fn serve(listener: TcpListener) -> Vec<Vec<String>> {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut commands = Vec::new();
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 {
+            let count: usize = line.trim_end()[1..].parse().unwrap();
+            let mut command = Vec::new();
+            for _ in 0..count {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+                command.push(line.trim_end().to_string());
+            }
+            line.clear();
+
+            let reply = match command[0].as_str() {
+                "HGETALL" if "features" == command[1] => {
+                    array(&["new-ui", "true", "limits:rps", "100"])
                 }
-            ]
+                "HGETALL" => "*0\r\n".to_string(),
+                "SCAN" => format!(
+                    "*2\r\n{}{}",
+                    bulk("0"),
+                    array(&["app:config:logger:level", "app:config:port"])
+                ),
+                "MGET" => array(&["debug", "8080"]),
+                _ => "+OK\r\n".to_string(),
+            };
+            (&stream).write_all(reply.as_bytes()).unwrap();
+            commands.push(command);
+        }
+        commands
+    }
+
+    fn url(listener: &TcpListener) -> AnyResult<String> {
+        Ok(format!("redis://{}", listener.local_addr()?))
+    }
+
+    #[test]
+    fn hash() -> AnyResult<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = url(&listener)?;
+        let server = thread::spawn(move || serve(listener));
+
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::default()
+                .url(url)
+                .hash("features")
+                .refresh(Duration::from_secs(60))
+                .build()?,
+        )?;
+        let commands = server.join().unwrap();
+        assert!(commands.contains(&vec!["HGETALL".to_string(), "features".to_string()]));
+
+        let expected = Value::try_from(json!({
+            "new-ui": true,
+            "limits": {
+                "rps": 100
+            }
         }))?;
-        println!("expected: {expected:?}");
+        assert_eq!(expected, conf.get::<Value>()?);
+
+        let (lease, _) = &conf.layers()[0].leases()[0];
+        assert_eq!("features", lease.id());
+        assert_eq!(Duration::from_secs(60), lease.get_duration());
+        Ok(())
+    }
+
+    #[test]
+    fn namespace() -> AnyResult<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = url(&listener)?;
+        let server = thread::spawn(move || serve(listener));
 
         let conf = ConfigBuilder::load_one(
             ParserBuilder::default()
-                .default_path(path)
-                .path_option("config")
+                .url(url)
+                .prefix("app:config")
                 .build()?,
         )?;
-        let calculated: Value = conf.get()?;
-        println!("calculated: {calculated:?}");
+        let commands = server.join().unwrap();
+        assert!(commands
+            .iter()
+            .any(|c| "SCAN" == c[0] && c.contains(&"app:config:*".to_string())));
 
-        assert_eq!(expected, calculated);
+        let expected = Value::try_from(json!({
+            "logger": {
+                "level": "debug"
+            },
+            "port": 8080
+        }))?;
+        assert_eq!(expected, conf.get::<Value>()?);
+        assert!(conf.layers()[0].leases().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn missing() -> AnyResult<()> {
+        for ignore_missing in [true, false] {
+            let listener = TcpListener::bind("127.0.0.1:0")?;
+            let url = url(&listener)?;
+            let server = thread::spawn(move || serve(listener));
+
+            let result = ConfigBuilder::load_one(
+                ParserBuilder::default()
+                    .url(url)
+                    .hash("missing")
+                    .ignore_missing(ignore_missing)
+                    .build()?,
+            );
+            server.join().unwrap();
+            assert_eq!(ignore_missing, result.is_ok());
+        }
         Ok(())
     }
 }
@@ -249,7 +2107,7 @@ mod toml_test {
 mod test_cmd {
     use super::*;
     use crate::parsers::cmd::ParserBuilder;
-    use clap::{value_parser, Arg, ArgAction, Command};
+    use clap::{value_parser, Arg, ArgAction, Command, ValueHint};
 
     fn create_app_with_subcmds() -> Command {
         let user_args = [
@@ -333,6 +2191,222 @@ mod test_cmd {
         Ok(())
     }
 
+    #[test]
+    fn from_derive() -> AnyResult<()> {
+        use clap::{builder::PossibleValue, CommandFactory, ValueEnum};
+
+        #[derive(Clone)]
+        enum Level {
+            Low,
+            High,
+        }
+
+        impl ValueEnum for Level {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[Self::Low, Self::High]
+            }
+
+            fn to_possible_value(&self) -> Option<PossibleValue> {
+                Some(PossibleValue::new(match self {
+                    Self::Low => "0",
+                    Self::High => "1",
+                }))
+            }
+        }
+
+        struct Cli;
+
+        impl CommandFactory for Cli {
+            fn command() -> Command {
+                Command::new("test").args([
+                    Arg::new("settings:port")
+                        .long("port")
+                        .value_parser(value_parser!(u16)),
+                    Arg::new("level")
+                        .long("level")
+                        .value_parser(value_parser!(Level)),
+                    Arg::new("verbose")
+                        .long("verbose")
+                        .action(ArgAction::SetTrue),
+                ])
+            }
+
+            fn command_for_update() -> Command {
+                Self::command()
+            }
+        }
+
+        let expected = Value::try_from(json!({
+            "settings": { "port": 8080 },
+            "level": "1",
+            "verbose": true
+        }))?;
+        let args = ["test", "--port", "8080", "--level", "1", "--verbose"];
+        let conf =
+            ConfigBuilder::load_one(ParserBuilder::from_derive::<Cli>().args(args).build()?)?;
+        assert_eq!(expected, *conf.get_value());
+        Ok(())
+    }
+
+    #[test]
+    fn rename_arg() -> AnyResult<()> {
+        let command = Command::new("test").args([
+            Arg::new("cfg-file").long("cfg-file"),
+            Arg::new("port").short('p'),
+        ]);
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::new(command)
+                .args(["test", "--cfg-file", "app.yaml", "-p", "80"])
+                .rename_arg("cfg-file", "settings:config:path")
+                .build()?,
+        )?;
+        let expected = Value::try_from(json!({
+            "settings": { "config": { "path": "app.yaml" } },
+            "port": "80"
+        }))?;
+        assert_eq!(expected, *conf.get_value());
+        Ok(())
+    }
+
+    #[test]
+    fn exclude_args() -> AnyResult<()> {
+        let command = Command::new("test").args([
+            Arg::new("completions").long("completions"),
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue),
+            Arg::new("name").short('n'),
+        ]);
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::new(command)
+                .args(["test", "--completions", "bash", "--dry-run", "-n", "Joe"])
+                .exclude_args(["completions", "dry-run"])
+                .build()?,
+        )?;
+        let expected = Value::try_from(json!({ "name": "Joe" }))?;
+        assert_eq!(expected, *conf.get_value());
+        Ok(())
+    }
+
+    #[test]
+    fn subcommand_key() -> AnyResult<()> {
+        let build = |args: &[&str]| -> AnyResult<Value> {
+            let conf = ConfigBuilder::load_one(
+                ParserBuilder::new(create_app_with_subcmds())
+                    .args(args)
+                    .subcommand_key("cli:command")
+                    .build()?,
+            )?;
+            Ok(conf.get_value().clone())
+        };
+
+        let expected = Value::try_from(json!({
+            "config": "app.yaml",
+            "name": { "first": "Joe" },
+            "cli": { "command": ["user", "add"] }
+        }))?;
+        assert_eq!(
+            expected,
+            build(&["test", "-c", "app.yaml", "user", "add", "-n", "Joe"])?
+        );
+        let expected = Value::try_from(json!({ "config": "app.yaml" }))?;
+        assert_eq!(expected, build(&["test", "-c", "app.yaml"])?);
+        Ok(())
+    }
+
+    #[test]
+    fn count_as() -> AnyResult<()> {
+        use crate::parsers::cmd::CountMode;
+
+        let command = Command::new("test").args([
+            Arg::new("verbose").short('v').action(ArgAction::Count),
+            Arg::new("quiet").short('q').action(ArgAction::Count),
+            Arg::new("debug").short('d').action(ArgAction::Count),
+        ]);
+        let levels = ["warn", "info", "debug"].map(String::from).to_vec();
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::new(command)
+                .args(["test", "-vvvv", "-q", "-dd"])
+                .count_as(CountMode::Bool)
+                .arg_count_as("verbose", CountMode::Level(levels))
+                .arg_count_as("debug", CountMode::Int)
+                .build()?,
+        )?;
+        let expected = Value::try_from(json!({ "verbose": "debug", "quiet": true, "debug": 2 }))?;
+        assert_eq!(expected, *conf.get_value());
+        Ok(())
+    }
+
+    #[test]
+    fn typed_values() -> AnyResult<()> {
+        use clap::builder::PossibleValuesParser;
+
+        let command = Command::new("test").args([
+            Arg::new("size")
+                .long("size")
+                .value_parser(value_parser!(u64)),
+            Arg::new("offset")
+                .long("offset")
+                .allow_negative_numbers(true)
+                .value_parser(value_parser!(i64)),
+            Arg::new("ratio")
+                .long("ratio")
+                .value_parser(value_parser!(f64)),
+            Arg::new("cache")
+                .long("cache")
+                .value_parser(value_parser!(bool)),
+            Arg::new("level")
+                .long("level")
+                .value_parser(PossibleValuesParser::new(["1", "2"])),
+            Arg::new("ports")
+                .long("port")
+                .action(ArgAction::Append)
+                .value_parser(value_parser!(u16)),
+        ]);
+        let args = [
+            "test", "--size", "10", "--offset", "-5", "--ratio", "1.5", "--cache", "false",
+            "--level", "2", "--port", "80",
+        ];
+        let expected = Value::try_from(json!({
+            "size": 10,
+            "offset": -5,
+            "ratio": 1.5,
+            "cache": false,
+            "level": "2",
+            "ports": [80]
+        }))?;
+        let conf = ConfigBuilder::load_one(ParserBuilder::new(command).args(args).build()?)?;
+        assert_eq!(expected, *conf.get_value());
+        Ok(())
+    }
+
+    #[test]
+    fn bool_flags() -> AnyResult<()> {
+        use crate::parsers::cmd::BoolMode;
+
+        let command = Command::new("test").args([
+            Arg::new("enabled").short('e').action(ArgAction::SetTrue),
+            Arg::new("color").short('C').action(ArgAction::SetFalse),
+            Arg::new("verbose").short('v').action(ArgAction::Count),
+        ]);
+        let args = ["test", "-e", "-C", "-vv"];
+        let load = |mode| -> AnyResult<_> {
+            Ok(ConfigBuilder::load_one(
+                ParserBuilder::new(command.clone())
+                    .args(args)
+                    .bool_flags(mode)
+                    .build()?,
+            )?)
+        };
+
+        let expected = Value::try_from(json!({ "enabled": true, "color": false, "verbose": 2 }))?;
+        assert_eq!(expected, *load(BoolMode::Strict)?.get_value());
+        let expected =
+            Value::try_from(json!({ "enabled": true, "color": false, "verbose": true }))?;
+        assert_eq!(expected, *load(BoolMode::Presence)?.get_value());
+        Ok(())
+    }
+
     #[test]
     fn use_value_delimiter() -> AnyResult<()> {
         let expected = Value::try_from(json!({
@@ -590,4 +2664,69 @@ mod test_cmd {
     fn not_use_defaults_explicit_for_string() -> AnyResult<()> {
         not_use_defaults_for_string(true)
     }
+
+    #[test]
+    fn unknown_args_key() -> AnyResult<()> {
+        let expected = Value::try_from(json!({
+            "config": "config.toml",
+            "extra": ["plugin", "--flag", "value"]
+        }))?;
+        println!("expected: {expected:?}");
+
+        let command = Command::new("test")
+            .arg(Arg::new("config").short('c'))
+            .allow_external_subcommands(true);
+        let args = ["test", "-c", "config.toml", "plugin", "--flag", "value"];
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::new(command)
+                .args(args)
+                .unknown_args_key("extra")
+                .build()?,
+        )?;
+        let calculated = conf.get_value();
+        println!("calculated: {calculated:?}");
+        assert_eq!(expected, *calculated);
+        Ok(())
+    }
+
+    #[test]
+    fn absolute_paths() -> AnyResult<()> {
+        let cwd = std::env::current_dir()?;
+        let expected = Value::try_from(json!({
+            "config": cwd.join("config.toml"),
+            "data": cwd.join("data"),
+            "name": "data"
+        }))?;
+        println!("expected: {expected:?}");
+
+        let command = Command::new("test").args([
+            Arg::new("config")
+                .short('c')
+                .value_hint(ValueHint::FilePath),
+            Arg::new("data").short('d').value_hint(ValueHint::DirPath),
+            Arg::new("name").short('n'),
+        ]);
+        let args = ["test", "-c", "config.toml", "-d", "data", "-n", "data"];
+        let conf = ConfigBuilder::load_one(
+            ParserBuilder::new(command)
+                .args(args)
+                .absolute_paths(true)
+                .build()?,
+        )?;
+        let calculated = conf.get_value();
+        println!("calculated: {calculated:?}");
+        assert_eq!(expected, *calculated);
+        Ok(())
+    }
+
+    #[test]
+    fn build_no_exit() -> AnyResult<()> {
+        let command = Command::new("test").arg(Arg::new("name").short('n').required(true));
+        let result = ParserBuilder::new(command)
+            .args(["test"])
+            .exit_on_error(true)
+            .build_no_exit();
+        assert!(result.is_err_and(|e| matches!(e, crate::parsers::cmd::Error::Clap(..))));
+        Ok(())
+    }
 }
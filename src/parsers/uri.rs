@@ -0,0 +1,244 @@
+//! This module provide parser implementation which dispatches configuration source URI to handler registered for its
+//! scheme, so a single user-facing option (e.g. `--config` command-line argument) could point at any supported source
+//! type. The following schemes are registered by default (see [`Registry`]):
+//!
+//! | URI                                   | Source                                                       |
+//! |---------------------------------------|--------------------------------------------------------------|
+//! | `path/to/config.yaml`, `file://path`  | File, format detected by extension (see [`crate::parsers::auto`]) |
+//! | `env://NAME`                          | `JSON` document stored in `NAME` environment variable        |
+//! | `http://...`, `https://...`           | Document fetched by `GET` request, format detected by extension of URL path (`uri-http` feature) |
+//!
+//! Other schemes (e.g. `s3://`) could be added by [`Registry::scheme`] or [`Registry::remote_scheme`]. The URIs with
+//! remote schemes (e.g. `http://`) are not fetched in offline mode (see [`crate::ConfigBuilder::offline`]).
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["uri", "yaml"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::uri::{ParserBuilder, Registry};
+//!
+//! let registry = Registry::default().scheme("s3", |location: &str| fetch_from_s3(location));
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .default_uri("file:///etc/app/config.yaml")
+//!             .uri_option("config")
+//!             .registry(registry)
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{
+    parsers::{auto, try_open_file},
+    AnyResult, Case, Parse, Value, DEFAULT_KEYS_SEPARATOR,
+};
+use derive_builder::Builder;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env::{self, VarError},
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+/// The scheme of URIs without scheme.
+pub const DEFAULT_SCHEME: &str = "file";
+
+const SCHEME_DELIMITER: &str = "://";
+
+type Handler = Box<dyn FnMut(&str) -> AnyResult<Value>>;
+
+/// All errors for URI parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to get URI by option: '{1}'")]
+    UriOption(#[source] crate::Error, String),
+    #[error("Unsupported scheme of URI: '{0}'")]
+    UnknownScheme(String),
+    #[error("Failed to get environment variable: '{1}'")]
+    EnvVar(#[source] VarError, String),
+    #[error("Failed parse JSON from environment variable: '{1}'")]
+    ParseJson(#[source] serde_json::Error, String),
+    #[cfg(feature = "uri-http")]
+    #[error("Failed to fetch URI: '{1}'")]
+    Request(#[source] ureq::Error, String),
+}
+
+/// The registry of handlers for URI schemes. Each handler gets location part of URI (after `://`) and returns parsed
+/// [`Value`].
+pub struct Registry {
+    handlers: BTreeMap<String, Handler>,
+    remote: BTreeSet<String>,
+}
+
+impl Registry {
+    /// Create empty registry without any schemes.
+    #[inline]
+    pub fn empty() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+            remote: BTreeSet::new(),
+        }
+    }
+
+    /// Register handler for given scheme (without `://`). The previous handler of the scheme (if any) is replaced.
+    #[inline]
+    pub fn scheme<S, F>(mut self, name: S, handler: F) -> Self
+    where
+        S: Into<String>,
+        F: FnMut(&str) -> AnyResult<Value> + 'static,
+    {
+        let name = name.into();
+        self.remote.remove(&name);
+        self.handlers.insert(name, Box::new(handler));
+        self
+    }
+
+    /// Same as [`Registry::scheme`], but the scheme fetches data over network, so URIs with such scheme will not be
+    /// fetched in offline mode (see [`Parse::is_remote`]).
+    #[inline]
+    pub fn remote_scheme<S, F>(self, name: S, handler: F) -> Self
+    where
+        S: Into<String>,
+        F: FnMut(&str) -> AnyResult<Value> + 'static,
+    {
+        let name = name.into();
+        let mut registry = self.scheme(name.clone(), handler);
+        registry.remote.insert(name);
+        registry
+    }
+
+    /// Return `true` if handler for given scheme is registered, otherwise return `false`.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// Return `true` if given scheme is registered as remote (see [`Registry::remote_scheme`]), otherwise return
+    /// `false`.
+    #[inline]
+    pub fn is_remote(&self, name: &str) -> bool {
+        self.remote.contains(name)
+    }
+
+    fn fetch(&mut self, uri: &str) -> AnyResult<Value> {
+        let (scheme, location) = split_uri(uri);
+        let handler = self
+            .handlers
+            .get_mut(scheme)
+            .ok_or_else(|| Error::UnknownScheme(uri.into()))?;
+        handler(location)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let registry = Self::empty()
+            .scheme(DEFAULT_SCHEME, load_file)
+            .scheme("env", load_env);
+        #[cfg(feature = "uri-http")]
+        let registry = registry
+            .remote_scheme("http", |l: &str| load_http(&format!("http://{l}")))
+            .remote_scheme("https", |l: &str| load_http(&format!("https://{l}")));
+        registry
+    }
+}
+
+/// The URI parser implementation.
+#[derive(Builder)]
+#[builder(setter(into, strip_option), pattern = "owned")]
+pub struct Parser {
+    /// Set default URI of the source to be parsed.
+    default_uri: String,
+    /// Set URI option name which could be used to get URI value from previous parsing [`Value`] results.
+    #[builder(default = "None")]
+    uri_option: Option<String>,
+    /// Set delimiter used to separate keys levels in URI option. Default is [`DEFAULT_KEYS_SEPARATOR`].
+    #[builder(default = "DEFAULT_KEYS_SEPARATOR.to_string()")]
+    keys_delimiter: String,
+    /// Set registry of URI schemes handlers. Default is [`Registry::default`].
+    #[builder(default)]
+    registry: Registry,
+    #[builder(setter(skip))]
+    last_uri: Option<String>,
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, value: &Value) -> AnyResult<Value> {
+        let uri = self.uri(value)?;
+        let result = self.registry.fetch(&uri);
+        self.last_uri = Some(uri);
+        result
+    }
+
+    /// Return `true` if scheme of URI to be parsed (from URI option or default one) is remote (see
+    /// [`Registry::remote_scheme`]). If URI option could not be read then `false` is returned, so the error will be
+    /// reported by [`Parse::parse`].
+    #[inline]
+    fn is_remote(&self, value: &Value) -> bool {
+        self.uri(value)
+            .is_ok_and(|uri| self.registry.is_remote(split_uri(&uri).0))
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.last_uri
+            .as_deref()
+            .map(split_uri)
+            .filter(|(s, _)| DEFAULT_SCHEME == *s)
+            .map(|(_, l)| PathBuf::from(l))
+            .into_iter()
+            .collect()
+    }
+
+    #[inline]
+    fn depends_on(&self) -> Vec<String> {
+        self.uri_option.iter().cloned().collect()
+    }
+}
+
+impl Parser {
+    fn uri(&self, value: &Value) -> Result<String, Error> {
+        let uri = match self.uri_option {
+            Some(ref option) => value
+                .get_by_key_path_with_delim(option, &self.keys_delimiter)
+                .map_err(|e| Error::UriOption(e, option.clone()))?,
+            None => None,
+        };
+        Ok(uri.unwrap_or_else(|| self.default_uri.clone()))
+    }
+}
+
+fn split_uri(uri: &str) -> (&str, &str) {
+    uri.split_once(SCHEME_DELIMITER)
+        .unwrap_or((DEFAULT_SCHEME, uri))
+}
+
+fn load_file(location: &str) -> AnyResult<Value> {
+    let path = Path::new(location);
+    let file = try_open_file(path)?;
+    auto::load(path, BufReader::new(file))
+}
+
+fn load_env(name: &str) -> AnyResult<Value> {
+    let data = env::var(name).map_err(|e| Error::EnvVar(e, name.into()))?;
+    Ok(serde_json::from_str(&data).map_err(|e| Error::ParseJson(e, name.into()))?)
+}
+
+#[cfg(feature = "uri-http")]
+fn load_http(url: &str) -> AnyResult<Value> {
+    let mut response = ureq::get(url)
+        .call()
+        .map_err(|e| Error::Request(e, url.into()))?;
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    auto::load(Path::new(path), response.body_mut().as_reader())
+}
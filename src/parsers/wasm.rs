@@ -0,0 +1,169 @@
+//! This module provide host of parser plugins compiled to [WebAssembly](https://webassembly.org/) modules, so custom
+//! (e.g. proprietary) configuration sources could be shipped separately and loaded at runtime without recompiling the
+//! application. The modules are executed by [wasmtime](https://wasmtime.dev/) in a sandbox without any imports (no
+//! file system, network or clock access), the plugin gets all its data from the host.
+//!
+//! The plugin module has to export the following items:
+//!
+//! | Export      | Signature                         | Description                                                   |
+//! |-------------|-----------------------------------|---------------------------------------------------------------|
+//! | `memory`    | memory                            | Linear memory used to exchange data                           |
+//! | `irx_alloc` | `(len: i32) -> i32`               | Allocate `len` bytes for input and return their pointer       |
+//! | `irx_parse` | `(ptr: i32, len: i32) -> i64`     | Parse and return result as `(ptr << 32) \| len`               |
+//!
+//! The input of `irx_parse` is `JSON` representation of the merged [`Value`] results from previous parsers (see
+//! [`Parse::parse`]), the result is `JSON` document to be used as parsed [`Value`]. The module is instantiated on the
+//! first parse and the instance is reused for subsequent parses (e.g. on reload), so the plugin could keep its state.
+//!
+//! To enable that parser one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["wasm"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::parsers::wasm::ParserBuilder;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(
+//!         ParserBuilder::default()
+//!             .path("/usr/lib/app/plugins/vault.wasm")
+//!             .fuel(10_000_000u64)
+//!             .build()?,
+//!     )
+//!     .load()?;
+//! ```
+
+use crate::{AnyError, AnyResult, Case, Parse, Value};
+use derive_builder::Builder;
+use std::path::PathBuf;
+use wasmtime::{Config as EngineConfig, Engine, Instance, Memory, Module, Store, TypedFunc};
+
+/// The name of plugin's memory export.
+pub const MEMORY_EXPORT: &str = "memory";
+
+/// The name of plugin's allocation function export.
+pub const ALLOC_EXPORT: &str = "irx_alloc";
+
+/// The name of plugin's parse function export.
+pub const PARSE_EXPORT: &str = "irx_parse";
+
+/// All errors for WASM plugin parser.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to {1} WASM plugin: '{2}'")]
+    Wasm(#[source] AnyError, &'static str, PathBuf),
+    #[error("Missing '{0}' export of WASM plugin: '{1}'")]
+    MissingExport(&'static str, PathBuf),
+    #[error("Out of bounds memory access by WASM plugin: '{0}'")]
+    OutOfBounds(PathBuf),
+    #[error("Failed to {1} JSON of WASM plugin: '{2}'")]
+    Json(#[source] serde_json::Error, &'static str, PathBuf),
+}
+
+struct Plugin {
+    store: Store<()>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    parse: TypedFunc<(i32, i32), i64>,
+}
+
+/// The WASM plugin parser implementation.
+#[derive(Builder)]
+#[builder(setter(into, strip_option))]
+pub struct Parser {
+    /// Set path to the plugin module in binary (`.wasm`) or text (`.wat`) format.
+    path: PathBuf,
+    /// Set amount of fuel (roughly the number of executed instructions) available to the plugin for instantiation
+    /// and each parse, so a faulty plugin could not hang the application. Default is unlimited.
+    #[builder(default)]
+    fuel: Option<u64>,
+    #[builder(setter(skip))]
+    plugin: Option<Plugin>,
+}
+
+impl Parser {
+    fn wasm_err(&self, action: &'static str) -> impl Fn(wasmtime::Error) -> Error + '_ {
+        move |e| Error::Wasm(e.into(), action, self.path.clone())
+    }
+
+    fn refuel(&self, store: &mut Store<()>) -> Result<(), Error> {
+        match self.fuel {
+            Some(fuel) => store.set_fuel(fuel).map_err(self.wasm_err("refuel")),
+            None => Ok(()),
+        }
+    }
+
+    fn instantiate(&self) -> Result<Plugin, Error> {
+        let mut config = EngineConfig::new();
+        config.consume_fuel(self.fuel.is_some());
+        let engine = Engine::new(&config).map_err(self.wasm_err("configure engine for"))?;
+        let module = Module::from_file(&engine, &self.path).map_err(self.wasm_err("load"))?;
+        let mut store = Store::new(&engine, ());
+        self.refuel(&mut store)?;
+        let instance =
+            Instance::new(&mut store, &module, &[]).map_err(self.wasm_err("instantiate"))?;
+        let memory = instance
+            .get_memory(&mut store, MEMORY_EXPORT)
+            .ok_or_else(|| Error::MissingExport(MEMORY_EXPORT, self.path.clone()))?;
+        let alloc = instance
+            .get_typed_func(&mut store, ALLOC_EXPORT)
+            .map_err(self.wasm_err("link"))?;
+        let parse = instance
+            .get_typed_func(&mut store, PARSE_EXPORT)
+            .map_err(self.wasm_err("link"))?;
+        Ok(Plugin {
+            store,
+            memory,
+            alloc,
+            parse,
+        })
+    }
+
+    fn call(&self, plugin: &mut Plugin, input: &[u8]) -> Result<Vec<u8>, Error> {
+        let out_of_bounds = || Error::OutOfBounds(self.path.clone());
+        let len = i32::try_from(input.len()).map_err(|_| out_of_bounds())?;
+        self.refuel(&mut plugin.store)?;
+        let ptr = plugin
+            .alloc
+            .call(&mut plugin.store, len)
+            .map_err(self.wasm_err("call"))?;
+        plugin
+            .memory
+            .write(&mut plugin.store, ptr as u32 as usize, input)
+            .map_err(|_| out_of_bounds())?;
+        let result = plugin
+            .parse
+            .call(&mut plugin.store, (ptr, len))
+            .map_err(self.wasm_err("call"))? as u64;
+
+        let (ptr, len) = ((result >> 32) as usize, (result & 0xffff_ffff) as usize);
+        let data = plugin.memory.data(&plugin.store);
+        ptr.checked_add(len)
+            .and_then(|end| data.get(ptr..end))
+            .map(<[u8]>::to_vec)
+            .ok_or_else(out_of_bounds)
+    }
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, value: &Value) -> AnyResult<Value> {
+        let input = serde_json::to_vec(value)
+            .map_err(|e| Error::Json(e, "serialize input for", self.path.clone()))?;
+        let mut plugin = match self.plugin.take() {
+            Some(plugin) => plugin,
+            None => self.instantiate()?,
+        };
+        let output = self.call(&mut plugin, &input);
+        self.plugin = Some(plugin);
+        Ok(serde_json::from_slice(&output?)
+            .map_err(|e| Error::Json(e, "parse output of", self.path.clone()))?)
+    }
+}
@@ -0,0 +1,356 @@
+//! This module provide C ABI interface of parser plugins compiled as dynamic libraries (`cdylib` crates), so custom
+//! (e.g. proprietary) configuration sources could be shipped separately, discovered in plugins directory and appended
+//! by name at runtime without recompiling the application. See also [`crate::parsers::wasm`] for sandboxed plugins.
+//!
+//! The plugin library has to export the following functions, the [`export_plugin!`](crate::export_plugin) macro
+//! generates all of them:
+//!
+//! | Function                 | Signature                                                | Description                 |
+//! |--------------------------|----------------------------------------------------------|-----------------------------|
+//! | `irx_plugin_abi_version` | `fn() -> u32`                                            | Must return [`ABI_VERSION`] |
+//! | `irx_plugin_name`        | `fn() -> *const c_char`                                  | Static nul-terminated name  |
+//! | `irx_plugin_parse`       | `fn(*const u8, usize, *mut *mut u8, *mut usize) -> i32`  | Parse, see below            |
+//! | `irx_plugin_free`        | `fn(*mut u8, usize)`                                     | Free output of parse        |
+//!
+//! The `irx_plugin_parse` gets `JSON` representation of the merged [`Value`] results from previous parsers (see
+//! [`Parse::parse`]) and stores pointer and length of output buffer allocated by plugin. On success it returns `0` and
+//! the output is `JSON` document to be used as parsed [`Value`], otherwise the output is error message. The output
+//! buffer is released by `irx_plugin_free`. The ABI version is checked on load, so incompatible plugins are rejected.
+//!
+//! To enable that module one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["plugin"] }
+//! ```
+//!
+//! # Example
+//!
+//! The plugin crate (`crate-type = ["cdylib"]`):
+//!
+//! ```
+//! use irx_config::{json, AnyResult, Value};
+//!
+//! fn parse(_value: &Value) -> AnyResult<Value> {
+//!     Ok(Value::try_from(json!({ "vault": { "token": fetch_token()? } }))?)
+//! }
+//!
+//! irx_config::export_plugin!("vault", parse);
+//! ```
+//!
+//! The application:
+//!
+//! ```
+//! use irx_config::ConfigBuilder;
+//! use irx_config::plugin::Plugins;
+//!
+//! // SAFETY: the plugins directory contains only trusted plugins.
+//! let plugins = unsafe { Plugins::discover("/usr/lib/app/plugins")? };
+//! let config = ConfigBuilder::default()
+//!     .append_parser(plugins.parser("vault")?)
+//!     .load()?;
+//! ```
+
+use crate::{AnyError, AnyResult, Case, Parse, Value};
+use libloading::Library;
+use std::{
+    collections::BTreeMap,
+    env::consts::DLL_EXTENSION,
+    ffi::{c_char, CStr},
+    fs,
+    io::Error as IoError,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    ptr, slice,
+    sync::Arc,
+};
+
+/// The version of plugin ABI supported by this crate.
+pub const ABI_VERSION: u32 = 1;
+
+/// The signature of `irx_plugin_abi_version` function.
+pub type AbiVersionFn = unsafe extern "C" fn() -> u32;
+
+/// The signature of `irx_plugin_name` function.
+pub type NameFn = unsafe extern "C" fn() -> *const c_char;
+
+/// The signature of `irx_plugin_parse` function.
+pub type ParseFn = unsafe extern "C" fn(*const u8, usize, *mut *mut u8, *mut usize) -> i32;
+
+/// The signature of `irx_plugin_free` function.
+pub type FreeFn = unsafe extern "C" fn(*mut u8, usize);
+
+/// All errors for plugins.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to read plugins directory: '{1}'")]
+    ReadDir(#[source] IoError, PathBuf),
+    #[error("Failed to load plugin: '{1}'")]
+    Load(#[source] libloading::Error, PathBuf),
+    #[error("Unsupported ABI version {0} of plugin (expected {ABI_VERSION}): '{1}'")]
+    AbiVersion(u32, PathBuf),
+    #[error("Invalid name of plugin: '{0}'")]
+    InvalidName(PathBuf),
+    #[error("Plugin '{0}' is provided by both '{1}' and '{2}'")]
+    Duplicate(String, PathBuf, PathBuf),
+    #[error("Plugin '{0}' is not found")]
+    MissingPlugin(String),
+    #[error("Plugin '{1}' failed: {0}")]
+    Parse(String, String),
+    #[error("Failed to {1} JSON of plugin: '{2}'")]
+    Json(#[source] serde_json::Error, &'static str, String),
+}
+
+struct Plugin {
+    path: PathBuf,
+    parse: ParseFn,
+    free: FreeFn,
+    library: Arc<Library>,
+}
+
+/// The set of loaded plugins.
+#[derive(Default)]
+pub struct Plugins {
+    plugins: BTreeMap<String, Plugin>,
+}
+
+impl Plugins {
+    /// Load all plugins (files with platform's dynamic library extension, e.g. `.so`) from given directory in sorted
+    /// order of their paths.
+    ///
+    /// # Safety
+    ///
+    /// The plugins are loaded as dynamic libraries, so their initialization code is executed. They have to be trusted
+    /// and implement plugin ABI properly (e.g. be built with [`export_plugin!`](crate::export_plugin)).
+    ///
+    /// # Errors
+    ///
+    /// If directory could not be read, any plugin could not be loaded or plugins names are not unique then error will
+    /// be returned.
+    pub unsafe fn discover<P: AsRef<Path>>(dir: P) -> Result<Self, Error> {
+        let dir = dir.as_ref();
+        let err = |e| Error::ReadDir(e, dir.to_path_buf());
+        let mut paths = fs::read_dir(dir)
+            .map_err(err)?
+            .map(|e| e.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(err)?;
+        paths.retain(|p| p.is_file() && p.extension().is_some_and(|e| e == DLL_EXTENSION));
+        paths.sort();
+
+        let mut plugins = Self::default();
+        for path in paths {
+            plugins.load(path)?;
+        }
+        Ok(plugins)
+    }
+
+    /// Load plugin from given dynamic library and return its name.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`Plugins::discover`].
+    ///
+    /// # Errors
+    ///
+    /// If plugin could not be loaded, its ABI version is not supported or plugin with the same name is already loaded
+    /// then error will be returned.
+    pub unsafe fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<String, Error> {
+        let path = path.as_ref().to_path_buf();
+        let err = |e| Error::Load(e, path.clone());
+        let library = Library::new(&path).map_err(err)?;
+        let version = library
+            .get::<AbiVersionFn>(b"irx_plugin_abi_version\0")
+            .map_err(err)?();
+        if version != ABI_VERSION {
+            return Err(Error::AbiVersion(version, path));
+        }
+
+        let name = library.get::<NameFn>(b"irx_plugin_name\0").map_err(err)?();
+        let name = if name.is_null() {
+            None
+        } else {
+            CStr::from_ptr(name).to_str().ok().map(str::to_string)
+        };
+        let name = name.ok_or_else(|| Error::InvalidName(path.clone()))?;
+        let parse = *library.get::<ParseFn>(b"irx_plugin_parse\0").map_err(err)?;
+        let free = *library.get::<FreeFn>(b"irx_plugin_free\0").map_err(err)?;
+        if let Some(plugin) = self.plugins.get(&name) {
+            return Err(Error::Duplicate(name, plugin.path.clone(), path));
+        }
+
+        let plugin = Plugin {
+            path,
+            parse,
+            free,
+            library: Arc::new(library),
+        };
+        self.plugins.insert(name.clone(), plugin);
+        Ok(name)
+    }
+
+    /// Return names of all loaded plugins in sorted order.
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.plugins.keys().map(String::as_str)
+    }
+
+    /// Create parser of given plugin.
+    ///
+    /// # Errors
+    ///
+    /// If plugin is not loaded then error will be returned.
+    pub fn parser(&self, name: &str) -> Result<Parser, Error> {
+        let plugin = self
+            .plugins
+            .get(name)
+            .ok_or_else(|| Error::MissingPlugin(name.into()))?;
+        Ok(Parser {
+            name: name.into(),
+            parse: plugin.parse,
+            free: plugin.free,
+            _library: Some(plugin.library.clone()),
+        })
+    }
+}
+
+/// The plugin parser implementation.
+pub struct Parser {
+    name: String,
+    parse: ParseFn,
+    free: FreeFn,
+    _library: Option<Arc<Library>>,
+}
+
+impl Parser {
+    /// Create parser from functions of plugin linked into the application (e.g. generated by
+    /// [`export_plugin!`](crate::export_plugin) in the same binary).
+    ///
+    /// # Safety
+    ///
+    /// The functions have to implement plugin ABI properly.
+    #[inline]
+    pub unsafe fn from_exports<S: Into<String>>(name: S, parse: ParseFn, free: FreeFn) -> Self {
+        Self {
+            name: name.into(),
+            parse,
+            free,
+            _library: None,
+        }
+    }
+}
+
+impl Case for Parser {}
+
+impl Parse for Parser {
+    fn parse(&mut self, value: &Value) -> AnyResult<Value> {
+        let input = serde_json::to_vec(value)
+            .map_err(|e| Error::Json(e, "serialize input for", self.name.clone()))?;
+        let (mut data, mut len) = (ptr::null_mut(), 0);
+        // SAFETY: the functions implement plugin ABI (see `Plugins::load` and `Parser::from_exports`).
+        let code = unsafe { (self.parse)(input.as_ptr(), input.len(), &mut data, &mut len) };
+        let output = if data.is_null() {
+            Vec::new()
+        } else {
+            // SAFETY: the output buffer is owned by plugin until it is released.
+            unsafe {
+                let output = slice::from_raw_parts(data, len).to_vec();
+                (self.free)(data, len);
+                output
+            }
+        };
+
+        if code != 0 {
+            let message = String::from_utf8_lossy(&output).into_owned();
+            return Err(Error::Parse(message, self.name.clone()).into());
+        }
+        Ok(serde_json::from_slice(&output)
+            .map_err(|e| Error::Json(e, "parse output of", self.name.clone()))?)
+    }
+}
+
+/// Implementation of `irx_plugin_parse` function used by [`export_plugin!`](crate::export_plugin).
+///
+/// # Safety
+///
+/// The arguments have to follow plugin ABI.
+#[doc(hidden)]
+pub unsafe fn export_parse<F>(
+    input: *const u8,
+    input_len: usize,
+    output: *mut *mut u8,
+    output_len: *mut usize,
+    parse: F,
+) -> i32
+where
+    F: FnOnce(&Value) -> AnyResult<Value>,
+{
+    let input = if input.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| -> AnyResult<Vec<u8>> {
+        let value: Value = serde_json::from_slice(input)?;
+        Ok(serde_json::to_vec(&parse(&value)?)?)
+    }))
+    .unwrap_or_else(|_| Err(AnyError::from("plugin panicked")));
+
+    let (code, data) = match result {
+        Ok(data) => (0, data),
+        Err(e) => (1, e.to_string().into_bytes()),
+    };
+    let data = data.into_boxed_slice();
+    *output_len = data.len();
+    *output = Box::into_raw(data).cast();
+    code
+}
+
+/// Implementation of `irx_plugin_free` function used by [`export_plugin!`](crate::export_plugin).
+///
+/// # Safety
+///
+/// The buffer has to be returned by [`export_parse`].
+#[doc(hidden)]
+pub unsafe fn export_free(data: *mut u8, len: usize) {
+    drop(Box::from_raw(ptr::slice_from_raw_parts_mut(data, len)));
+}
+
+/// Export plugin ABI functions from `cdylib` crate for given plugin name (string literal) and parse function
+/// (`fn(&Value) -> AnyResult<Value>`).
+#[macro_export]
+macro_rules! export_plugin {
+    ($name:literal, $parse:expr) => {
+        #[no_mangle]
+        pub extern "C" fn irx_plugin_abi_version() -> u32 {
+            $crate::plugin::ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub extern "C" fn irx_plugin_name() -> *const ::std::ffi::c_char {
+            concat!($name, "\0").as_ptr().cast()
+        }
+
+        /// # Safety
+        ///
+        /// The arguments have to follow plugin ABI.
+        #[no_mangle]
+        pub unsafe extern "C" fn irx_plugin_parse(
+            input: *const u8,
+            input_len: usize,
+            output: *mut *mut u8,
+            output_len: *mut usize,
+        ) -> i32 {
+            $crate::plugin::export_parse(input, input_len, output, output_len, $parse)
+        }
+
+        /// # Safety
+        ///
+        /// The buffer has to be returned by `irx_plugin_parse`.
+        #[no_mangle]
+        pub unsafe extern "C" fn irx_plugin_free(data: *mut u8, len: usize) {
+            $crate::plugin::export_free(data, len)
+        }
+    };
+}
@@ -0,0 +1,184 @@
+//! This module provide one-line constructors of [`ConfigBuilder`] with parsers set for common application stacks.
+//! Each preset has own feature which enables all required parsers, the returned [`ConfigBuilder`] could be extended
+//! with other parsers or options before load.
+//!
+//! | Feature          | Constructor  | Parsers (in precedence order)                                           |
+//! |------------------|--------------|-------------------------------------------------------------------------|
+//! | `preset-cli`     | [`cli`]      | command-line, environment variables, `TOML` file                        |
+//! | `preset-service` | [`service`]  | environment variables, `YAML` file (could be watched by `ConfigWatcher`) |
+//! | `preset-k8s`     | [`k8s`]      | environment variables, mounted `Secret`, mounted `ConfigMap` (`YAML`)    |
+//!
+//! To enable preset one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["preset-service"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::presets;
+//!
+//! let config = presets::service("APP_", "/etc/app/config.yaml")?.load()?;
+//! ```
+
+use crate::{AnyError, ConfigBuilder};
+use std::{path::PathBuf, result::Result as StdResult};
+
+/// A result type for presets errors.
+pub type Result<T> = StdResult<T, Error>;
+
+/// The option name which could be used to override default path of configuration file (e.g. `--config` command-line
+/// argument or `APP_CONFIG` environment variable).
+pub const PATH_OPTION: &str = "config";
+
+/// The sealed suffix appended to secrets by [`k8s`] preset.
+#[cfg(feature = "preset-k8s")]
+pub const SEALED_SUFFIX: &str = "_sealed_";
+
+/// All errors for presets.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to build {1} parser")]
+    Build(#[source] AnyError, &'static str),
+}
+
+/// Create [`ConfigBuilder`] for command-line application. The values of command-line arguments take precedence over
+/// environment variables with given prefix, which take precedence over optional `TOML` file. The path of the file
+/// could be overridden by [`PATH_OPTION`].
+///
+/// # Errors
+///
+/// If any parser could not be built then error will be returned.
+///
+/// # Example
+///
+/// ```
+/// use clap::command;
+/// use irx_config::presets;
+///
+/// let config = presets::cli(command!(), "APP_", "config.toml")?.load()?;
+/// ```
+#[cfg(feature = "preset-cli")]
+pub fn cli<S, P>(command: clap::Command, env_prefix: S, default_path: P) -> Result<ConfigBuilder>
+where
+    S: Into<String>,
+    P: Into<PathBuf>,
+{
+    use crate::parsers::{cmd, env, toml};
+
+    Ok(ConfigBuilder::default()
+        .append_parser(
+            cmd::ParserBuilder::new(command)
+                .exit_on_error(true)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "command-line"))?,
+        )
+        .append_parser(
+            env::ParserBuilder::default()
+                .default_prefix(env_prefix)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "environment variables"))?,
+        )
+        .append_parser(
+            toml::ParserBuilder::default()
+                .default_path(default_path)
+                .path_option(PATH_OPTION)
+                .ignore_missing_file(true)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "TOML"))?,
+        ))
+}
+
+/// Create [`ConfigBuilder`] for service. The environment variables with given prefix take precedence over optional
+/// `YAML` file. The path of the file could be overridden by [`PATH_OPTION`].
+///
+/// # Errors
+///
+/// If any parser could not be built then error will be returned.
+///
+/// # Example
+///
+/// ```
+/// use irx_config::{presets, watch::ConfigWatcher};
+///
+/// let config = presets::service("APP_", "/etc/app/config.yaml")?.load()?;
+/// let watcher = ConfigWatcher::new(config)?;
+/// ```
+#[cfg(feature = "preset-service")]
+pub fn service<S, P>(env_prefix: S, default_path: P) -> Result<ConfigBuilder>
+where
+    S: Into<String>,
+    P: Into<PathBuf>,
+{
+    use crate::parsers::{env, yaml};
+
+    Ok(ConfigBuilder::default()
+        .append_parser(
+            env::ParserBuilder::default()
+                .default_prefix(env_prefix)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "environment variables"))?,
+        )
+        .append_parser(
+            yaml::ParserBuilder::default()
+                .default_path(default_path)
+                .path_option(PATH_OPTION)
+                .ignore_missing_file(true)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "YAML"))?,
+        ))
+}
+
+/// Create [`ConfigBuilder`] for Kubernetes workload. The environment variables with given prefix take precedence
+/// over `Secret` mounted as directory, which takes precedence over `ConfigMap` mounted as directory of `YAML` files.
+/// The secret files are handled same way as `systemd` credentials (see [`crate::parsers::systemd`]) and sealed with
+/// [`SEALED_SUFFIX`]. The missing directories are ignored.
+///
+/// # Errors
+///
+/// If any parser could not be built then error will be returned.
+///
+/// # Example
+///
+/// ```
+/// use irx_config::presets;
+///
+/// let config = presets::k8s("APP_", "/etc/app/config", "/etc/app/secrets")?.load()?;
+/// ```
+#[cfg(feature = "preset-k8s")]
+pub fn k8s<S, C, P>(env_prefix: S, config_dir: C, secrets_dir: P) -> Result<ConfigBuilder>
+where
+    S: Into<String>,
+    C: Into<PathBuf>,
+    P: Into<PathBuf>,
+{
+    use crate::parsers::{dir, env, systemd::CredentialsParserBuilder, yaml::LoadYaml};
+
+    Ok(ConfigBuilder::default()
+        .append_parser(
+            env::ParserBuilder::default()
+                .default_prefix(env_prefix)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "environment variables"))?,
+        )
+        .append_parser(
+            CredentialsParserBuilder::default()
+                .directory(secrets_dir)
+                .sealed_suffix(SEALED_SUFFIX)
+                .ignore_missing(true)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "secrets"))?,
+        )
+        .append_parser(
+            dir::ParserBuilder::<LoadYaml>::default()
+                .directory(config_dir)
+                .extension("yaml")
+                .ignore_missing(true)
+                .build()
+                .map_err(|e| Error::Build(e.into(), "config map"))?,
+        )
+        .sealed_suffix(SEALED_SUFFIX))
+}
@@ -0,0 +1,67 @@
+//! This module provide conversion of configuration data into protobuf messages (via
+//! [prost-reflect](https://github.com/andrewhickman/prost-reflect)), so configuration schema could be defined in
+//! `.proto` file. The configuration data is mapped to message fields according to the canonical protobuf `JSON`
+//! mapping, the unknown keys are ignored.
+//!
+//! To enable that functionality one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["protobuf"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::proto;
+//!
+//! // `AppConfig` is a prost-generated message which implements `ReflectMessage` trait.
+//! let app: AppConfig = proto::to_message(config.get_value())?;
+//! ```
+
+use crate::Value;
+use prost_reflect::{
+    prost::DecodeError, DeserializeOptions, DynamicMessage, MessageDescriptor, ReflectMessage,
+};
+use std::result::Result as StdResult;
+
+/// A result type for protobuf conversion errors.
+pub type Result<T> = StdResult<T, Error>;
+
+/// All errors for protobuf conversion.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to map value to message: '{1}'")]
+    Deserialize(#[source] serde_json::Error, String),
+    #[error("Failed to transcode message: '{1}'")]
+    Transcode(#[source] DecodeError, String),
+}
+
+/// Convert [`Value`] structure to dynamic message with given descriptor.
+///
+/// # Errors
+///
+/// If value could not be mapped to the message then error will be returned.
+pub fn to_dynamic_message(value: &Value, descriptor: MessageDescriptor) -> Result<DynamicMessage> {
+    let name = descriptor.full_name().to_string();
+    let options = DeserializeOptions::new().deny_unknown_fields(false);
+    DynamicMessage::deserialize_with_options(descriptor, value.as_inner(), &options)
+        .map_err(|e| Error::Deserialize(e, name))
+}
+
+/// Convert [`Value`] structure to prost-generated message which implements [`ReflectMessage`] trait.
+///
+/// # Errors
+///
+/// If value could not be mapped to the message then error will be returned.
+pub fn to_message<M>(value: &Value) -> Result<M>
+where
+    M: ReflectMessage + Default,
+{
+    let descriptor = M::default().descriptor();
+    let name = descriptor.full_name().to_string();
+    to_dynamic_message(value, descriptor)?
+        .transcode_to()
+        .map_err(|e| Error::Transcode(e, name))
+}
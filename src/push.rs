@@ -0,0 +1,391 @@
+//! This module provide [`PushLayer`] parser which holds in-memory configuration data pushed from outside of the
+//! process via Unix domain socket served by [`PushServer`]. It is a building block for control tooling like
+//! `myappctl set key value`.
+//!
+//! The [`PushLayer`] should be usually appended first to [`crate::ConfigBuilder`] to have highest priority. The
+//! [`PushServer`] accepts connections and reads requests line by line, each request is `JSON` object:
+//!
+//! * `{"set": "logger:level", "value": "debug"}` -- set value for given key path.
+//! * `{"merge": {"logger": {"level": "debug"}}}` -- merge given document into pushed one (see
+//!   [`PushLayer::merge`]).
+//! * `{"replace": {"logger": {"level": "debug"}}}` -- replace whole pushed document.
+//!
+//! For each request the reply line `{"ok":true}` or `{"ok":false,"error":"..."}` will be sent back. After each
+//! successfully applied request, the callback given to [`PushServer::bind`] will be called, so application could
+//! trigger [`crate::Config::reload`].
+//!
+//! The socket file is accessible by its owner only. The number of concurrently served connections, the length of
+//! request and the time to wait for it are limited by default, see [`PushOptions`] to change that.
+//!
+//! To enable that module one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["push"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::push::{PushLayer, PushServer};
+//! use irx_config::ConfigBuilder;
+//! use std::sync::mpsc;
+//!
+//! let layer = PushLayer::default();
+//! let (tx, rx) = mpsc::channel();
+//! let _server = PushServer::bind("/run/myapp/config.sock", layer.clone(), move || {
+//!     let _ = tx.send(());
+//! })?;
+//!
+//! let mut config = ConfigBuilder::default().append_parser(layer).load()?;
+//! while rx.recv().is_ok() {
+//!     config.reload()?;
+//! }
+//! ```
+
+use crate::{json, AnyResult, Case, Parse, Value, DEFAULT_KEYS_SEPARATOR};
+use serde::Deserialize;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Error as IoError, ErrorKind, Read, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// The default permissions of socket file: read/write for owner only.
+pub const DEFAULT_SOCKET_MODE: u32 = 0o600;
+
+/// The default maximum number of concurrently served connections.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 8;
+
+/// The default maximum length of request line in bytes.
+pub const DEFAULT_MAX_REQUEST_LEN: usize = 1024 * 1024;
+
+/// The default timeout to read request or write reply.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// All errors for push layer and server.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to bind Unix socket: '{1}'")]
+    Bind(#[source] IoError, PathBuf),
+    #[error("Failed to parse request")]
+    Request(#[source] serde_json::Error),
+    #[error("Failed to set value for key path: '{1}'")]
+    Value(#[source] crate::Error, String),
+}
+
+/// The options for [`PushServer::bind_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushOptions {
+    /// Permissions of socket file. Default is [`DEFAULT_SOCKET_MODE`].
+    pub mode: u32,
+    /// Maximum number of concurrently served connections, the connections above that limit are answered with error
+    /// reply and closed. Default is [`DEFAULT_MAX_CONNECTIONS`].
+    pub max_connections: usize,
+    /// Maximum length of request line in bytes, the connection is answered with error reply and closed if request
+    /// is longer. Default is [`DEFAULT_MAX_REQUEST_LEN`].
+    pub max_request_len: usize,
+    /// Timeout to read request or write reply, the idle connection is closed after it. Default is
+    /// [`DEFAULT_TIMEOUT`].
+    pub timeout: Duration,
+}
+
+impl Default for PushOptions {
+    fn default() -> Self {
+        Self {
+            mode: DEFAULT_SOCKET_MODE,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            max_request_len: DEFAULT_MAX_REQUEST_LEN,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Request {
+    Set { set: String, value: Value },
+    Merge { merge: Value },
+    Replace { replace: Value },
+}
+
+/// The parser which returns configuration data pushed via [`PushServer`] or [`PushLayer::set`]/[`PushLayer::merge`]/
+/// [`PushLayer::replace`] methods. The clones of [`PushLayer`] share same data.
+#[derive(Clone, Default)]
+pub struct PushLayer {
+    value: Arc<Mutex<Value>>,
+}
+
+impl PushLayer {
+    /// Set value for given key path with default keys level delimiter [`DEFAULT_KEYS_SEPARATOR`].
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    #[inline]
+    pub fn set<P: AsRef<str>>(&self, path: P, value: &Value) -> Result<(), Error> {
+        self.set_with_delim(path, DEFAULT_KEYS_SEPARATOR, value)
+    }
+
+    /// Set value for given key path with given keys level delimiter.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    pub fn set_with_delim<P, D>(&self, path: P, delim: D, value: &Value) -> Result<(), Error>
+    where
+        P: AsRef<str>,
+        D: AsRef<str>,
+    {
+        let path = path.as_ref();
+        self.lock()
+            .set_by_key_path_with_delim(path, delim, value)
+            .map_err(|e| Error::Value(e, path.into()))?;
+        Ok(())
+    }
+
+    /// Merge given configuration data into pushed one, given data has higher priority (see [`Value::merge_from`]).
+    #[inline]
+    pub fn merge(&self, value: &Value) {
+        self.lock().merge_from(value);
+    }
+
+    /// Replace whole pushed configuration data.
+    #[inline]
+    pub fn replace(&self, value: Value) {
+        *self.lock() = value;
+    }
+
+    /// Return copy of pushed configuration data.
+    #[inline]
+    pub fn value(&self) -> Value {
+        self.lock().clone()
+    }
+
+    fn apply(&self, request: &str) -> Result<(), Error> {
+        match serde_json::from_str(request).map_err(Error::Request)? {
+            Request::Set { set, value } => self.set(set, &value),
+            Request::Merge { merge } => {
+                self.merge(&merge);
+                Ok(())
+            }
+            Request::Replace { replace } => {
+                self.replace(replace);
+                Ok(())
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Value> {
+        self.value.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Case for PushLayer {}
+
+impl Parse for PushLayer {
+    #[inline]
+    fn parse(&mut self, _value: &Value) -> AnyResult<Value> {
+        Ok(self.value())
+    }
+}
+
+/// The server which listens on Unix domain socket and applies received requests to [`PushLayer`]. The server will
+/// stop to accept new connections and socket file will be removed on drop.
+pub struct PushServer {
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl PushServer {
+    /// Bind Unix domain socket to given path with default options (see [`PushOptions`]) and start serving requests in
+    /// background thread. The `on_update` callback will be called after each successfully applied request.
+    ///
+    /// # Errors
+    ///
+    /// If socket could not be bound then error will be returned.
+    #[inline]
+    pub fn bind<P, F>(path: P, layer: PushLayer, on_update: F) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        F: Fn() + Send + Sync + 'static,
+    {
+        Self::bind_with(path, layer, PushOptions::default(), on_update)
+    }
+
+    /// Same as [`PushServer::bind`], but with given options. Each connection is served in its own thread, up to
+    /// [`PushOptions::max_connections`] at once. The server checks for new connections and stop request every 50ms.
+    ///
+    /// # Errors
+    ///
+    /// If socket could not be bound or its permissions could not be set then error will be returned.
+    pub fn bind_with<P, F>(
+        path: P,
+        layer: PushLayer,
+        options: PushOptions,
+        on_update: F,
+    ) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        F: Fn() + Send + Sync + 'static,
+    {
+        let path = path.as_ref().to_path_buf();
+        let listener = bind(&path, options.mode)
+            .and_then(|l| l.set_nonblocking(true).map(|_| l))
+            .map_err(|e| Error::Bind(e, path.clone()))?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let on_update = Arc::new(on_update);
+        let active = Arc::new(AtomicUsize::new(0));
+        let handle = {
+            let stop = stop.clone();
+            // The listener is polled, so the server could be stopped even if socket file was removed.
+            thread::spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    let mut s = match listener.accept() {
+                        Ok((s, _)) if s.set_nonblocking(false).is_ok() => s,
+                        Ok(_) => continue,
+                        Err(e) => {
+                            if ErrorKind::Interrupted != e.kind() {
+                                thread::sleep(ACCEPT_INTERVAL);
+                            }
+                            continue;
+                        }
+                    };
+                    let Some(slot) = Slot::acquire(&active, options.max_connections) else {
+                        let reply = json!({"ok": false, "error": "Too many connections"});
+                        let _ = writeln!(s, "{reply}");
+                        continue;
+                    };
+                    let (layer, on_update) = (layer.clone(), on_update.clone());
+                    thread::spawn(move || {
+                        serve(s, &layer, &options, on_update.as_ref());
+                        drop(slot);
+                    });
+                }
+            })
+        };
+
+        Ok(Self {
+            path,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// Return path of Unix domain socket.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for PushServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(h) = self.handle.take() {
+            let _ = h.join();
+        }
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Counted connection slot, released on drop.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn acquire(active: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Self(active.clone()))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Bind socket to temporary path, set its permissions and only then link it to given path, so the socket is never
+/// reachable with default permissions. Linking fails if given path already exists.
+fn bind(path: &Path, mode: u32) -> Result<UnixListener, IoError> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", std::process::id()));
+    let tmp = path.with_file_name(name);
+    let _ = fs::remove_file(&tmp);
+
+    let listener = UnixListener::bind(&tmp)?;
+    let result = fs::set_permissions(&tmp, fs::Permissions::from_mode(mode))
+        .and_then(|_| fs::hard_link(&tmp, path));
+    let _ = fs::remove_file(&tmp);
+    result.map(|_| listener)
+}
+
+fn serve<F: Fn()>(stream: UnixStream, layer: &PushLayer, options: &PushOptions, on_update: &F) {
+    let timeout = Some(options.timeout).filter(|t| !t.is_zero());
+    if stream.set_read_timeout(timeout).is_err() || stream.set_write_timeout(timeout).is_err() {
+        return;
+    }
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+
+    let limit = u64::try_from(options.max_request_len).unwrap_or(u64::MAX);
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.by_ref().take(limit).read_line(&mut line) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => (),
+        }
+
+        if !line.ends_with('\n') && line.len() >= options.max_request_len {
+            let reply = json!({"ok": false, "error": "Request is too long"});
+            let _ = writeln!(writer, "{reply}");
+            return;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let reply = match layer.apply(&line) {
+            Ok(()) => {
+                on_update();
+                json!({"ok": true})
+            }
+            Err(e) => json!({"ok": false, "error": error_chain(&e)}),
+        };
+
+        if writeln!(writer, "{reply}").is_err() {
+            return;
+        }
+    }
+}
+
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut result = error.to_string();
+    let mut source = error.source();
+    while let Some(e) = source {
+        result = [&result, ": ", &e.to_string()].concat();
+        source = e.source();
+    }
+    result
+}
@@ -0,0 +1,182 @@
+//! This module define human oriented output formats of configuration data (see [`Config::render`]). If provenance
+//! tracking is enabled (see [`ConfigBuilder::provenance`]), the name of the parser which supplied each value will be
+//! rendered as well. The keys tree could be also exported as [Graphviz](https://graphviz.org/) graph (see
+//! [`Config::to_dot`]).
+//!
+//! [`Config::render`]: crate::Config::render
+//! [`Config::to_dot`]: crate::Config::to_dot
+//! [`ConfigBuilder::provenance`]: crate::ConfigBuilder::provenance
+
+use crate::Value;
+use serde_json::Value as InnerValue;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+/// The marker which will be appended to sealed values.
+pub const SEALED_MARKER: &str = "[sealed]";
+
+/// Human oriented output formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Table with aligned key paths and values columns.
+    #[default]
+    Table,
+    /// Indented tree of keys.
+    Tree,
+    /// One `key path = value` line per value.
+    Flat,
+}
+
+struct Entry<'a> {
+    keys: Vec<String>,
+    value: String,
+    sealed: bool,
+    source: Option<&'a str>,
+}
+
+impl Entry<'_> {
+    fn value(&self) -> String {
+        if self.sealed {
+            return [&self.value, " ", SEALED_MARKER].concat();
+        }
+        self.value.clone()
+    }
+
+    fn value_with_source(&self) -> String {
+        match self.source {
+            Some(s) => [&self.value(), "  # ", s].concat(),
+            None => self.value(),
+        }
+    }
+}
+
+pub(crate) fn render(
+    value: &Value,
+    delim: &str,
+    format: Format,
+    sources: &BTreeMap<&str, &str>,
+) -> String {
+    let mut entries = Vec::new();
+    collect(value, &value.get_sealed(), &mut Vec::new(), &mut entries);
+    for entry in &mut entries {
+        entry.source = sources.get(entry.keys.join(delim).as_str()).copied();
+    }
+
+    let mut result = String::new();
+    match format {
+        Format::Table => {
+            let rows: Vec<_> = entries
+                .iter()
+                .map(|e| (e.keys.join(delim), e.value(), e.source))
+                .collect();
+            let width = rows.iter().map(|(k, ..)| k.len()).fold(3, usize::max);
+            if sources.is_empty() {
+                let _ = writeln!(result, "{:width$}  VALUE", "KEY");
+                for (k, v, _) in rows {
+                    let _ = writeln!(result, "{k:width$}  {v}");
+                }
+            } else {
+                let value_width = rows.iter().map(|(_, v, _)| v.len()).fold(5, usize::max);
+                let _ = writeln!(result, "{:width$}  {:value_width$}  SOURCE", "KEY", "VALUE");
+                for (k, v, s) in rows {
+                    let s = s.unwrap_or_default();
+                    let _ = writeln!(result, "{k:width$}  {v:value_width$}  {s}");
+                }
+            }
+        }
+        Format::Tree => {
+            let mut prev: &[String] = &[];
+            for entry in &entries {
+                let common = prev
+                    .iter()
+                    .zip(&entry.keys)
+                    .take_while(|(p, k)| p == k)
+                    .count()
+                    .min(entry.keys.len() - 1);
+                for (depth, key) in entry.keys.iter().enumerate().skip(common) {
+                    let indent = "  ".repeat(depth);
+                    if depth + 1 == entry.keys.len() {
+                        let _ = writeln!(result, "{indent}{key}: {}", entry.value_with_source());
+                    } else {
+                        let _ = writeln!(result, "{indent}{key}");
+                    }
+                }
+                prev = &entry.keys;
+            }
+        }
+        Format::Flat => {
+            for entry in &entries {
+                let _ = writeln!(
+                    result,
+                    "{} = {}",
+                    entry.keys.join(delim),
+                    entry.value_with_source()
+                );
+            }
+        }
+    }
+    result
+}
+
+pub(crate) fn dot(value: &Value, delim: &str, sources: &BTreeMap<&str, &str>) -> String {
+    let mut entries = Vec::new();
+    collect(value, &value.get_sealed(), &mut Vec::new(), &mut entries);
+
+    let mut result = String::from("digraph config {\n    rankdir=LR;\n    node [shape=box];\n");
+    let _ = writeln!(result, "    {} [label=\"\", shape=point];", quote(""));
+    let mut nodes = BTreeSet::new();
+    for entry in &entries {
+        for depth in 1..=entry.keys.len() {
+            let id = entry.keys[..depth].join(delim);
+            if !nodes.insert(id.clone()) {
+                continue;
+            }
+
+            let key = &entry.keys[depth - 1];
+            let attrs = if depth < entry.keys.len() {
+                format!("label={}, shape=folder", quote(key))
+            } else {
+                let mut label = [key, " = ", &entry.value()].concat();
+                if let Some(source) = sources.get(id.as_str()) {
+                    label = [&label, "\n", source].concat();
+                }
+                let style = if entry.sealed { ", style=dashed" } else { "" };
+                format!("label={}{style}", quote(&label))
+            };
+            let parent = entry.keys[..depth - 1].join(delim);
+            let _ = writeln!(result, "    {} [{attrs}];", quote(&id));
+            let _ = writeln!(result, "    {} -> {};", quote(&parent), quote(&id));
+        }
+    }
+    result.push_str("}\n");
+    result
+}
+
+fn quote(s: &str) -> String {
+    let s = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    ["\"", &s, "\""].concat()
+}
+
+fn collect(value: &Value, node: &InnerValue, keys: &mut Vec<String>, entries: &mut Vec<Entry<'_>>) {
+    match node {
+        InnerValue::Object(m) if !m.is_empty() => {
+            for (k, v) in m {
+                keys.push(k.clone());
+                collect(value, v, keys, entries);
+                keys.pop();
+            }
+        }
+        _ if keys.is_empty() => (),
+        _ => entries.push(Entry {
+            keys: keys.clone(),
+            value: node.to_string(),
+            sealed: value.is_secret(keys),
+            source: None,
+        }),
+    }
+}
@@ -0,0 +1,101 @@
+//! This module define [`RotationNotifier`] which invokes registered rotation handlers (e.g. to rebuild database
+//! connections pool or to reload TLS certificates) only when the values of specific (usually sealed) key paths are
+//! changed across (re)loads of [`Config`]. The changes are detected via [`Value::diff`].
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::rotation::RotationNotifier;
+//!
+//! let mut notifier = RotationNotifier::new(&config)
+//!     .on(["db:password", "db:user"], |c| rebuild_pool(c))
+//!     .on(["tls"], |c| reload_certs(c));
+//!
+//! watcher.run(|result| {
+//!     if let Ok(config) = result {
+//!         notifier.notify(config);
+//!     }
+//!     true
+//! })?;
+//! ```
+//!
+//! [`Value::diff`]: crate::Value::diff
+
+use crate::{Config, Value};
+use std::sync::Arc;
+
+type Handler = Box<dyn FnMut(&Config)>;
+
+struct Rotation {
+    paths: Vec<Vec<String>>,
+    handler: Handler,
+}
+
+/// The helper which invokes rotation handlers when the values of their key paths are changed.
+pub struct RotationNotifier {
+    rotations: Vec<Rotation>,
+    last: Arc<Value>,
+    delim: String,
+}
+
+impl RotationNotifier {
+    /// Create [`RotationNotifier`] for given [`Config`], its current configuration data is used as baseline.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            rotations: Vec::new(),
+            last: config.snapshot(),
+            delim: config.keys_delimiter().to_owned(),
+        }
+    }
+
+    /// Register handler which will be invoked if value of any of given key paths (with keys delimiter) or any value
+    /// inside of them will be added, removed or changed. The handlers are invoked in order of registration.
+    pub fn on<I, P, F>(mut self, paths: I, handler: F) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+        F: FnMut(&Config) + 'static,
+    {
+        let case_on = self.last.is_case_sensitive();
+        let paths = paths
+            .into_iter()
+            .map(|p| {
+                p.as_ref()
+                    .split(&self.delim)
+                    .map(|k| crate::normalize_case(k, case_on).into_owned())
+                    .collect()
+            })
+            .collect();
+        self.rotations.push(Rotation {
+            paths,
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    /// Compare configuration data of given [`Config`] with the data from previous call (or creation) and invoke
+    /// handlers for changed key paths. Return number of invoked handlers.
+    pub fn notify(&mut self, config: &Config) -> usize {
+        let current = config.snapshot();
+        if Arc::ptr_eq(&self.last, &current) {
+            return 0;
+        }
+
+        let diff = self.last.diff(&current);
+        self.last = current;
+        let mut count = 0;
+        for rotation in &mut self.rotations {
+            let is_changed = diff.entries().iter().any(|e| {
+                rotation
+                    .paths
+                    .iter()
+                    .any(|p| e.keys().starts_with(p) || p.starts_with(e.keys()))
+            });
+            if is_changed {
+                (rotation.handler)(config);
+                count += 1;
+            }
+        }
+        count
+    }
+}
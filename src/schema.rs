@@ -0,0 +1,200 @@
+//! This module define [`Schema`] structure which is used to inject default values and to coerce types of
+//! configuration data during (re)load (see [`ConfigBuilder::schema`]), so defaults could live alongside validation
+//! constraints in one artifact.
+//!
+//! The schema is a subset of [JSON Schema](https://json-schema.org/), the following keywords are supported:
+//!
+//! * `type` - the value will be coerced to given type (or to first of given types) if possible: strings to numbers
+//!   and booleans, numbers and booleans to strings;
+//! * `properties` - the schemas of dictionary values;
+//! * `items` - the schema of all array items;
+//! * `default` - the value to be used if key is missing.
+//!
+//! All other keywords are ignored.
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::schema::Schema;
+//! use irx_config::{json, ConfigBuilder};
+//!
+//! let schema = Schema::try_from(json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "port": { "type": "integer", "default": 8080 },
+//!         "debug": { "type": "boolean", "default": false }
+//!     }
+//! }))?;
+//!
+//! let config = ConfigBuilder::default()
+//!     .append_parser(parser)
+//!     .schema(schema)
+//!     .load()?;
+//! ```
+//!
+//! [`ConfigBuilder::schema`]: crate::ConfigBuilder::schema
+
+use crate::{Error, Result, Value};
+use serde::Serialize;
+use serde_json::{Number, Value as InnerValue};
+use std::io::Read;
+
+/// The schema used to inject default values and to coerce types of configuration data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    schema: InnerValue,
+}
+
+impl Schema {
+    /// Try to create [`Schema`] from any type which implements [`Serialize`] trait.
+    ///
+    /// # Errors
+    ///
+    /// If given data could not be serialized or it is not a dictionary then error will be returned.
+    pub fn try_from<T: Serialize>(schema: T) -> Result<Self> {
+        let schema = serde_json::to_value(schema)
+            .map_err(|e| Error::SerdeError(e, "Failed to serialize schema".into()))?;
+        if !schema.is_object() {
+            return Err(Error::NotMap);
+        }
+        Ok(Self { schema })
+    }
+
+    /// Try to read [`Schema`] in `JSON` format from given reader.
+    ///
+    /// # Errors
+    ///
+    /// If schema could not be read or it is not a dictionary then error will be returned.
+    pub fn from_reader(reader: impl Read) -> Result<Self> {
+        let schema: InnerValue = serde_json::from_reader(reader)
+            .map_err(|e| Error::SerdeError(e, "Failed to read schema".into()))?;
+        Self::try_from(schema)
+    }
+
+    /// Inject default values for missing keys and coerce types of values in given [`Value`] structure according to
+    /// the schema. If [`Value`] was sealed and it was changed then it will be in
+    /// [`SealedState::Mutated`](crate::value::SealedState::Mutated).
+    pub fn apply(&self, value: &mut Value) {
+        let case_on = value.is_case_sensitive();
+        value.update_inner(|v| apply(&self.schema, v, case_on));
+    }
+}
+
+fn apply(schema: &InnerValue, value: &mut InnerValue, case_on: bool) -> bool {
+    let mut is_changed = coerce(schema, value);
+    match value {
+        InnerValue::Object(m) => {
+            let Some(InnerValue::Object(props)) = schema.get("properties") else {
+                return is_changed;
+            };
+
+            for (k, s) in props {
+                let key = crate::normalize_case(k, case_on);
+                match m.get_mut(key.as_ref()) {
+                    Some(v) => is_changed |= apply(s, v, case_on),
+                    None => {
+                        if let Some(mut v) = default(s, case_on) {
+                            apply(s, &mut v, case_on);
+                            m.insert(key.into_owned(), v);
+                            is_changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        InnerValue::Array(a) => {
+            if let Some(s) = schema.get("items") {
+                for v in a {
+                    is_changed |= apply(s, v, case_on);
+                }
+            }
+        }
+        _ => (),
+    }
+    is_changed
+}
+
+fn default(schema: &InnerValue, case_on: bool) -> Option<InnerValue> {
+    if let Some(v) = schema.get("default") {
+        return Some(v.clone());
+    }
+
+    let is_object = match schema.get("type") {
+        Some(InnerValue::String(t)) => "object" == t,
+        _ => schema.get("properties").is_some(),
+    };
+    if !is_object {
+        return None;
+    }
+
+    let mut result = InnerValue::Object(Default::default());
+    apply(schema, &mut result, case_on);
+    result
+        .as_object()
+        .is_some_and(|m| !m.is_empty())
+        .then_some(result)
+}
+
+fn coerce(schema: &InnerValue, value: &mut InnerValue) -> bool {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(InnerValue::String(t)) => vec![t],
+        Some(InnerValue::Array(a)) => a.iter().filter_map(InnerValue::as_str).collect(),
+        _ => return false,
+    };
+    if types.iter().any(|t| is_type(value, t)) {
+        return false;
+    }
+
+    let Some(coerced) = types.iter().find_map(|t| convert(value, t)) else {
+        return false;
+    };
+    *value = coerced;
+    true
+}
+
+fn is_type(value: &InnerValue, name: &str) -> bool {
+    match name {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+fn convert(value: &InnerValue, name: &str) -> Option<InnerValue> {
+    match (name, value) {
+        ("string", InnerValue::Number(n)) => Some(InnerValue::String(n.to_string())),
+        ("string", InnerValue::Bool(b)) => Some(InnerValue::String(b.to_string())),
+        ("integer", InnerValue::String(s)) => parse_integer(s),
+        ("integer", InnerValue::Number(n)) => n
+            .as_f64()
+            .filter(|f| 0.0 == f.fract() && *f >= i64::MIN as f64 && *f <= i64::MAX as f64)
+            .map(|f| InnerValue::Number(Number::from(f as i64))),
+        ("number", InnerValue::String(s)) => parse_integer(s).or_else(|| {
+            s.trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(InnerValue::Number)
+        }),
+        ("boolean", InnerValue::String(s)) => match s.trim().to_lowercase().as_str() {
+            "true" => Some(InnerValue::Bool(true)),
+            "false" => Some(InnerValue::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_integer(data: &str) -> Option<InnerValue> {
+    let data = data.trim();
+    data.parse::<i64>()
+        .map(Number::from)
+        .or_else(|_| data.parse::<u64>().map(Number::from))
+        .ok()
+        .map(InnerValue::Number)
+}
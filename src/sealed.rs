@@ -0,0 +1,128 @@
+//! This module define [`Sealed`] structure which keeps secret value obfuscated during display/debugging output beyond
+//! the [`crate::Value`] structure, plus helpers to be used with `#[serde(with = "irx_config::sealed")]` field
+//! attribute.
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::sealed::Sealed;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Deserialize, Serialize)]
+//! struct Connection {
+//!     user: String,
+//!     #[serde(with = "irx_config::sealed")]
+//!     password: Sealed<String>,
+//! }
+//!
+//! let conn: Connection = config.get_by_key_path("connection")?.unwrap();
+//! // Password will be displayed as `********`
+//! println!("{conn:?}");
+//! let password: &str = conn.password.expose();
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    result::Result as StdResult,
+};
+
+/// The string which is used to obfuscate secret values during display/debugging/serialization output.
+pub const OBFUSCATED: &str = "********";
+
+/// The wrapper for secret value. Such value will be obfuscated with [`OBFUSCATED`] during display/debugging output and
+/// serialization. The wrapped value could be accessed only explicitly via [`Sealed::expose`] or
+/// [`Sealed::into_inner`] methods.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sealed<T>(T);
+
+impl<T> Sealed<T> {
+    /// Create [`Sealed`] structure from given secret value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Return reference to the secret value.
+    #[inline]
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Return the secret value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Sealed<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Debug for Sealed<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_fmt(format_args!("Sealed({OBFUSCATED:?})"))
+    }
+}
+
+impl<T> Display for Sealed<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(OBFUSCATED)
+    }
+}
+
+impl<T> Serialize for Sealed<T> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(self, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Sealed<T> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer)
+    }
+}
+
+/// Serialize [`Sealed`] value as obfuscated string [`OBFUSCATED`]. To be used with
+/// `#[serde(with = "irx_config::sealed")]` or `#[serde(serialize_with = "irx_config::sealed::serialize")]` field
+/// attribute.
+///
+/// # Errors
+///
+/// If any errors will occur during serialization then error will be returned.
+#[inline]
+pub fn serialize<T, S>(_value: &Sealed<T>, serializer: S) -> StdResult<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(OBFUSCATED)
+}
+
+/// Deserialize value into [`Sealed`] structure. To be used with `#[serde(with = "irx_config::sealed")]` or
+/// `#[serde(deserialize_with = "irx_config::sealed::deserialize")]` field attribute.
+///
+/// # Errors
+///
+/// If any errors will occur during de-serialization then error will be returned.
+#[inline]
+pub fn deserialize<'de, T, D>(deserializer: D) -> StdResult<Sealed<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Sealed)
+}
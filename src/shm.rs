@@ -0,0 +1,391 @@
+//! This module provide experimental sharing of resolved configuration between processes via POSIX shared memory
+//! segment, so in high fan-out process trees only one process (e.g. supervisor) runs parsers, and all others just map
+//! the segment by [`ShmReader`] and deserialize published data once per version.
+//!
+//! The segment contains header with version counter followed by serialized [`Config`] (see [`Config::meta`])
+//! published by [`ShmWriter`]. The version counter works as sequence lock: all shared data is accessed atomically, the
+//! reader retries if the version was changed while data was copied, so readers never see partially written data.
+//! The segment is created only if it does not exist yet and is never resized, so there is only one writer of the
+//! segment at a time unless the segment is explicitly reopened by [`ShmWriter::open`].
+//!
+//! To enable that module one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["shm"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::shm::{ShmReader, ShmWriter};
+//!
+//! // Supervisor
+//! let mut writer = ShmWriter::create("/myapp-config", 1 << 20)?;
+//! writer.publish(&config)?;
+//!
+//! // Worker
+//! let mut reader = ShmReader::open("/myapp-config")?;
+//! let level: Option<String> = reader.get_by_key_path("logger:level")?;
+//! if reader.refresh()? {
+//!     // New version was published
+//! }
+//! ```
+
+use crate::{Config, Result as CrateResult};
+use serde::de::DeserializeOwned;
+use std::{
+    ffi::CString,
+    fs::File,
+    io::Error as IoError,
+    mem::size_of,
+    os::fd::{FromRawFd, OwnedFd},
+    ptr, slice,
+    sync::atomic::{fence, AtomicU64, AtomicU8, Ordering},
+    thread,
+};
+
+const MAGIC: u64 = u64::from_le_bytes(*b"IRXSHM01");
+
+const MAX_READ_ATTEMPTS: usize = 1000;
+
+/// All errors for shared memory configuration.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Invalid name of shared memory segment: '{0}'")]
+    InvalidName(String),
+    #[error("Failed to {1} shared memory segment: '{2}'")]
+    Segment(#[source] IoError, &'static str, String),
+    #[error("Unsupported format of shared memory segment: '{0}'")]
+    Format(String),
+    #[error(
+        "Serialized configuration size {0} exceeds capacity {1} of shared memory segment: '{2}'"
+    )]
+    TooLarge(usize, usize, String),
+    #[error("No configuration published to shared memory segment: '{0}'")]
+    Empty(String),
+    #[error("Failed to get consistent data of shared memory segment: '{0}'")]
+    Busy(String),
+    #[error("Failed to {1} configuration of shared memory segment: '{2}'")]
+    Json(#[source] serde_json::Error, &'static str, String),
+}
+
+#[repr(C)]
+struct Header {
+    magic: AtomicU64,
+    sequence: AtomicU64,
+    capacity: AtomicU64,
+    len: AtomicU64,
+}
+
+const HEADER_SIZE: usize = size_of::<Header>();
+
+struct Mapping {
+    ptr: *mut u8,
+    size: usize,
+}
+
+// The mapping is just a region of memory, all shared state (header and data) is accessed atomically.
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    /// Create new segment of given size, or open existing one if size is not given.
+    fn new(name: &str, writable: bool, size: Option<usize>) -> Result<Self, Error> {
+        let c_name = CString::new(name).map_err(|_| Error::InvalidName(name.to_string()))?;
+        let (oflag, prot) = match (writable, size) {
+            (true, Some(_)) => (
+                libc::O_CREAT | libc::O_EXCL | libc::O_RDWR,
+                libc::PROT_READ | libc::PROT_WRITE,
+            ),
+            (true, None) => (libc::O_RDWR, libc::PROT_READ | libc::PROT_WRITE),
+            (false, _) => (libc::O_RDONLY, libc::PROT_READ),
+        };
+        let err = |action| move |e| Error::Segment(e, action, name.to_string());
+        let fd = unsafe { libc::shm_open(c_name.as_ptr(), oflag, 0o600 as libc::mode_t) };
+        if fd < 0 {
+            return Err(err("open")(IoError::last_os_error()));
+        }
+        let file = File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        let size = match size {
+            Some(size) => {
+                file.set_len(size as u64).map_err(err("resize"))?;
+                size
+            }
+            None => file.metadata().map_err(err("open"))?.len() as usize,
+        };
+        if size < HEADER_SIZE {
+            return Err(Error::Format(name.to_string()));
+        }
+
+        let ptr = unsafe { libc::mmap(ptr::null_mut(), size, prot, libc::MAP_SHARED, fd, 0) };
+        if ptr == libc::MAP_FAILED {
+            return Err(err("map")(IoError::last_os_error()));
+        }
+        Ok(Self {
+            ptr: ptr.cast(),
+            size,
+        })
+    }
+
+    #[inline]
+    fn header(&self) -> &Header {
+        unsafe { &*self.ptr.cast::<Header>() }
+    }
+
+    #[inline]
+    fn data(&self) -> &[AtomicU8] {
+        // `AtomicU8` has the same in-memory representation as `u8`.
+        unsafe {
+            slice::from_raw_parts(
+                self.ptr.add(HEADER_SIZE).cast::<AtomicU8>(),
+                self.size - HEADER_SIZE,
+            )
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let header = self.header();
+        header.magic.load(Ordering::Acquire) == MAGIC
+            && header.capacity.load(Ordering::Relaxed) <= (self.size - HEADER_SIZE) as u64
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.ptr.cast(), self.size) };
+    }
+}
+
+/// The writer which publishes configuration to shared memory segment.
+pub struct ShmWriter {
+    name: String,
+    mapping: Mapping,
+    capacity: usize,
+}
+
+impl ShmWriter {
+    /// Create new shared memory segment with given name (e.g. `/myapp-config`) and capacity in bytes for serialized
+    /// configuration. The segment stays in the system until it will be removed by [`remove`].
+    ///
+    /// # Errors
+    ///
+    /// If segment already exists (e.g. it was not removed after previous writer, see [`ShmWriter::open`]), or it
+    /// could not be created or mapped then error will be returned.
+    pub fn create<S: Into<String>>(name: S, capacity: usize) -> Result<Self, Error> {
+        let name = name.into();
+        let mapping = Mapping::new(&name, true, Some(HEADER_SIZE + capacity))?;
+        let header = mapping.header();
+        header.capacity.store(capacity as u64, Ordering::Relaxed);
+        header.magic.store(MAGIC, Ordering::Release);
+        Ok(Self {
+            name,
+            mapping,
+            capacity,
+        })
+    }
+
+    /// Open existing shared memory segment with given name created by [`ShmWriter::create`] (e.g. after restart of
+    /// the writer), so readers which already mapped it get new versions. The segment is not resized. The caller is
+    /// responsible that there is no other writer of the segment.
+    ///
+    /// # Errors
+    ///
+    /// If segment could not be opened or mapped, or it has unsupported format then error will be returned.
+    pub fn open<S: Into<String>>(name: S) -> Result<Self, Error> {
+        let name = name.into();
+        let mapping = Mapping::new(&name, true, None)?;
+        if !mapping.is_valid() {
+            return Err(Error::Format(name));
+        }
+
+        let capacity = mapping.header().capacity.load(Ordering::Relaxed) as usize;
+        Ok(Self {
+            name,
+            mapping,
+            capacity,
+        })
+    }
+
+    /// Get name of shared memory segment.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Publish given configuration and return its version (starting from `1`). The sealed values are obfuscated (see
+    /// [`Config`] serialization), use [`ShmWriter::publish_unsealed`] if readers need them.
+    ///
+    /// # Errors
+    ///
+    /// If configuration could not be serialized or does not fit in the segment then error will be returned.
+    #[inline]
+    pub fn publish(&mut self, config: &Config) -> Result<u64, Error> {
+        let data = serde_json::to_vec(config)
+            .map_err(|e| Error::Json(e, "serialize", self.name.clone()))?;
+        self.write(&data)
+    }
+
+    /// Same as [`ShmWriter::publish`], but the sealed values are published as is (see [`Config::unsealed`]). It
+    /// should be used only if all processes which could open the segment are trusted.
+    ///
+    /// # Errors
+    ///
+    /// If configuration could not be serialized or does not fit in the segment then error will be returned.
+    #[inline]
+    pub fn publish_unsealed(&mut self, config: &Config) -> Result<u64, Error> {
+        let data = serde_json::to_vec(&config.unsealed())
+            .map_err(|e| Error::Json(e, "serialize", self.name.clone()))?;
+        self.write(&data)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<u64, Error> {
+        if data.len() > self.capacity {
+            return Err(Error::TooLarge(
+                data.len(),
+                self.capacity,
+                self.name.clone(),
+            ));
+        }
+
+        let header = self.mapping.header();
+        let sequence = header.sequence.load(Ordering::Relaxed) & !1;
+        header.sequence.store(sequence + 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+        for (dst, src) in self.mapping.data().iter().zip(data) {
+            dst.store(*src, Ordering::Relaxed);
+        }
+        header.len.store(data.len() as u64, Ordering::Relaxed);
+        header.sequence.store(sequence + 2, Ordering::Release);
+        Ok(sequence / 2 + 1)
+    }
+}
+
+/// The reader of configuration published to shared memory segment by [`ShmWriter`]. The configuration is
+/// deserialized on open and on [`ShmReader::refresh`] only if new version was published.
+pub struct ShmReader {
+    name: String,
+    mapping: Mapping,
+    sequence: u64,
+    config: Config,
+}
+
+impl ShmReader {
+    /// Open shared memory segment with given name and read the last published configuration.
+    ///
+    /// # Errors
+    ///
+    /// If segment could not be opened or mapped, or configuration was not published yet then error will be returned.
+    pub fn open<S: Into<String>>(name: S) -> Result<Self, Error> {
+        let name = name.into();
+        let mapping = Mapping::new(&name, false, None)?;
+        if !mapping.is_valid() {
+            return Err(Error::Format(name));
+        }
+
+        let (sequence, config) = read(&mapping, &name)?;
+        Ok(Self {
+            name,
+            mapping,
+            sequence,
+            config,
+        })
+    }
+
+    /// Get the latest version published to the segment.
+    #[inline]
+    pub fn version(&self) -> u64 {
+        self.mapping.header().sequence.load(Ordering::Acquire) / 2
+    }
+
+    /// Read configuration again if new version was published. Return `true` if configuration was updated.
+    ///
+    /// # Errors
+    ///
+    /// If configuration could not be read or deserialized then error will be returned.
+    pub fn refresh(&mut self) -> Result<bool, Error> {
+        if self.mapping.header().sequence.load(Ordering::Acquire) == self.sequence {
+            return Ok(false);
+        }
+
+        (self.sequence, self.config) = read(&self.mapping, &self.name)?;
+        Ok(true)
+    }
+
+    /// Get the last read configuration.
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Same as [`Config::get_by_key_path`], but for the last read configuration.
+    ///
+    /// # Errors
+    ///
+    /// If keys path or keys delimiter is empty, the corresponding error will be returned.
+    #[inline]
+    pub fn get_by_key_path<T, P>(&self, path: P) -> CrateResult<Option<T>>
+    where
+        T: DeserializeOwned,
+        P: AsRef<str>,
+    {
+        self.config.get_by_key_path(path)
+    }
+
+    /// Same as [`Config::get`], but for the last read configuration.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur during deserialization then error will be returned.
+    #[inline]
+    pub fn get<T: DeserializeOwned>(&self) -> CrateResult<T> {
+        self.config.get()
+    }
+}
+
+/// Remove shared memory segment with given name. Already mapped segment stays available to its readers and writer.
+///
+/// # Errors
+///
+/// If segment could not be removed then error will be returned.
+pub fn remove(name: &str) -> Result<(), Error> {
+    let c_name = CString::new(name).map_err(|_| Error::InvalidName(name.to_string()))?;
+    if unsafe { libc::shm_unlink(c_name.as_ptr()) } < 0 {
+        return Err(Error::Segment(
+            IoError::last_os_error(),
+            "remove",
+            name.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn read(mapping: &Mapping, name: &str) -> Result<(u64, Config), Error> {
+    let header = mapping.header();
+    let capacity = mapping.size - HEADER_SIZE;
+    for _ in 0..MAX_READ_ATTEMPTS {
+        let sequence = header.sequence.load(Ordering::Acquire);
+        if sequence == 0 {
+            return Err(Error::Empty(name.to_string()));
+        }
+        let len = header.len.load(Ordering::Relaxed) as usize;
+        if sequence % 2 == 1 || len > capacity {
+            thread::yield_now();
+            continue;
+        }
+
+        // The data could be changed by writer during copying, such torn copy is dropped if version was changed.
+        let data: Vec<_> = mapping.data()[..len]
+            .iter()
+            .map(|b| b.load(Ordering::Relaxed))
+            .collect();
+        fence(Ordering::Acquire);
+        if header.sequence.load(Ordering::Relaxed) == sequence {
+            let config = serde_json::from_slice(&data)
+                .map_err(|e| Error::Json(e, "deserialize", name.to_string()))?;
+            return Ok((sequence, config));
+        }
+    }
+    Err(Error::Busy(name.to_string()))
+}
@@ -0,0 +1,70 @@
+//! This module provide [`simulate`] function which runs the same merge algorithm as [`ConfigBuilder::load`] (including
+//! profiles, interpolation, schema and sealing) over raw layer documents, so external tools (e.g. linters or web UIs)
+//! could predict effective configuration without constructing parsers.
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::{json, ConfigBuilder, Value};
+//!
+//! let layers = vec![
+//!     ("env", Value::try_from(json!({ "port": 8080 }))?),
+//!     ("file", Value::try_from(json!({ "port": 80, "password_": "secret" }))?),
+//! ];
+//! let (value, trace) = irx_config::simulate(layers, ConfigBuilder::default().sealed_suffix("_"))?;
+//! for record in trace.records() {
+//!     println!("{} = {} ({:?})", record.key(), record.value(), record.origin());
+//! }
+//! ```
+//!
+//! [`ConfigBuilder::load`]: crate::ConfigBuilder::load
+
+use crate::{config::StaticParser, export::Record, ConfigBuilder, Result, Value};
+
+/// The trace of simulated merge (see [`simulate`]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MergeTrace {
+    records: Vec<Record>,
+    warnings: Vec<String>,
+}
+
+impl MergeTrace {
+    /// Get flattened merged data as typed records in sorted order of key paths with names of the layers which
+    /// supplied values (see [`crate::Config::records`]). Sealed values are obfuscated.
+    #[inline]
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Get warnings reported during merge (see [`crate::Config::warnings`]).
+    #[inline]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+/// Merge given named layers documents in the same way as [`ConfigBuilder::load`] would merge results of parsers
+/// appended in the same order (the first layer has highest priority) and return merged [`Value`] with trace of the
+/// merge. The merge settings (e.g. sealed suffix, arrays merge mode or profile) are taken from given options, the
+/// layers are appended after its parsers (if any). Provenance tracking is always enabled.
+///
+/// # Errors
+///
+/// If any errors will occur during merging then error will be returned.
+pub fn simulate<I, S>(layers: I, options: ConfigBuilder) -> Result<(Value, MergeTrace)>
+where
+    I: IntoIterator<Item = (S, Value)>,
+    S: Into<String>,
+{
+    let config = layers
+        .into_iter()
+        .fold(options.provenance(true), |b, (n, v)| {
+            b.append_named_parser(n, StaticParser(v))
+        })
+        .load()?;
+    let trace = MergeTrace {
+        records: config.records(),
+        warnings: config.warnings().to_vec(),
+    };
+    Ok((config.get_value().clone(), trace))
+}
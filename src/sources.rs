@@ -0,0 +1,179 @@
+//! This module provide parsing of compact sources descriptors (e.g. `json:./cfg.json,env:APP_,cmd`) into
+//! [`ConfigBuilder`], so wrapper frameworks could let end users choose and order sources at runtime via a single
+//! string. The descriptor is a comma separated list of `kind[:argument]` entries, the first entry has highest priority
+//! during merge (same as for [`ConfigBuilder::append_parser`]):
+//!
+//! | Kind                                 | Argument      | Parser                              | Feature          |
+//! |--------------------------------------|---------------|-------------------------------------|------------------|
+//! | `cmd`                                | -             | [`crate::parsers::cmd`]             | `cmd`            |
+//! | `env`                                | prefix        | [`crate::parsers::env`]             | `env`            |
+//! | `file`                               | path          | [`crate::parsers::auto`]            | `auto`           |
+//! | `uri`                                | URI           | [`crate::parsers::uri`]             | `uri`            |
+//! | `dotenv`                             | path          | [`crate::parsers::dotenv`]          | `dotenv`         |
+//! | `json`                               | path          | [`crate::parsers::json`]            | `sources`        |
+//! | `json5`, `yaml`, `toml`              | path          | file parser of given format         | format's feature |
+//! | `properties`, `kdl`, `msgpack`, `cbor` | path        | file parser of given format         | format's feature |
+//!
+//! The arguments could not contain commas. The kinds which features are not enabled are rejected.
+//!
+//! To enable that module one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["sources", "env"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::sources::Sources;
+//!
+//! let descriptor = std::env::var("APP_SOURCES").unwrap_or("json:./cfg.json,env:APP_".into());
+//! let config = Sources::default().parse(&descriptor)?.load()?;
+//! ```
+
+use crate::{AnyError, ConfigBuilder};
+use std::result::Result as StdResult;
+
+/// A result type for sources descriptors errors.
+pub type Result<T> = StdResult<T, Error>;
+
+/// The delimiter of entries in sources descriptor.
+pub const ENTRIES_DELIMITER: char = ',';
+
+/// The delimiter of kind and argument of sources descriptor entry.
+pub const ARGUMENT_DELIMITER: char = ':';
+
+/// All errors for sources descriptors.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Empty entry in sources descriptor: '{0}'")]
+    EmptyEntry(String),
+    #[error("Unsupported kind of source: '{0}'")]
+    UnknownKind(String),
+    #[error("Missing argument of '{0}' source")]
+    MissingArgument(String),
+    #[error("The command is not set for '{0}' source")]
+    MissingCommand(String),
+    #[error("Failed to build parser for '{1}' source")]
+    Build(#[source] AnyError, String),
+}
+
+/// The parser of sources descriptors.
+#[derive(Default)]
+pub struct Sources {
+    #[cfg(feature = "cmd")]
+    command: Option<clap::Command>,
+}
+
+impl Sources {
+    /// Set command used by `cmd` source. If not set then descriptor with `cmd` source will be rejected.
+    #[cfg(feature = "cmd")]
+    #[inline]
+    pub fn command(mut self, command: clap::Command) -> Self {
+        self.command = Some(command);
+        self
+    }
+
+    /// Parse sources descriptor and return [`ConfigBuilder`] with appended parsers in descriptor's order.
+    ///
+    /// # Errors
+    ///
+    /// If descriptor is malformed, contains unsupported kinds or any parser could not be built then error will be
+    /// returned.
+    pub fn parse(self, descriptor: &str) -> Result<ConfigBuilder> {
+        let mut sources = self;
+        let mut builder = ConfigBuilder::default();
+        for entry in descriptor.split(ENTRIES_DELIMITER) {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                return Err(Error::EmptyEntry(descriptor.into()));
+            }
+
+            let (kind, argument) = match entry.split_once(ARGUMENT_DELIMITER) {
+                Some((k, a)) => (k.trim(), Some(a.trim())),
+                None => (entry, None),
+            };
+            builder = sources.append(builder, kind, argument)?;
+        }
+        Ok(builder)
+    }
+
+    fn append(
+        &mut self,
+        builder: ConfigBuilder,
+        kind: &str,
+        argument: Option<&str>,
+    ) -> Result<ConfigBuilder> {
+        let required = || argument.ok_or_else(|| Error::MissingArgument(kind.into()));
+        match kind {
+            #[cfg(feature = "cmd")]
+            "cmd" => {
+                let command = self
+                    .command
+                    .take()
+                    .ok_or_else(|| Error::MissingCommand(kind.into()))?;
+                let parser = crate::parsers::cmd::ParserBuilder::new(command)
+                    .build()
+                    .map_err(|e| Error::Build(e.into(), kind.into()))?;
+                Ok(builder.append_parser(parser))
+            }
+            #[cfg(feature = "env")]
+            "env" => {
+                let parser = crate::parsers::env::ParserBuilder::default()
+                    .default_prefix(argument.unwrap_or_default())
+                    .build()
+                    .map_err(|e| Error::Build(e.into(), kind.into()))?;
+                Ok(builder.append_parser(parser))
+            }
+            #[cfg(feature = "auto")]
+            "file" => {
+                let parser = crate::parsers::auto::ParserBuilder::default()
+                    .default_path(required()?)
+                    .build()
+                    .map_err(|e| Error::Build(e.into(), kind.into()))?;
+                Ok(builder.append_parser(parser))
+            }
+            #[cfg(feature = "uri")]
+            "uri" => {
+                let parser = crate::parsers::uri::ParserBuilder::default()
+                    .default_uri(required()?)
+                    .build()
+                    .map_err(|e| Error::Build(e.into(), kind.into()))?;
+                Ok(builder.append_parser(parser))
+            }
+            #[cfg(feature = "dotenv")]
+            "dotenv" => file::<crate::parsers::dotenv::LoadDotenv>(builder, kind, required()?),
+            "json" => file::<crate::parsers::json::LoadJson>(builder, kind, required()?),
+            #[cfg(feature = "json5-parser")]
+            "json5" => file::<crate::parsers::json5::LoadJson>(builder, kind, required()?),
+            #[cfg(feature = "yaml")]
+            "yaml" => file::<crate::parsers::yaml::LoadYaml>(builder, kind, required()?),
+            #[cfg(feature = "toml-parser")]
+            "toml" => file::<crate::parsers::toml::LoadToml>(builder, kind, required()?),
+            #[cfg(feature = "properties")]
+            "properties" => {
+                file::<crate::parsers::properties::LoadProperties>(builder, kind, required()?)
+            }
+            #[cfg(feature = "kdl")]
+            "kdl" => file::<crate::parsers::kdl::LoadKdl>(builder, kind, required()?),
+            #[cfg(feature = "msgpack")]
+            "msgpack" => file::<crate::parsers::msgpack::LoadMsgpack>(builder, kind, required()?),
+            #[cfg(feature = "cbor")]
+            "cbor" => file::<crate::parsers::cbor::LoadCbor>(builder, kind, required()?),
+            _ => Err(Error::UnknownKind(kind.into())),
+        }
+    }
+}
+
+fn file<L>(builder: ConfigBuilder, kind: &str, path: &str) -> Result<ConfigBuilder>
+where
+    L: crate::parsers::Load + Clone + Default + 'static,
+{
+    let parser = crate::parsers::FileParserBuilder::<L>::default()
+        .default_path(path)
+        .build()
+        .map_err(|e| Error::Build(e.into(), kind.into()))?;
+    Ok(builder.append_parser(parser))
+}
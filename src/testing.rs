@@ -0,0 +1,62 @@
+//! This module provide helpers to write tests for code which is using [`crate::Config`].
+//!
+//! To enable that module one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! irx-config = { version = "3.5", features = ["testing"] }
+//! ```
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    sync::{Mutex, PoisonError},
+};
+
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Set environment variables, run given closure and restore previous environment variables values. All calls are
+/// serialized under global lock, so tests which depends on environment variables could be run in parallel.
+///
+/// # Example
+///
+/// ```
+/// use irx_config::parsers::env::ParserBuilder;
+/// use irx_config::testing::with_env;
+/// use irx_config::ConfigBuilder;
+///
+/// let config = with_env(&[("APP_ID", "42")], || {
+///     ConfigBuilder::load_one(ParserBuilder::default().default_prefix("APP_").build()?)
+/// })?;
+/// ```
+pub fn with_env<K, V, F, R>(vars: &[(K, V)], f: F) -> R
+where
+    K: AsRef<OsStr>,
+    V: AsRef<OsStr>,
+    F: FnOnce() -> R,
+{
+    let _lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let _guard = EnvGuard(
+        vars.iter()
+            .map(|(k, v)| {
+                let prev = env::var_os(k);
+                env::set_var(k, v);
+                (k.as_ref().to_owned(), prev)
+            })
+            .collect(),
+    );
+    f()
+}
+
+struct EnvGuard(Vec<(OsString, Option<OsString>)>);
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (k, v) in self.0.iter().rev() {
+            match v {
+                Some(v) => env::set_var(k, v),
+                None => env::remove_var(k),
+            }
+        }
+    }
+}
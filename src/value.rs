@@ -1,25 +1,37 @@
 //! This module define [`Value`] structure which represent key-value based configuration data.
 
-use crate::{Error, Result, DEFAULT_KEYS_SEPARATOR};
-use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "diff")]
+use crate::diff::ValueDiff;
+use crate::{sealed::OBFUSCATED, ArrayMerge, Error, MergeStrategy, Result, DEFAULT_KEYS_SEPARATOR};
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 pub use serde_json::json;
 pub(super) use serde_json::Error as SerdeError;
 use serde_json::{map::Map, Value as InnerValue};
 use std::{
     borrow::Cow,
-    fmt::{Debug, Display, Error as FmtError, Formatter, Result as FmtResult},
+    collections::BTreeMap,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    mem,
     result::Result as StdResult,
 };
 
 type ValueMap = Map<String, InnerValue>;
 type CowInnerValue<'a> = Cow<'a, InnerValue>;
 
+const REFERENCE_START: &str = "${";
+const REFERENCE_END: char = '}';
+const ENV_REFERENCE_PREFIX: &str = "ENV:";
+
 /// The sealed states for [`Value`] structure.
 ///
 /// If [`Value`] is sealed, the sensitive fields values will be obfuscated with `********` during display/debugging output.
 ///
 /// **IMPORTANT:** Once [`Value`] was sealed, but fully/partially mutated after that, it will be represented as empty
-/// dictionary during display/debugging output, to prevent sensitive data leakages.
+/// dictionary during display/debugging output, to prevent sensitive data leakages. Use [`Value::update_sealed`] to
+/// mutate sealed [`Value`] without such effect.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SealedState {
     /// A [`Value`] was never sealed, all data will be represented as is during display/debugging output.
@@ -32,6 +44,17 @@ pub enum SealedState {
     Mutated,
 }
 
+/// The options for [`Value::pretty`] output. The default options do not limit anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PrettyOptions {
+    /// Maximum nesting depth, deeper dictionaries/arrays will be replaced with `{...}`/`[...]`.
+    pub max_depth: Option<usize>,
+    /// Maximum number of items to output per array, the rest will be replaced with `... (N more)` item.
+    pub max_items: Option<usize>,
+    /// Maximum number of characters to output per string, longer strings will be cut and ended with `...`.
+    pub max_string_len: Option<usize>,
+}
+
 /// This structure represent key-value based configuration data.
 ///
 /// **IMPORTANT:** All functionality related to the sealed state only affects the display/debugging output.
@@ -40,6 +63,7 @@ pub struct Value {
     value: InnerValue,
     sealed: Option<InnerValue>,
     sealed_state: SealedState,
+    sealed_suffix: String,
     case_on: bool,
 }
 
@@ -130,15 +154,55 @@ impl Value {
         self.merge_with_case(value, case_on)
     }
 
+    /// Merge a input [`Value`] into the given [`Value`] structure in place, same way as [`Value::merge`], but without
+    /// consuming it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let mut person = Value::try_from(json!({ "name": "John Doe" }))?;
+    /// person.merge_from(&Value::try_from(json!({ "age": 43 }))?);
+    /// ```
+    #[inline]
+    pub fn merge_from(&mut self, value: &Value) {
+        *self = mem::take(self).merge(value);
+    }
+
+    /// Return a new [`Value`] structure as result of merge of a input [`Value`] to the copy of given [`Value`]
+    /// structure (see [`Value::merge`]). The given [`Value`] is not changed.
+    #[inline]
+    pub fn merged(&self, value: &Value) -> Self {
+        self.clone().merge(value)
+    }
+
     /// Merge a input [`Value`] to the given [`Value`] structure. The key names will be case-sensitive or
     /// case-insensitive during merge, according to `case_on` parameter. Return merged result [`Value`] structure.
     /// If given [`Value`] was sealed and merge operation was mutating then it will be in [`SealedState::Mutated`].
-    pub fn merge_with_case(mut self, value: &Value, case_on: bool) -> Self {
+    #[inline]
+    pub fn merge_with_case(self, value: &Value, case_on: bool) -> Self {
+        self.merge_with_arrays(value, case_on, ArrayMerge::Replace)
+    }
+
+    /// Merge a input [`Value`] to the given [`Value`] structure same way as [`Value::merge_with_case`], but arrays
+    /// will be merged according to `arrays` parameter (see [`ArrayMerge`]). The input [`Value`] has higher priority.
+    #[inline]
+    pub fn merge_with_arrays(self, value: &Value, case_on: bool, arrays: ArrayMerge) -> Self {
+        self.merge_with_rules(value, case_on, &MergeRules::new(arrays))
+    }
+
+    pub(crate) fn merge_with_rules(
+        mut self,
+        value: &Value,
+        case_on: bool,
+        rules: &MergeRules,
+    ) -> Self {
         let mut is_changed = self.normalize_case(case_on);
         self.value = match self.value {
             InnerValue::Object(dst) if value.value.is_object() => {
                 is_changed = true;
-                merge_into_value_map(dst, &value.value, self.case_on)
+                merge_into_value_map(dst, &value.value, self.case_on, rules, &mut Vec::new())
             }
             _ => self.value,
         };
@@ -268,6 +332,73 @@ impl Value {
         inner(self, path.as_ref(), delim.as_ref())
     }
 
+    /// Return all key paths (including paths to dictionaries) joined with given keys level delimiter in sorted order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let logger = Value::try_from(json!({
+    ///     "logger": {
+    ///         "id": 42
+    ///     }
+    /// }))?;
+    ///
+    /// assert_eq!(["logger", "logger:id"], logger.key_paths(":")[..]);
+    /// ```
+    pub fn key_paths<D: AsRef<str>>(&self, delim: D) -> Vec<String> {
+        fn inner(value: &InnerValue, prefix: &str, delim: &str, result: &mut Vec<String>) {
+            if let InnerValue::Object(m) = value {
+                for (k, v) in m {
+                    let path = if prefix.is_empty() {
+                        k.clone()
+                    } else {
+                        [prefix, delim, k].concat()
+                    };
+                    inner(v, &path, delim, result);
+                    result.push(path);
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        inner(&self.value, "", delim.as_ref(), &mut result);
+        result.sort();
+        result
+    }
+
+    /// Return existing key path nearest (by edit distance) to given key path, which is joined with given keys level
+    /// delimiter, if any. It could be used to suggest correct key path for missing one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let logger = Value::try_from(json!({
+    ///     "logger": {
+    ///         "level": "info"
+    ///     }
+    /// }))?;
+    ///
+    /// assert_eq!(Some("logger:level".to_string()), logger.suggest_key_path("loger:levle", ":"));
+    /// ```
+    pub fn suggest_key_path<P, D>(&self, path: P, delim: D) -> Option<String>
+    where
+        P: AsRef<str>,
+        D: AsRef<str>,
+    {
+        let path = crate::normalize_case(path.as_ref(), self.case_on);
+        let max = (path.chars().count() / 3).max(1);
+        self.key_paths(delim)
+            .into_iter()
+            .map(|p| (edit_distance(&path, &p), p))
+            .filter(|(d, _)| *d <= max)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, p)| p)
+    }
+
     /// Return deserialized data of any type which implements [`Deserialize`] trait.
     ///
     /// # Errors
@@ -305,6 +436,47 @@ impl Value {
         get(self.value.clone())
     }
 
+    /// Return [`Deserializer`] over whole [`Value`] structure, so any deserialization approach could be used
+    /// directly (e.g. [`DeserializeSeed`], borrowing of strings or crates like `serde_ignored`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let value = Value::try_from(json!({"id": 42, "extra": true}))?;
+    ///
+    /// let mut unused = Vec::new();
+    /// let config: Config = serde_ignored::deserialize(value.deserializer(), |p| unused.push(p.to_string()))?;
+    /// ```
+    #[inline]
+    pub fn deserializer(&self) -> impl Deserializer<'_, Error = SerdeError> {
+        &self.value
+    }
+
+    /// Return deserialized data of whole [`Value`] structure via given [`DeserializeSeed`], so stateful
+    /// deserialization could be used.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let value = Value::try_from(json!({"items": [1, 2, 3]}))?;
+    ///
+    /// let mut registry = Registry::default();
+    /// value.get_seeded(RegistrySeed(&mut registry))?;
+    /// ```
+    #[inline]
+    pub fn get_seeded<'de, S: DeserializeSeed<'de>>(&'de self, seed: S) -> Result<S::Value> {
+        seed.deserialize(&self.value)
+            .map_err(|e| Error::SerdeError(e, "Failed to deserialize value".into()))
+    }
+
     /// Set value of any type which implements [`Serialize`] trait for given key path represented as iterator.
     /// If [`Value`] was sealed and set operation was successful then it will be in [`SealedState::Mutated`]. Return
     /// previous value for same key path if any.
@@ -427,12 +599,361 @@ impl Value {
         inner(self, path.as_ref(), delim.as_ref(), value)
     }
 
+    /// Remove value for given key/nested keys. If [`Value`] was sealed and some value was removed then it will be in
+    /// [`SealedState::Mutated`]. If keys is empty then whole data will be removed. Return removed value if any.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let mut value = Value::try_from(json!({
+    ///     "logger": {
+    ///         "id": 42,
+    ///         "host": "localhost"
+    ///     }
+    /// }))?;
+    ///
+    /// let id: u32 = value.remove_by_keys(["logger", "id"])?.unwrap().get()?;
+    /// ```
+    pub fn remove_by_keys<I, K>(&mut self, keys: I) -> Result<Option<Self>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+    {
+        let mut keys: Vec<_> = keys
+            .into_iter()
+            .map(|k| crate::normalize_case(k.as_ref(), self.case_on).into_owned())
+            .collect();
+        let removed = match keys.pop() {
+            None => Some(std::mem::replace(&mut self.value, json!({}))),
+            Some(last) => match find_mut(&mut self.value, &keys) {
+                Some(InnerValue::Object(m)) => m.remove(&last),
+                _ => None,
+            },
+        };
+
+        Ok(removed.map(|r| {
+            self.unseal();
+            Self {
+                value: r,
+                case_on: self.case_on,
+                ..Default::default()
+            }
+        }))
+    }
+
+    /// Remove value for given key path represented as string with default keys level delimiter
+    /// [`DEFAULT_KEYS_SEPARATOR`]. If [`Value`] was sealed and some value was removed then it will be in
+    /// [`SealedState::Mutated`]. Return removed value if any.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::Value;
+    ///
+    /// let mut value = Value::default();
+    /// value.set_by_key_path("logger:id", 42)?;
+    /// value.remove_by_key_path("logger:id")?;
+    /// ```
+    #[inline]
+    pub fn remove_by_key_path<P>(&mut self, path: P) -> Result<Option<Self>>
+    where
+        P: AsRef<str>,
+    {
+        self.remove_by_key_path_with_delim(path, DEFAULT_KEYS_SEPARATOR)
+    }
+
+    /// Remove value for given key path represented as string with given keys level delimiter. If [`Value`] was
+    /// sealed and some value was removed then it will be in [`SealedState::Mutated`]. Return removed value if any.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::Value;
+    ///
+    /// let mut value = Value::default();
+    /// value.set_by_key_path_with_delim("logger/id", "/", 42)?;
+    /// value.remove_by_key_path_with_delim("logger/id", "/")?;
+    /// ```
+    pub fn remove_by_key_path_with_delim<P, D>(&mut self, path: P, delim: D) -> Result<Option<Self>>
+    where
+        P: AsRef<str>,
+        D: AsRef<str>,
+    {
+        fn inner(this: &mut Value, path: &str, delim: &str) -> Result<Option<Value>> {
+            if delim.is_empty() {
+                return Err(Error::EmptySeparator("remove", path.into()));
+            }
+
+            if path.is_empty() {
+                return this.remove_by_keys([""; 0]);
+            }
+
+            this.remove_by_keys(path.split(delim))
+        }
+
+        inner(self, path.as_ref(), delim.as_ref())
+    }
+
+    /// Update value for given key path represented as string with default keys level delimiter
+    /// [`DEFAULT_KEYS_SEPARATOR`]. The closure `f` will get previous value for the key path if any and return new one.
+    /// Unlike [`Value::set_by_key_path`], if [`Value`] was sealed then it will stay in [`SealedState::On`]: the
+    /// secret values will stay obfuscated (even if they were replaced) and all other values will be displayed as is.
+    /// If last key of the key path has sealed suffix then new value will be sealed as well. Return previous value for
+    /// same key path if any, the previous value is sealed same way, so its secret values stay obfuscated.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let mut value = Value::try_from(json!({
+    ///     "user": "user name",
+    ///     "token_sealed_": "secret"
+    /// }))?;
+    ///
+    /// value.seal("_sealed_");
+    /// value.update_sealed("token", |_| "rotated secret")?;
+    /// ```
+    #[inline]
+    pub fn update_sealed<P, F, T>(&mut self, path: P, f: F) -> Result<Option<Self>>
+    where
+        P: AsRef<str>,
+        F: FnOnce(Option<Self>) -> T,
+        T: Serialize,
+    {
+        self.update_sealed_with_delim(path, DEFAULT_KEYS_SEPARATOR, f)
+    }
+
+    /// Update value for given key path represented as string with given keys level delimiter. See
+    /// [`Value::update_sealed`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    pub fn update_sealed_with_delim<P, D, F, T>(
+        &mut self,
+        path: P,
+        delim: D,
+        f: F,
+    ) -> Result<Option<Self>>
+    where
+        P: AsRef<str>,
+        D: AsRef<str>,
+        F: FnOnce(Option<Self>) -> T,
+        T: Serialize,
+    {
+        let (path, delim) = (path.as_ref(), delim.as_ref());
+        if delim.is_empty() {
+            return Err(Error::EmptySeparator("update", path.into()));
+        }
+
+        if path.is_empty() {
+            return self.update_sealed_by_keys([""; 0], f);
+        }
+
+        self.update_sealed_by_keys(path.split(delim), f)
+    }
+
+    /// Update value for given key path represented as iterator. See [`Value::update_sealed`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If any errors will occur then error will be returned.
+    pub fn update_sealed_by_keys<I, K, F, T>(&mut self, keys: I, f: F) -> Result<Option<Self>>
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+        F: FnOnce(Option<Self>) -> T,
+        T: Serialize,
+    {
+        let keys: Vec<_> = keys
+            .into_iter()
+            .map(|k| crate::normalize_case(k.as_ref(), self.case_on).into_owned())
+            .collect();
+        let prev = find(&self.value, &keys).map(|v| Self {
+            value: v.clone(),
+            sealed: match self.sealed_state {
+                SealedState::On => find_sealed(self.sealed.as_ref(), &keys),
+                _ => None,
+            },
+            sealed_state: self.sealed_state,
+            sealed_suffix: self.sealed_suffix.clone(),
+            case_on: self.case_on,
+        });
+        let value = set(f(prev.clone()), self.case_on)?;
+
+        if SealedState::On != self.sealed_state {
+            self.set_by_keys(&keys, value)?;
+            return Ok(prev);
+        }
+
+        let Some((last, parents)) = keys.split_last() else {
+            self.value = value;
+            self.sealed = None;
+            if let (InnerValue::Object(ref map), false) =
+                (&self.value, self.sealed_suffix.is_empty())
+            {
+                let (v, s) = get_sealed(
+                    map,
+                    &self.sealed_suffix,
+                    self.case_on,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                );
+                self.value = v;
+                self.sealed = s;
+            }
+            return Ok(prev);
+        };
+
+        let mut node = &mut self.value;
+        for key in parents {
+            node = match node {
+                InnerValue::Object(m) => m.entry(key).or_insert_with(|| json!({})),
+                _ => return Err(Error::NotMap),
+            }
+        }
+        let InnerValue::Object(map) = node else {
+            return Err(Error::NotMap);
+        };
+
+        if is_secret(self.sealed.as_ref(), &keys) {
+            map.insert(last.clone(), value);
+            return Ok(prev);
+        }
+
+        let mut entry = ValueMap::default();
+        entry.insert(last.clone(), value);
+        let (entry, sealed) = if self.sealed_suffix.is_empty() {
+            (InnerValue::Object(entry), None)
+        } else {
+            get_sealed(
+                &entry,
+                &self.sealed_suffix,
+                self.case_on,
+                &mut Vec::new(),
+                &mut Vec::new(),
+            )
+        };
+
+        if let InnerValue::Object(entry) = entry {
+            for (key, val) in entry {
+                if let Some(InnerValue::Object(ref mut s)) = self.sealed {
+                    remove(s, parents, &key);
+                }
+                map.insert(key, val);
+            }
+        }
+
+        if let Some(InnerValue::Object(sealed)) = sealed {
+            let mut node = self.sealed.get_or_insert_with(|| json!({}));
+            for key in parents {
+                node = match node {
+                    InnerValue::Object(m) => m.entry(key).or_insert_with(|| json!({})),
+                    _ => return Ok(prev),
+                }
+            }
+            if let InnerValue::Object(m) = node {
+                m.extend(sealed);
+            }
+        }
+        Ok(prev)
+    }
+
     /// Return [`Value`] structure as a sequence of bytes.
     #[inline]
     pub fn as_bytes(&self) -> Vec<u8> {
         self.value.to_string().as_bytes().to_owned()
     }
 
+    /// Return pretty printed [`Value`] structure with obfuscated sealed values, same as display output. Unlike
+    /// [`ToString::to_string`], which panics if [`Display`] implementation fails, serialization errors are returned.
+    ///
+    /// # Errors
+    ///
+    /// If [`Value`] could not be serialized then error will be returned.
+    #[inline]
+    pub fn try_to_string(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.get_sealed())
+            .map_err(|e| Error::SerdeError(e, "Failed to serialize value".into()))
+    }
+
+    /// Return pretty printed [`Value`] structure limited according to given options (see [`PrettyOptions`]). Sealed
+    /// values are obfuscated the same way as for display output, so result is safe to be written to logs.
+    ///
+    /// # Errors
+    ///
+    /// If limited [`Value`] could not be serialized then error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::value::PrettyOptions;
+    ///
+    /// let output = value.pretty(&PrettyOptions {
+    ///     max_depth: Some(3),
+    ///     max_items: Some(10),
+    ///     ..Default::default()
+    /// })?;
+    /// ```
+    pub fn pretty(&self, opts: &PrettyOptions) -> Result<String> {
+        let value = limit_value(&self.get_sealed(), opts, 0);
+        serde_json::to_string_pretty(&value)
+            .map_err(|e| Error::SerdeError(e, "Failed to serialize value".into()))
+    }
+
+    /// Return [Graphviz](https://graphviz.org/) `DOT` representation of the keys tree. The values are shown in leaf
+    /// nodes, sealed values are obfuscated, marked with [`SEALED_MARKER`] and drawn dashed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// std::fs::write("config.dot", value.to_dot())?;
+    /// ```
+    ///
+    /// [`SEALED_MARKER`]: crate::render::SEALED_MARKER
+    #[cfg(feature = "render")]
+    #[inline]
+    pub fn to_dot(&self) -> String {
+        crate::render::dot(self, DEFAULT_KEYS_SEPARATOR, &Default::default())
+    }
+
+    /// Return changes (see [`ValueDiff`]) needed to get `other` [`Value`] from this one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use irx_config::{json, Value};
+    ///
+    /// let old = Value::try_from(json!({"id": 1}))?;
+    /// let new = Value::try_from(json!({"id": 2, "name": "node"}))?;
+    ///
+    /// println!("{}", old.diff(&new).render_colored(":"));
+    /// ```
+    #[cfg(feature = "diff")]
+    #[inline]
+    pub fn diff(&self, other: &Value) -> ValueDiff {
+        ValueDiff::new(self, other)
+    }
+
     /// Seal secret values in [`Value`] structure with given suffix. Such values will be obfuscated with `********`
     /// during display/debugging output. If not set then all values will be displayed as is.
     ///
@@ -452,27 +973,82 @@ impl Value {
     where
         S: AsRef<str>,
     {
-        fn inner<'a>(this: &'a mut Value, suffix: &str) -> &'a mut Value {
-            if SealedState::On == this.sealed_state {
-                return this;
-            }
+        self.seal_checked(suffix.as_ref());
+        self
+    }
 
-            this.sealed = None;
-            this.sealed_state = SealedState::On;
+    /// Same as [`Value::seal`], but return key paths which collide after suffix removal (e.g. both `password` and
+    /// `password_sealed_` keys are present). The sealed value always wins in such case.
+    pub(crate) fn seal_checked(&mut self, suffix: &str) -> Vec<Vec<String>> {
+        let mut collisions = Vec::new();
+        if SealedState::On == self.sealed_state {
+            return collisions;
+        }
 
-            if suffix.is_empty() {
-                return this;
-            }
+        self.sealed = None;
+        self.sealed_state = SealedState::On;
+        self.sealed_suffix = suffix.to_string();
 
-            if let InnerValue::Object(ref map) = this.value {
-                let (v, s) = get_sealed(map, suffix, this.case_on);
-                this.value = v;
-                this.sealed = s;
-            }
-            this
+        if suffix.is_empty() {
+            return collisions;
+        }
+
+        if let InnerValue::Object(ref map) = self.value {
+            let (v, s) = get_sealed(map, suffix, self.case_on, &mut Vec::new(), &mut collisions);
+            self.value = v;
+            self.sealed = s;
         }
+        collisions
+    }
 
-        inner(self, suffix.as_ref())
+    pub(crate) fn seal_if<F>(&mut self, delim: &str, detector: F)
+    where
+        F: Fn(&str, &Value) -> bool,
+    {
+        let case_on = self.case_on;
+        self.seal_leaves(|keys, value| {
+            let leaf = Value {
+                value: value.clone(),
+                case_on,
+                ..Default::default()
+            };
+            detector(&keys.join(delim), &leaf)
+        });
+    }
+
+    /// Seal all values under given key paths represented as keys (e.g. secrets declared by parser, see
+    /// [`crate::Parse::sealed_keys`]). The [`Value`] should be sealed already, otherwise nothing will be done.
+    pub(crate) fn seal_keys(&mut self, paths: &[Vec<String>]) {
+        if paths.is_empty() {
+            return;
+        }
+
+        let case_on = self.case_on;
+        let paths: Vec<Vec<_>> = paths
+            .iter()
+            .map(|p| {
+                p.iter()
+                    .map(|k| crate::normalize_case(k, case_on).into_owned())
+                    .collect()
+            })
+            .collect();
+        self.seal_leaves(|keys, _| paths.iter().any(|p| keys.starts_with(p)));
+    }
+
+    fn seal_leaves<F>(&mut self, mut detector: F)
+    where
+        F: FnMut(&[String], &InnerValue) -> bool,
+    {
+        if SealedState::On != self.sealed_state {
+            return;
+        }
+
+        let mut sealed = match self.sealed.take() {
+            Some(InnerValue::Object(m)) => m,
+            _ => ValueMap::default(),
+        };
+        seal_if(&self.value, &mut Vec::new(), &mut detector, &mut sealed);
+        self.sealed = (!sealed.is_empty()).then_some(InnerValue::Object(sealed));
     }
 
     /// Return `true` if [`Value`] is sealed, otherwise return `false`.
@@ -504,19 +1080,167 @@ impl Value {
         false
     }
 
-    fn get_sealed(&self) -> CowInnerValue {
+    pub(crate) fn get_sealed(&self) -> CowInnerValue<'_> {
         if SealedState::Mutated == self.sealed_state {
             return CowInnerValue::Owned(json!({}));
         }
 
         if let Some(ref s) = self.sealed {
+            if !s.is_object() {
+                return CowInnerValue::Owned(s.clone());
+            }
             if let InnerValue::Object(ref m) = self.value {
-                return CowInnerValue::Owned(merge_into_value_map(m.clone(), s, self.case_on));
+                return CowInnerValue::Owned(merge_into_value_map(
+                    m.clone(),
+                    s,
+                    self.case_on,
+                    &MergeRules::default(),
+                    &mut Vec::new(),
+                ));
             }
         }
         CowInnerValue::Borrowed(&self.value)
     }
 
+    #[inline]
+    pub(crate) fn as_inner(&self) -> &InnerValue {
+        &self.value
+    }
+
+    #[cfg(feature = "lint")]
+    #[inline]
+    pub(crate) fn has_secrets(&self) -> bool {
+        self.sealed.is_some()
+    }
+
+    pub(crate) fn update_inner<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut InnerValue) -> bool,
+    {
+        if f(&mut self.value) {
+            self.unseal();
+        }
+    }
+
+    pub(crate) fn apply_templates(&mut self, key: &str) {
+        let case_on = self.case_on;
+        let key = crate::normalize_case(key, case_on);
+        self.update_inner(|v| apply_templates(v, &key, case_on));
+    }
+
+    /// Merge `key:name` profile subtree over the rest of data and remove `key` subtree (see
+    /// [`crate::ConfigBuilder::profile`]).
+    pub(crate) fn apply_profile(&mut self, key: &str, name: &str, rules: &MergeRules) {
+        let case_on = self.case_on;
+        let key = crate::normalize_case(key, case_on);
+        let name = crate::normalize_case(name, case_on);
+        self.update_inner(|v| apply_profile(v, &key, &name, case_on, rules));
+    }
+
+    /// Expand `${key:path}` references in string values with values of referenced key paths (see
+    /// [`crate::ConfigBuilder::interpolation`]) and/or `${ENV:NAME}`, `$NAME` references with values of environment
+    /// variables (see [`crate::ConfigBuilder::env_interpolation`]). The sealed values: the ones with keys ending with
+    /// given suffix or under given key paths, are left as is.
+    pub(crate) fn interpolate(
+        &mut self,
+        delim: &str,
+        keys: bool,
+        env: bool,
+        suffix: &str,
+        sealed: &[Vec<String>],
+    ) -> Result<()> {
+        let source = self.value.clone();
+        let case_on = self.case_on;
+        let mut interpolator = Interpolator {
+            source: &source,
+            delim,
+            case_on,
+            keys,
+            env,
+            suffix: crate::normalize_case(suffix, case_on).into_owned(),
+            sealed: sealed
+                .iter()
+                .map(|p| {
+                    p.iter()
+                        .map(|k| crate::normalize_case(k, case_on))
+                        .collect::<Vec<_>>()
+                        .join(delim)
+                })
+                .collect(),
+            resolved: BTreeMap::new(),
+            stack: Vec::new(),
+        };
+        let mut result = Ok(());
+        self.update_inner(|v| {
+            interpolator.walk(v, "").unwrap_or_else(|e| {
+                result = Err(e);
+                false
+            })
+        });
+        result
+    }
+
+    /// Return key paths of sealed values joined with given delimiter in sorted order. If [`Value`] was mutated after
+    /// sealing then all key paths are returned.
+    pub(crate) fn sealed_key_paths(&self, delim: &str) -> Vec<String> {
+        fn inner(value: &InnerValue, prefix: &str, delim: &str, result: &mut Vec<String>) {
+            let InnerValue::Object(m) = value else {
+                result.push(prefix.to_string());
+                return;
+            };
+            for (k, v) in m {
+                let path = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    [prefix, delim, k].concat()
+                };
+                inner(v, &path, delim, result);
+            }
+        }
+
+        let mut result = Vec::new();
+        match self.sealed_state {
+            SealedState::None => (),
+            SealedState::On => {
+                if let Some(ref sealed) = self.sealed {
+                    inner(sealed, "", delim, &mut result);
+                }
+            }
+            SealedState::Mutated => result = self.key_paths(delim),
+        }
+        result.sort();
+        result
+    }
+
+    #[cfg(any(test, feature = "export", feature = "lint", feature = "render"))]
+    pub(crate) fn is_secret(&self, keys: &[String]) -> bool {
+        SealedState::On == self.sealed_state && is_secret(self.sealed.as_ref(), keys)
+    }
+
+    /// Keep only values which key paths are matched by any of given patterns (`*` matches any single key), the
+    /// sealed values are dropped. If [`Value`] was mutated after sealing then nothing is kept.
+    pub(crate) fn restricted(&self, patterns: &[Vec<String>]) -> Self {
+        let patterns: Vec<Vec<_>> = patterns
+            .iter()
+            .map(|p| {
+                p.iter()
+                    .map(|k| crate::normalize_case(k, self.case_on))
+                    .collect()
+            })
+            .collect();
+        let value = match self.sealed_state {
+            SealedState::Mutated => None,
+            _ => restrict(&self.value, &mut Vec::new(), &patterns, &|k| {
+                is_secret(self.sealed.as_ref(), k)
+            }),
+        };
+        Self {
+            value: value.unwrap_or_else(|| json!({})),
+            case_on: self.case_on,
+            ..Default::default()
+        }
+    }
+
     fn unseal(&mut self) {
         if SealedState::On == self.sealed_state {
             self.sealed_state = SealedState::Mutated;
@@ -532,6 +1256,7 @@ impl Default for Value {
             value: json!({}),
             sealed: None,
             sealed_state: SealedState::None,
+            sealed_suffix: Default::default(),
             case_on: true,
         }
     }
@@ -583,23 +1308,84 @@ impl Debug for Value {
 }
 
 impl Display for Value {
+    /// The serialization errors are written as `<error: ...>` instead of returning [`std::fmt::Error`], so formatting
+    /// never panics (see [`Value::try_to_string`]).
     #[inline]
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        let value = serde_json::to_string_pretty(&self.get_sealed()).map_err(|_| FmtError)?;
-        f.write_str(&value)
+        match self.try_to_string() {
+            Ok(value) => f.write_str(&value),
+            Err(e) => f.write_fmt(format_args!("<error: {e}>")),
+        }
+    }
+}
+
+/// The rules used to merge [`Value`] structures during (re)load.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MergeRules {
+    arrays: ArrayMerge,
+    strategies: Vec<(Vec<String>, MergeStrategy)>,
+}
+
+impl MergeRules {
+    pub(crate) fn new(arrays: ArrayMerge) -> Self {
+        Self {
+            arrays,
+            strategies: Default::default(),
+        }
+    }
+
+    pub(crate) fn strategy_for(&mut self, keys: Vec<String>, strategy: MergeStrategy) {
+        self.strategies.retain(|(k, _)| *k != keys);
+        self.strategies.push((keys, strategy));
+    }
+
+    fn strategy(&self, path: &[String], case_on: bool) -> MergeStrategy {
+        self.strategies
+            .iter()
+            .find(|(keys, _)| {
+                keys.len() == path.len()
+                    && keys
+                        .iter()
+                        .zip(path)
+                        .all(|(k, p)| crate::normalize_case(k, case_on) == p.as_str())
+            })
+            .map_or(MergeStrategy::Merge, |(_, s)| *s)
+    }
+
+    fn arrays(&self, strategy: MergeStrategy) -> ArrayMerge {
+        match strategy {
+            MergeStrategy::Merge => self.arrays,
+            MergeStrategy::Replace => ArrayMerge::Replace,
+            MergeStrategy::Append => ArrayMerge::Append,
+            MergeStrategy::UniqueUnion => ArrayMerge::UniqueUnion,
+        }
     }
 }
 
-fn merge_into_value_map(dst: ValueMap, src: &InnerValue, case_on: bool) -> InnerValue {
+fn merge_into_value_map(
+    dst: ValueMap,
+    src: &InnerValue,
+    case_on: bool,
+    rules: &MergeRules,
+    path: &mut Vec<String>,
+) -> InnerValue {
     if let InnerValue::Object(src) = src {
         let mut result = dst;
         for (k, v) in src {
             let norm_key = crate::normalize_case(k, case_on);
-            let val = if let Some(InnerValue::Object(m)) = result.get(norm_key.as_ref()) {
-                merge_into_value_map(m.clone(), v, case_on)
-            } else {
-                v.clone()
+            path.push(norm_key.to_string());
+            let strategy = rules.strategy(path, case_on);
+            let val = match (result.get(norm_key.as_ref()), v) {
+                _ if MergeStrategy::Replace == strategy => v.clone(),
+                (Some(InnerValue::Object(m)), _) => {
+                    merge_into_value_map(m.clone(), v, case_on, rules, path)
+                }
+                (Some(InnerValue::Array(d)), InnerValue::Array(s)) => {
+                    merge_arrays(d, s, rules.arrays(strategy))
+                }
+                _ => v.clone(),
             };
+            path.pop();
 
             result.insert(norm_key.into_owned(), val);
         }
@@ -608,6 +1394,387 @@ fn merge_into_value_map(dst: ValueMap, src: &InnerValue, case_on: bool) -> Inner
     src.clone()
 }
 
+fn apply_templates(value: &mut InnerValue, key: &str, case_on: bool) -> bool {
+    let InnerValue::Object(map) = value else {
+        return false;
+    };
+
+    let mut is_changed = false;
+    if let Some(template) = map.remove(key) {
+        is_changed = true;
+        if let InnerValue::Object(t) = template {
+            for v in map.values_mut().filter(|v| v.is_object()) {
+                *v = merge_into_value_map(
+                    t.clone(),
+                    v,
+                    case_on,
+                    &MergeRules::default(),
+                    &mut Vec::new(),
+                );
+            }
+        }
+    }
+
+    for v in map.values_mut() {
+        is_changed |= apply_templates(v, key, case_on);
+    }
+    is_changed
+}
+
+fn apply_profile(
+    value: &mut InnerValue,
+    key: &str,
+    name: &str,
+    case_on: bool,
+    rules: &MergeRules,
+) -> bool {
+    let InnerValue::Object(map) = value else {
+        return false;
+    };
+    let Some(profiles) = map.remove(key) else {
+        return false;
+    };
+
+    if let Some(profile) = profiles.get(name) {
+        let merged = merge_into_value_map(mem::take(map), profile, case_on, rules, &mut Vec::new());
+        *value = merged;
+    }
+    true
+}
+
+struct Interpolator<'a> {
+    source: &'a InnerValue,
+    delim: &'a str,
+    case_on: bool,
+    keys: bool,
+    env: bool,
+    suffix: String,
+    sealed: Vec<String>,
+    resolved: BTreeMap<String, InnerValue>,
+    stack: Vec<String>,
+}
+
+impl Interpolator<'_> {
+    fn walk(&mut self, value: &mut InnerValue, path: &str) -> Result<bool> {
+        let join = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                [path, self.delim, key].concat()
+            }
+        };
+
+        let mut is_changed = false;
+        match value {
+            InnerValue::Object(map) => {
+                for (k, v) in map.iter_mut() {
+                    is_changed |= self.walk(v, &join(k))?;
+                }
+            }
+            InnerValue::Array(items) => {
+                for (i, v) in items.iter_mut().enumerate() {
+                    is_changed |= self.walk(v, &join(&i.to_string()))?;
+                }
+            }
+            InnerValue::String(text)
+                if (text.contains(REFERENCE_START) || (self.env && text.contains('$')))
+                    && !self.is_sealed(path) =>
+            {
+                let expanded = self.expand(text, path)?;
+                if expanded != *value {
+                    *value = expanded;
+                    is_changed = true;
+                }
+            }
+            _ => (),
+        }
+        Ok(is_changed)
+    }
+
+    fn is_sealed(&self, path: &str) -> bool {
+        let suffixed =
+            !self.suffix.is_empty() && path.split(self.delim).any(|k| k.ends_with(&self.suffix));
+        suffixed
+            || self.sealed.iter().any(|p| {
+                path.strip_prefix(p.as_str())
+                    .is_some_and(|r| r.is_empty() || r.starts_with(self.delim))
+            })
+    }
+
+    fn expand(&mut self, text: &str, path: &str) -> Result<InnerValue> {
+        let single = text
+            .strip_prefix(REFERENCE_START)
+            .and_then(|t| t.strip_suffix(REFERENCE_END))
+            .filter(|r| !r.contains(REFERENCE_END));
+        if let Some(reference) = single {
+            return self.reference(reference, path);
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('$') {
+            result.push_str(&rest[..start]);
+            let tail = &rest[start + 1..];
+            let escaped = tail
+                .strip_prefix('$')
+                .filter(|t| self.env || t.starts_with('{'));
+            if let Some(tail) = escaped {
+                result.push('$');
+                rest = tail;
+                continue;
+            }
+
+            let reference = tail
+                .strip_prefix('{')
+                .and_then(|t| t.find(REFERENCE_END).map(|end| (&t[..end], &t[end + 1..])));
+            if let Some((reference, tail)) = reference {
+                match self.reference(reference, path)? {
+                    InnerValue::String(s) => result.push_str(&s),
+                    v => result.push_str(&v.to_string()),
+                }
+                rest = tail;
+                continue;
+            }
+
+            let is_name =
+                self.env && tail.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+            let len = if is_name {
+                tail.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(tail.len())
+            } else {
+                0
+            };
+            if len > 0 {
+                result.push_str(&env_var(&tail[..len], path)?);
+            } else {
+                result.push('$');
+            }
+            rest = &tail[len..];
+        }
+        result.push_str(rest);
+        Ok(InnerValue::String(result))
+    }
+
+    fn reference(&mut self, reference: &str, path: &str) -> Result<InnerValue> {
+        match reference.strip_prefix(ENV_REFERENCE_PREFIX) {
+            Some(name) if self.env => Ok(InnerValue::String(env_var(name, path)?)),
+            _ if self.keys => self.resolve(reference, path),
+            _ => Ok(InnerValue::String(
+                [REFERENCE_START, reference, "}"].concat(),
+            )),
+        }
+    }
+
+    fn resolve(&mut self, reference: &str, path: &str) -> Result<InnerValue> {
+        if let Some(value) = self.resolved.get(reference) {
+            return Ok(value.clone());
+        }
+        if self.stack.iter().any(|r| r == reference) {
+            let mut chain = self.stack.clone();
+            chain.push(reference.to_string());
+            return Err(Error::CyclicReference(path.into(), chain.join(" -> ")));
+        }
+
+        let mut value = reference
+            .split(self.delim)
+            .try_fold(self.source, |v, k| match v {
+                InnerValue::Object(map) => map.get(crate::normalize_case(k, self.case_on).as_ref()),
+                _ => None,
+            })
+            .cloned()
+            .ok_or_else(|| Error::MissingReference(path.into(), reference.into()))?;
+        self.stack.push(reference.to_string());
+        let result = self.walk(&mut value, reference);
+        self.stack.pop();
+        result?;
+        self.resolved.insert(reference.to_string(), value.clone());
+        Ok(value)
+    }
+}
+
+fn env_var(name: &str, path: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| Error::MissingEnvVar(path.into(), name.into()))
+}
+
+fn limit_value(value: &InnerValue, opts: &PrettyOptions, depth: usize) -> InnerValue {
+    let is_deep = opts.max_depth.is_some_and(|d| depth > d);
+    match value {
+        InnerValue::Object(_) if is_deep => json!("{...}"),
+        InnerValue::Array(_) if is_deep => json!("[...]"),
+        InnerValue::Object(m) => InnerValue::Object(
+            m.iter()
+                .map(|(k, v)| (k.clone(), limit_value(v, opts, depth + 1)))
+                .collect(),
+        ),
+        InnerValue::Array(a) => {
+            let max = opts.max_items.unwrap_or(a.len());
+            let mut result: Vec<_> = a
+                .iter()
+                .take(max)
+                .map(|v| limit_value(v, opts, depth + 1))
+                .collect();
+            if a.len() > max {
+                result.push(json!(format!("... ({} more)", a.len() - max)));
+            }
+            InnerValue::Array(result)
+        }
+        InnerValue::String(s) => match opts.max_string_len {
+            Some(max) if s.chars().count() > max => {
+                json!(format!("{}...", s.chars().take(max).collect::<String>()))
+            }
+            _ => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+fn merge_arrays(dst: &[InnerValue], src: &[InnerValue], arrays: ArrayMerge) -> InnerValue {
+    let mut result = src.to_vec();
+    match arrays {
+        ArrayMerge::Replace => (),
+        ArrayMerge::Append => result.extend_from_slice(dst),
+        ArrayMerge::UniqueUnion => {
+            for v in dst {
+                if !result.contains(v) {
+                    result.push(v.clone());
+                }
+            }
+        }
+    }
+    InnerValue::Array(result)
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<_> = right.chars().collect();
+    let mut prev: Vec<_> = (0..=right.len()).collect();
+    for (i, l) in left.chars().enumerate() {
+        let mut curr = vec![i + 1; right.len() + 1];
+        for (j, r) in right.iter().enumerate() {
+            let cost = usize::from(l != *r);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[right.len()]
+}
+
+fn find<'a>(value: &'a InnerValue, keys: &[String]) -> Option<&'a InnerValue> {
+    keys.iter().try_fold(value, |v, k| match v {
+        InnerValue::Object(m) => m.get(k),
+        _ => None,
+    })
+}
+
+fn find_mut<'a>(value: &'a mut InnerValue, keys: &[String]) -> Option<&'a mut InnerValue> {
+    keys.iter().try_fold(value, |v, k| match v {
+        InnerValue::Object(m) => m.get_mut(k),
+        _ => None,
+    })
+}
+
+fn find_sealed(sealed: Option<&InnerValue>, keys: &[String]) -> Option<InnerValue> {
+    let mut node = sealed?;
+    for key in keys {
+        node = match node {
+            InnerValue::Object(m) => m.get(key)?,
+            _ => break,
+        }
+    }
+    Some(node.clone())
+}
+
+fn is_secret(sealed: Option<&InnerValue>, keys: &[String]) -> bool {
+    let Some(mut node) = sealed else {
+        return false;
+    };
+
+    for key in keys {
+        node = match node {
+            InnerValue::Object(m) => match m.get(key) {
+                Some(v) => v,
+                None => return false,
+            },
+            _ => return true,
+        }
+    }
+    !node.is_object()
+}
+
+fn restrict<F>(
+    value: &InnerValue,
+    keys: &mut Vec<String>,
+    patterns: &[Vec<Cow<str>>],
+    is_secret: &F,
+) -> Option<InnerValue>
+where
+    F: Fn(&[String]) -> bool,
+{
+    if is_secret(keys) {
+        return None;
+    }
+
+    let allowed = patterns.iter().any(|p| {
+        p.len() <= keys.len() && p.iter().zip(keys.iter()).all(|(p, k)| p == "*" || p == k)
+    });
+    let InnerValue::Object(map) = value else {
+        return allowed.then(|| value.clone());
+    };
+
+    let mut result = ValueMap::new();
+    for (key, val) in map {
+        keys.push(key.clone());
+        if let Some(v) = restrict(val, keys, patterns, is_secret) {
+            result.insert(key.clone(), v);
+        }
+        keys.pop();
+    }
+    (allowed || !result.is_empty()).then_some(InnerValue::Object(result))
+}
+
+fn seal_if<F>(value: &InnerValue, keys: &mut Vec<String>, detector: &mut F, sealed: &mut ValueMap)
+where
+    F: FnMut(&[String], &InnerValue) -> bool,
+{
+    if let InnerValue::Object(m) = value {
+        for (k, v) in m {
+            keys.push(k.clone());
+            seal_if(v, keys, detector, sealed);
+            keys.pop();
+        }
+        return;
+    }
+
+    let Some((key, parents)) = keys.split_last() else {
+        return;
+    };
+    if !detector(keys, value) {
+        return;
+    }
+
+    let mut node = sealed;
+    for k in parents {
+        let entry = node
+            .entry(k.clone())
+            .or_insert_with(|| InnerValue::Object(Default::default()));
+        node = match entry {
+            InnerValue::Object(m) => m,
+            _ => return,
+        };
+    }
+    node.insert(key.clone(), json!(OBFUSCATED));
+}
+
+fn remove(sealed: &mut ValueMap, parents: &[String], key: &str) {
+    let mut node = sealed;
+    for k in parents {
+        node = match node.get_mut(k) {
+            Some(InnerValue::Object(m)) => m,
+            _ => return,
+        }
+    }
+    node.remove(key);
+}
+
 fn unicase_value_map(map: &ValueMap) -> InnerValue {
     let mut result = ValueMap::default();
     for (k, v) in map {
@@ -636,23 +1803,40 @@ fn set<T: Serialize>(value: T, case_on: bool) -> Result<InnerValue> {
     })
 }
 
-fn get_sealed(value: &ValueMap, suffix: &str, case_on: bool) -> (InnerValue, Option<InnerValue>) {
+fn get_sealed(
+    value: &ValueMap,
+    suffix: &str,
+    case_on: bool,
+    keys: &mut Vec<String>,
+    collisions: &mut Vec<Vec<String>>,
+) -> (InnerValue, Option<InnerValue>) {
     let mut result = ValueMap::default();
     let mut sealed = ValueMap::default();
     let uni_suffix = crate::normalize_case(suffix, case_on);
     for (k, v) in value {
         let key = crate::normalize_case(k, case_on);
+        let norm_key = key.strip_suffix(uni_suffix.as_ref()).unwrap_or(&key);
+        let is_sealed = key.len() != norm_key.len();
+        keys.push(norm_key.to_string());
+        if result.contains_key(norm_key) {
+            collisions.push(keys.clone());
+            if !is_sealed {
+                keys.pop();
+                continue;
+            }
+        }
+
         let (val, opt) = if let Some(InnerValue::Object(nested)) = value.get(key.as_ref()) {
-            let (v, s) = get_sealed(nested, suffix, case_on);
+            let (v, s) = get_sealed(nested, suffix, case_on, keys, collisions);
             (CowInnerValue::Owned(v), s)
         } else {
             (CowInnerValue::Borrowed(v), None)
         };
+        keys.pop();
 
-        let norm_key = key.trim_end_matches(uni_suffix.as_ref());
         result.insert(norm_key.to_string(), val.into_owned());
-        if key.len() != norm_key.len() {
-            sealed.insert(norm_key.to_string(), json!("********"));
+        if is_sealed {
+            sealed.insert(norm_key.to_string(), json!(OBFUSCATED));
         } else if let Some(s) = opt {
             sealed.insert(norm_key.to_string(), s);
         }
@@ -0,0 +1,350 @@
+//! This module provide [`ConfigWatcher`] which watches files used by parsers of [`Config`] (see
+//! [`Parse::watch_paths`]) and (re)loads configuration automatically on any changes of them.
+//!
+//! The parent directories of the files are watched, so files could be atomically replaced (as most editors and
+//! deployment tools do) or created later. The list of watched files is updated after each (re)load.
+//!
+//! **NOTE:** [`Config`] is not [`Send`], so watcher should be created in the thread where [`Config`] was built.
+//!
+//! The leases returned by parsers (see [`Parse::leases`]) are renewed by the watcher before they expire (see
+//! [`ConfigWatcher::renew_margin`]), the layers with leases which could not be renewed are reloaded.
+//!
+//! The watcher could be stopped from any thread or task via [`StopHandle`], so it terminates cleanly with the
+//! application. For example, it could be tied to `tokio::signal::ctrl_c()` or `CancellationToken::cancelled()`
+//! future via [`StopHandle::stop_on`].
+//!
+//! To enable that functionality one has to add the following to Cargo.toml:
+//!
+//! ```toml
+//! [dependencies]
+//! irx-config = { version = "3.5", features = ["watch"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use irx_config::parsers::json;
+//! use irx_config::watch::ConfigWatcher;
+//! use irx_config::ConfigBuilder;
+//!
+//! let config = ConfigBuilder::load_one(
+//!     json::ParserBuilder::default()
+//!         .default_path("config.json")
+//!         .build()?,
+//! )?;
+//!
+//! let watcher = ConfigWatcher::new(config)?;
+//! tokio::spawn(watcher.stop_handle().stop_on(tokio::signal::ctrl_c()));
+//!
+//! // Returns after `Ctrl+C`.
+//! watcher.run(|result| {
+//!     match result {
+//!         Ok(config) => println!("Reloaded: {config}"),
+//!         Err(e) => eprintln!("Failed to reload: {e}"),
+//!     }
+//!     true
+//! })?;
+//! ```
+//!
+//! [`Parse::leases`]: crate::Parse::leases
+//! [`Parse::watch_paths`]: crate::Parse::watch_paths
+
+use crate::{Config, Value};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::BTreeSet,
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    result::Result as StdResult,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    time::{Duration, Instant},
+};
+
+/// The default time to wait for more changes before (re)load.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The default time before expiration of leases to renew them.
+pub const DEFAULT_RENEW_MARGIN: Duration = Duration::from_secs(30);
+
+const RENEW_RETRY: Duration = Duration::from_secs(5);
+
+/// A result type for watcher errors.
+pub type Result<T> = StdResult<T, Error>;
+
+/// All errors for configuration watcher.
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to watch files")]
+    Notify(#[from] notify::Error),
+    #[error("Failed to reload configuration")]
+    Reload(#[from] crate::Error),
+    #[error("Files watcher was stopped")]
+    Stopped,
+}
+
+enum Message {
+    Event(notify::Result<Event>),
+    Stop,
+}
+
+/// The handle to stop [`ConfigWatcher`] from any thread or task (see [`ConfigWatcher::stop_handle`]).
+#[derive(Clone, Debug)]
+pub struct StopHandle {
+    tx: Sender<Message>,
+}
+
+impl StopHandle {
+    /// Stop the watcher: the blocked [`ConfigWatcher::wait`] will return [`Error::Stopped`] and
+    /// [`ConfigWatcher::run`] will return `Ok(())`. Does nothing if the watcher was dropped already.
+    #[inline]
+    pub fn stop(&self) {
+        let _ = self.tx.send(Message::Stop);
+    }
+
+    /// Wait for given shutdown signal future (e.g. `tokio::signal::ctrl_c()` or `CancellationToken::cancelled()`)
+    /// and stop the watcher. The returned future could be spawned on any async runtime.
+    pub async fn stop_on<F: Future>(self, signal: F) {
+        signal.await;
+        self.stop();
+    }
+}
+
+/// The watcher which (re)loads [`Config`] on changes of files used by its parsers.
+pub struct ConfigWatcher {
+    config: Config,
+    watcher: RecommendedWatcher,
+    tx: Sender<Message>,
+    events: Receiver<Message>,
+    stopped: bool,
+    files: BTreeSet<PathBuf>,
+    dirs: BTreeSet<PathBuf>,
+    debounce: Duration,
+    renew_margin: Duration,
+    renew_after: Option<Instant>,
+}
+
+impl ConfigWatcher {
+    /// Create [`ConfigWatcher`] for given [`Config`] and start watching its files.
+    ///
+    /// # Errors
+    ///
+    /// If files watching could not be started then error will be returned.
+    pub fn new(config: Config) -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher({
+            let tx = tx.clone();
+            move |e| {
+                let _ = tx.send(Message::Event(e));
+            }
+        })?;
+        let mut result = Self {
+            config,
+            watcher,
+            tx,
+            events,
+            stopped: false,
+            files: Default::default(),
+            dirs: Default::default(),
+            debounce: DEFAULT_DEBOUNCE,
+            renew_margin: DEFAULT_RENEW_MARGIN,
+            renew_after: None,
+        };
+        result.rewatch()?;
+        Ok(result)
+    }
+
+    /// Set time to wait for more changes before (re)load. Default is [`DEFAULT_DEBOUNCE`].
+    #[inline]
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set time before expiration of leases (see [`Config::renew_leases`]) to renew them. Default is
+    /// [`DEFAULT_RENEW_MARGIN`].
+    #[inline]
+    pub fn renew_margin(mut self, margin: Duration) -> Self {
+        self.renew_margin = margin;
+        self
+    }
+
+    /// Get handle to stop the watcher from any thread or task.
+    #[inline]
+    pub fn stop_handle(&self) -> StopHandle {
+        StopHandle {
+            tx: self.tx.clone(),
+        }
+    }
+
+    /// Get reference to watched [`Config`].
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Get watched [`Config`] back.
+    #[inline]
+    pub fn into_config(self) -> Config {
+        self.config
+    }
+
+    /// Block until any watched file will be changed or any lease will be rotated, (re)load configuration and return
+    /// reference to it. The leases which could be renewed are renewed silently.
+    ///
+    /// # Errors
+    ///
+    /// If (re)load failed then error will be returned, the previously loaded configuration data will be kept.
+    #[inline]
+    pub fn wait(&mut self) -> Result<&Config> {
+        self.wait_deadline(None)?.ok_or(Error::Stopped)
+    }
+
+    /// Same as [`ConfigWatcher::wait`], but wait for changes not longer than given timeout. If there were no changes
+    /// then `Ok(None)` will be returned.
+    ///
+    /// # Errors
+    ///
+    /// If (re)load failed then error will be returned, the previously loaded configuration data will be kept.
+    #[inline]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Result<Option<&Config>> {
+        self.wait_deadline(Some(Instant::now() + timeout))
+    }
+
+    /// Watch files and call `f` after each (re)load attempt until `f` returns `false` or the watcher will be stopped
+    /// (see [`StopHandle`]).
+    ///
+    /// # Errors
+    ///
+    /// If files watching failed then error will be returned.
+    pub fn run<F>(mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(Result<&Config>) -> bool,
+    {
+        loop {
+            let proceed = match self.wait() {
+                Ok(c) => f(Ok(c)),
+                Err(e @ Error::Reload(_)) => f(Err(e)),
+                Err(Error::Stopped) => return Ok(()),
+                Err(e) => return Err(e),
+            };
+            if !proceed {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Watch files and send (re)loaded configuration data to given channel until receiver will be dropped or the
+    /// watcher will be stopped (see [`StopHandle`]). The failed (re)load attempts are sent as errors.
+    ///
+    /// # Errors
+    ///
+    /// If files watching failed then error will be returned.
+    pub fn run_with_sender(self, tx: Sender<Result<Value>>) -> Result<()> {
+        self.run(|result| tx.send(result.map(|c| c.get_value().clone())).is_ok())
+    }
+
+    fn wait_deadline(&mut self, deadline: Option<Instant>) -> Result<Option<&Config>> {
+        if self.stopped {
+            return Err(Error::Stopped);
+        }
+
+        loop {
+            loop {
+                let renewal = self
+                    .config
+                    .next_renewal()
+                    .map(|r| r.checked_sub(self.renew_margin).unwrap_or(r))
+                    .map(|r| self.renew_after.map_or(r, |a| a.max(r)));
+                let message = match deadline.into_iter().chain(renewal).min() {
+                    None => self.events.recv().map_err(|_| Error::Stopped)?,
+                    Some(d) => match self
+                        .events
+                        .recv_timeout(d.saturating_duration_since(Instant::now()))
+                    {
+                        Ok(m) => m,
+                        Err(RecvTimeoutError::Timeout) => {
+                            if renewal.is_some_and(|r| r <= Instant::now()) {
+                                let result = self.config.renew_leases(self.renew_margin);
+                                self.renew_after =
+                                    result.is_err().then(|| Instant::now() + RENEW_RETRY);
+                                if result? {
+                                    self.rewatch()?;
+                                    return Ok(Some(&self.config));
+                                }
+                                continue;
+                            }
+                            return Ok(None);
+                        }
+                        Err(RecvTimeoutError::Disconnected) => return Err(Error::Stopped),
+                    },
+                };
+
+                match message {
+                    Message::Event(e) => {
+                        if self.is_relevant(&e?) {
+                            break;
+                        }
+                    }
+                    Message::Stop => {
+                        self.stopped = true;
+                        return Err(Error::Stopped);
+                    }
+                }
+            }
+
+            while let Ok(message) = self.events.recv_timeout(self.debounce) {
+                if let Message::Stop = message {
+                    self.stopped = true;
+                    return Err(Error::Stopped);
+                }
+            }
+            let result = self.config.reload().map(|_| ());
+            self.rewatch()?;
+            result?;
+            if !self.config.is_suppressed() {
+                return Ok(Some(&self.config));
+            }
+        }
+    }
+
+    fn is_relevant(&self, event: &Event) -> bool {
+        let kind = event.kind;
+        (kind.is_create() || kind.is_modify() || kind.is_remove())
+            && event.paths.iter().any(|p| self.files.contains(p))
+    }
+
+    fn rewatch(&mut self) -> Result<()> {
+        let files: BTreeSet<_> = self
+            .config
+            .watch_paths()
+            .iter()
+            .filter_map(|p| absolute(p))
+            .collect();
+        let dirs: BTreeSet<_> = files
+            .iter()
+            .filter_map(|p| p.parent().map(Path::to_path_buf))
+            .collect();
+
+        for dir in self.dirs.difference(&dirs) {
+            let _ = self.watcher.unwatch(dir);
+        }
+        for dir in dirs.difference(&self.dirs) {
+            self.watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        self.files = files;
+        self.dirs = dirs;
+        Ok(())
+    }
+}
+
+fn absolute(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?;
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    Some(fs::canonicalize(parent).ok()?.join(name))
+}
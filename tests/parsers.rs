@@ -1,12 +1,18 @@
-#[cfg(all(feature = "env", feature = "json", feature = "yaml", feature = "cmd"))]
+#[cfg(all(
+    feature = "env",
+    feature = "json",
+    feature = "yaml",
+    feature = "cmd",
+    feature = "testing"
+))]
 mod integration {
     use clap::{Arg, ArgAction, Command};
     use irx_config::{
         json,
         parsers::{cmd, env, json, toml, yaml},
+        testing::with_env,
         AnyResult, ConfigBuilder, MergeCase, Value,
     };
-    use std::env as StdEnv;
 
     #[macro_export]
     macro_rules! resource_dir {
@@ -89,12 +95,14 @@ mod integration {
             .prefix_option("prefix")
             .build()?;
 
-        let config = ConfigBuilder::default()
-            .append_parser(cmd_parser)
-            .append_parser(json_parser)
-            .append_parser(yaml_parser)
-            .append_parser(env_parser)
-            .load()?;
+        let config = with_env(&[("APP_LOGGER__TAG", "logger env file tag")], || {
+            ConfigBuilder::default()
+                .append_parser(cmd_parser)
+                .append_parser(json_parser)
+                .append_parser(yaml_parser)
+                .append_parser(env_parser)
+                .load()
+        })?;
         println!("{config}");
         assert_eq!(expected, *config.get_value());
         Ok(())
@@ -154,8 +162,6 @@ mod integration {
             .path_option("json-config")
             .build()?;
 
-        StdEnv::set_var("APP_SETTINGS__NAME", "name from env");
-        StdEnv::set_var("APP_LOGGER__TAG", "logger env file tag");
         let mut builder = env::ParserBuilder::default();
         builder.default_prefix("APP_").prefix_option("prefix");
         if let Some(c) = env_case.into() {
@@ -169,7 +175,13 @@ mod integration {
         if let Some(m) = merge_case.into() {
             builder = builder.merge_case(m)
         }
-        let config = builder.load()?;
+        let config = with_env(
+            &[
+                ("APP_SETTINGS__NAME", "name from env"),
+                ("APP_LOGGER__TAG", "logger env file tag"),
+            ],
+            || builder.load(),
+        )?;
         println!("{config}");
         assert_eq!(
             expected,
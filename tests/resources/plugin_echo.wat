@@ -0,0 +1,8 @@
+(module
+  (memory (export "memory") 1)
+  (func (export "irx_alloc") (param $len i32) (result i32)
+    (i32.const 16))
+  (func (export "irx_parse") (param $ptr i32) (param $len i32) (result i64)
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+      (i64.extend_i32_u (local.get $len)))))